@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use grid_forge::{GridMap2D, GridPosition, GridSize, GridTile, TileData};
+
+struct NoiseTileData {
+    _value: f32,
+}
+impl TileData for NoiseTileData {}
+
+/// Some costly per-position work, standing in for noise sampling.
+fn sample_noise(position: GridPosition) -> GridTile<NoiseTileData> {
+    let mut value = (*position.x() as f32).sin() * (*position.y() as f32).cos();
+    for _ in 0..200 {
+        value = (value * 1.618 + 0.5).sin();
+    }
+    GridTile::new(position, NoiseTileData { _value: value })
+}
+
+fn fill_serial_500(c: &mut Criterion) {
+    c.bench_function("fill_serial_500", |b| {
+        b.iter(|| {
+            let mut map = GridMap2D::<NoiseTileData>::new(GridSize::new_xy(500, 500));
+            map.fill_empty_using(sample_noise);
+            black_box(map);
+        });
+    });
+}
+
+fn fill_parallel_500(c: &mut Criterion) {
+    c.bench_function("fill_parallel_500", |b| {
+        b.iter(|| {
+            let mut map = GridMap2D::<NoiseTileData>::new(GridSize::new_xy(500, 500));
+            map.fill_empty_using_par(sample_noise);
+            black_box(map);
+        });
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = fill_serial_500, fill_parallel_500
+}
+criterion_main!(benches);
@@ -1,14 +1,20 @@
 use std::{error::Error, fmt::Display};
 
+use crate::map::GridSize;
+use crate::tile::identifiable::builders::TileBuilderError;
+use crate::tile::GridPosition;
+
 #[derive(Debug)]
 pub struct BuilderError {
     missing_fields: Vec<&'static str>,
+    invalid_fields: Vec<(&'static str, &'static str)>,
 }
 
 impl BuilderError {
     pub(crate) fn new() -> Self {
         Self {
             missing_fields: Vec::new(),
+            invalid_fields: Vec::new(),
         }
     }
 
@@ -16,8 +22,12 @@ impl BuilderError {
         self.missing_fields.push(field);
     }
 
+    pub(crate) fn add_invalid_field(&mut self, field: &'static str, reason: &'static str) {
+        self.invalid_fields.push((field, reason));
+    }
+
     pub(crate) fn try_throw(self) -> Result<(), Self> {
-        if self.missing_fields.is_empty() {
+        if self.missing_fields.is_empty() && self.invalid_fields.is_empty() {
             return Ok(());
         }
 
@@ -26,13 +36,441 @@ impl BuilderError {
 }
 
 impl Display for BuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.missing_fields.is_empty() {
+            write!(
+                f,
+                "there are missing field values: {missing}",
+                missing = self.missing_fields.join(", ")
+            )?;
+        }
+        for (n, (field, reason)) in self.invalid_fields.iter().enumerate() {
+            if n > 0 || !self.missing_fields.is_empty() {
+                write!(f, "; ")?;
+            }
+            write!(f, "invalid value of field `{field}`: {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for BuilderError {}
+
+/// Error occuring when some of the requested positions hold no tile, eg. in
+/// [`GridMap2D::get_tiles_at_positions_checked`](crate::map::GridMap2D::get_tiles_at_positions_checked).
+#[derive(Debug, Clone)]
+pub struct MissingTilesError {
+    positions: Vec<GridPosition>,
+}
+
+impl MissingTilesError {
+    pub(crate) fn new(positions: Vec<GridPosition>) -> Self {
+        Self { positions }
+    }
+
+    /// Returns all requested positions without a tile.
+    pub fn missing_positions(&self) -> &[GridPosition] {
+        &self.positions
+    }
+}
+
+impl Display for MissingTilesError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "there are missing field values: {missing}",
-            missing = self.missing_fields.join(", ")
+            "{} requested positions hold no tile, first one: {:?}",
+            self.positions.len(),
+            self.positions[0]
         )
     }
 }
 
-impl Error for BuilderError {}
+impl Error for MissingTilesError {}
+
+/// Error occuring when the size of provided grid is incompatible with the size required by the operation.
+#[derive(Debug, Clone)]
+pub struct SizeError {
+    kind: SizeErrorKind,
+}
+
+impl SizeError {
+    pub(crate) fn new_mismatch(expected: GridSize, actual: GridSize) -> Self {
+        Self {
+            kind: SizeErrorKind::Mismatch { expected, actual },
+        }
+    }
+
+    pub(crate) fn new_length(size: GridSize, expected: usize, actual: usize) -> Self {
+        Self {
+            kind: SizeErrorKind::Length {
+                size,
+                expected,
+                actual,
+            },
+        }
+    }
+}
+
+impl Display for SizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            SizeErrorKind::Mismatch { expected, actual } => write!(
+                f,
+                "size of provided grid: {actual:?} is different than expected: {expected:?}"
+            ),
+            SizeErrorKind::Length {
+                size,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "number of provided tiles: {actual} is different than expected for size {size:?}: {expected}"
+            ),
+        }
+    }
+}
+
+impl Error for SizeError {}
+
+#[derive(Debug, Clone)]
+enum SizeErrorKind {
+    Mismatch {
+        expected: GridSize,
+        actual: GridSize,
+    },
+    Length {
+        size: GridSize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// Error occuring while reading [`GridMap2D`](crate::map::GridMap2D) from CSV with
+/// [`GridMap2D::read_csv`](crate::map::GridMap2D::read_csv).
+#[derive(Debug)]
+pub struct CsvError {
+    line: usize,
+    kind: CsvErrorKind,
+}
+
+impl CsvError {
+    pub(crate) fn new_io(line: usize, error: std::io::Error) -> Self {
+        Self {
+            line,
+            kind: CsvErrorKind::Io(error),
+        }
+    }
+
+    pub(crate) fn new_empty() -> Self {
+        Self {
+            line: 1,
+            kind: CsvErrorKind::Empty,
+        }
+    }
+
+    pub(crate) fn new_ragged(line: usize, expected: usize, actual: usize) -> Self {
+        Self {
+            line,
+            kind: CsvErrorKind::RaggedRow { expected, actual },
+        }
+    }
+
+    pub(crate) fn new_invalid_id(line: usize, column: usize, content: &str) -> Self {
+        Self {
+            line,
+            kind: CsvErrorKind::InvalidId {
+                column,
+                content: content.to_string(),
+            },
+        }
+    }
+
+    pub(crate) fn new_builder(error: TileBuilderError) -> Self {
+        Self {
+            line: 0,
+            kind: CsvErrorKind::Builder(error),
+        }
+    }
+
+    /// Returns the number of line (starting from `1`) at which the error occured. Returns `0` if the error isn't
+    /// connected to any specific line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            CsvErrorKind::Io(error) => write!(f, "cannot read line {}: {error}", self.line),
+            CsvErrorKind::Empty => write!(f, "provided CSV contains no rows"),
+            CsvErrorKind::RaggedRow { expected, actual } => write!(
+                f,
+                "line {} contains {actual} cells, while previous rows contain {expected}",
+                self.line
+            ),
+            CsvErrorKind::InvalidId { column, content } => write!(
+                f,
+                "cell {column} in line {} is not a valid `tile_type_id`: {content:?}",
+                self.line
+            ),
+            CsvErrorKind::Builder(error) => write!(f, "cannot build tiles: {error}"),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            CsvErrorKind::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CsvErrorKind {
+    Io(std::io::Error),
+    Empty,
+    RaggedRow { expected: usize, actual: usize },
+    InvalidId { column: usize, content: String },
+    Builder(TileBuilderError),
+}
+
+/// Error occuring while reading the map from its ASCII representation with
+/// [`read_gridmap_ascii`](crate::map::read_gridmap_ascii).
+#[cfg(feature = "gen")]
+#[derive(Debug, Clone)]
+pub struct AsciiError {
+    line: usize,
+    kind: AsciiErrorKind,
+}
+
+#[cfg(feature = "gen")]
+impl AsciiError {
+    pub(crate) fn new_empty() -> Self {
+        Self {
+            line: 1,
+            kind: AsciiErrorKind::Empty,
+        }
+    }
+
+    pub(crate) fn new_ragged(line: usize, expected: usize, actual: usize) -> Self {
+        Self {
+            line,
+            kind: AsciiErrorKind::RaggedRow { expected, actual },
+        }
+    }
+
+    pub(crate) fn new_unknown_glyph(line: usize, column: usize, glyph: char) -> Self {
+        Self {
+            line,
+            kind: AsciiErrorKind::UnknownGlyph { column, glyph },
+        }
+    }
+
+    /// Returns the number of line (starting from `1`) at which the error occured.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+#[cfg(feature = "gen")]
+impl Display for AsciiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            AsciiErrorKind::Empty => write!(f, "provided string contains no rows"),
+            AsciiErrorKind::RaggedRow { expected, actual } => write!(
+                f,
+                "line {} contains {actual} glyphs, while previous rows contain {expected}",
+                self.line
+            ),
+            AsciiErrorKind::UnknownGlyph { column, glyph } => write!(
+                f,
+                "glyph {glyph:?} at column {column} in line {} is not mapped to any `tile_type_id`",
+                self.line
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "gen")]
+impl Error for AsciiError {}
+
+#[cfg(feature = "gen")]
+#[derive(Debug, Clone)]
+enum AsciiErrorKind {
+    Empty,
+    RaggedRow { expected: usize, actual: usize },
+    UnknownGlyph { column: usize, glyph: char },
+}
+
+/// Error occuring while parsing [`AdjacencyRules`](crate::gen::collapse::singular::AdjacencyRules) from text lines with
+/// [`AdjacencyRules::from_rule_lines`](crate::gen::collapse::singular::AdjacencyRules::from_rule_lines).
+#[cfg(feature = "gen")]
+#[derive(Debug, Clone)]
+pub struct RuleParseError {
+    line: usize,
+    content: String,
+    kind: RuleParseErrorKind,
+}
+
+#[cfg(feature = "gen")]
+impl RuleParseError {
+    pub(crate) fn new_malformed(line: usize, content: &str) -> Self {
+        Self {
+            line,
+            content: content.to_string(),
+            kind: RuleParseErrorKind::Malformed,
+        }
+    }
+
+    pub(crate) fn new_unknown_name(line: usize, content: &str, name: &str) -> Self {
+        Self {
+            line,
+            content: content.to_string(),
+            kind: RuleParseErrorKind::UnknownName(name.to_string()),
+        }
+    }
+
+    pub(crate) fn new_unknown_direction(line: usize, content: &str, name: &str) -> Self {
+        Self {
+            line,
+            content: content.to_string(),
+            kind: RuleParseErrorKind::UnknownDirection(name.to_string()),
+        }
+    }
+
+    /// Returns the number of line (starting from `1`) at which the error occured.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns content of the offending line.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+#[cfg(feature = "gen")]
+impl Display for RuleParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            RuleParseErrorKind::Malformed => write!(
+                f,
+                "line {} is not in format `<tile> <DIRECTION> <neighbours>`: {}",
+                self.line, self.content
+            ),
+            RuleParseErrorKind::UnknownName(name) => write!(
+                f,
+                "unknown tile name `{name}` in line {}: {}",
+                self.line, self.content
+            ),
+            RuleParseErrorKind::UnknownDirection(name) => write!(
+                f,
+                "unknown direction `{name}` in line {}: {}",
+                self.line, self.content
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "gen")]
+impl Error for RuleParseError {}
+
+#[cfg(feature = "gen")]
+#[derive(Debug, Clone)]
+enum RuleParseErrorKind {
+    Malformed,
+    UnknownName(String),
+    UnknownDirection(String),
+}
+
+/// Error occuring while reading the map from the CSV layer data of the Tiled editor with
+/// [`load_gridmap_from_tiled_csv`](crate::map::load_gridmap_from_tiled_csv) or writing it with
+/// [`write_gridmap_to_tiled_csv`](crate::map::write_gridmap_to_tiled_csv).
+#[derive(Debug, Clone)]
+pub struct TiledError {
+    kind: TiledErrorKind,
+}
+
+impl TiledError {
+    pub(crate) fn new_length(size: GridSize, actual: usize) -> Self {
+        Self {
+            kind: TiledErrorKind::Length { size, actual },
+        }
+    }
+
+    pub(crate) fn new_invalid_gid(position: GridPosition, content: &str) -> Self {
+        Self {
+            kind: TiledErrorKind::InvalidGid {
+                position,
+                content: content.to_string(),
+            },
+        }
+    }
+
+    pub(crate) fn new_unknown_gid(position: GridPosition, gid: u32) -> Self {
+        Self {
+            kind: TiledErrorKind::UnknownGid { position, gid },
+        }
+    }
+
+    pub(crate) fn new_unmapped_type(position: GridPosition, tile_type_id: u64) -> Self {
+        Self {
+            kind: TiledErrorKind::UnmappedType {
+                position,
+                tile_type_id,
+            },
+        }
+    }
+}
+
+impl Display for TiledError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            TiledErrorKind::Length { size, actual } => write!(
+                f,
+                "layer data contains {actual} gids, while map of size {size:?} needs {}",
+                size.area()
+            ),
+            TiledErrorKind::InvalidGid { position, content } => write!(
+                f,
+                "cell at position {position:?} is not a valid gid: {content:?}"
+            ),
+            TiledErrorKind::UnknownGid { position, gid } => write!(
+                f,
+                "gid {gid} at position {position:?} is not mapped to any `tile_type_id`"
+            ),
+            TiledErrorKind::UnmappedType {
+                position,
+                tile_type_id,
+            } => write!(
+                f,
+                "`tile_type_id` {tile_type_id} at position {position:?} is not mapped to any gid"
+            ),
+        }
+    }
+}
+
+impl Error for TiledError {}
+
+#[derive(Debug, Clone)]
+enum TiledErrorKind {
+    Length {
+        size: GridSize,
+        actual: usize,
+    },
+    InvalidGid {
+        position: GridPosition,
+        content: String,
+    },
+    UnknownGid {
+        position: GridPosition,
+        gid: u32,
+    },
+    UnmappedType {
+        position: GridPosition,
+        tile_type_id: u64,
+    },
+}
@@ -42,24 +42,35 @@ impl GodotInfoBuilder {
         }
     }
 
-    /// Creates [`GodotTileMapTileInfo`] for specific tile in [`TileMap`].
+    /// Creates [`GodotTileMapTileInfo`] for specific tile in the first layer of the [`TileMap`].
     ///
-    /// For now supports only `layer = 0`.
+    /// To read the tile from other layer, use [`build_from_tilemap_layer`](Self::build_from_tilemap_layer).
     pub fn build_from_tilemap(
         &mut self,
         source_id: i32,
         tilemap: &Gd<TileMap>,
         coord: Vector2i,
+    ) -> GodotTileMapTileInfo {
+        self.build_from_tilemap_layer(source_id, tilemap, coord, 0)
+    }
+
+    /// Creates [`GodotTileMapTileInfo`] for specific tile in given `layer` of the [`TileMap`].
+    pub fn build_from_tilemap_layer(
+        &mut self,
+        source_id: i32,
+        tilemap: &Gd<TileMap>,
+        coord: Vector2i,
+        layer: i32,
     ) -> GodotTileMapTileInfo {
         match self.identify(source_id, tilemap) {
             TileSourceType::Atlas => GodotTileMapTileInfo::new_atlas(
                 source_id,
-                tilemap.get_cell_atlas_coords(0, coord),
-                tilemap.get_cell_alternative_tile(0, coord),
+                tilemap.get_cell_atlas_coords(layer, coord),
+                tilemap.get_cell_alternative_tile(layer, coord),
             ),
             TileSourceType::Collection => GodotTileMapTileInfo::new_scene(
                 source_id,
-                tilemap.get_cell_alternative_tile(0, coord),
+                tilemap.get_cell_alternative_tile(layer, coord),
             ),
             TileSourceType::Mesh => unreachable!(),
         }
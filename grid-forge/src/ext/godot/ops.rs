@@ -1,19 +1,22 @@
-use godot::builtin::Rect2i;
+use std::collections::HashMap;
+
+use godot::builtin::{Rect2i, Vector2i};
 use godot::classes::TileMap;
+use godot::log::godot_warn;
 use godot::obj::Gd;
 
-use crate::map::{GridMap2D, GridSize};
+use crate::map::{GridMap2D, GridMap3D, GridSize};
 use crate::tile::identifiable::builders::IdentTileBuilder;
 use crate::tile::identifiable::collection::IdentTileCollection;
 use crate::tile::identifiable::IdentifiableTileData;
-use crate::tile::GridPosition;
+use crate::tile::{GridPosition, TileContainer};
 
 use super::collection::{GodotInfoBuilder, GodotTileMapCollection};
 use super::error::GodotTileError;
 
 /// Loads [`GridMap2D`] from [`TileMap`], automatically loading read tiles into [`GodotTileMapCollection`].
 ///
-/// Currently supports only `layer = 0`.
+/// Reads only `layer = 0`. To read all layers, use [`load_gridmap3d_from_tilemap_auto`].
 ///
 /// Automatic character of the process means that:
 /// - not all tiles from underlying [`TileSet`](godot::classes::TileSet) sources will be loaded into [`GodotTileMapCollection`], only
@@ -47,7 +50,7 @@ pub fn load_gridmap_from_tilemap_auto<Data: IdentifiableTileData, B: IdentTileBu
 
 /// Loads [`GridMap2D`] from [`TileMap`], using tiles collected in [`GodotTileMapCollection`].
 ///
-/// Currently supports only `layer = 0`.
+/// Reads only `layer = 0`. To read all layers, use [`load_gridmap3d_from_tilemap_manual`].
 ///
 /// As the process is manual, the `tile_type_id` for each tile will be taken from the collection, not generated
 /// automatically. Process can fail if the collection does not contain the required tile.
@@ -81,7 +84,8 @@ pub fn load_gridmap_from_tilemap_manual<Data: IdentifiableTileData, B: IdentTile
 
 /// Writes [`GridMap2D`] to [`TileMap`], using [`GodotTileMapTileInfo`](crate::godot::GodotTileMapTileInfo) from [`GodotTileMapCollection`].
 ///
-/// Currently supports only `layer = 0`.
+/// Tiles are written into the layer specified by their [`GridPosition::get_godot_layer`], defaulting to `layer = 0`.
+/// To write all layers of [`GridMap3D`], use [`write_gridmap3d_to_tilemap`].
 pub fn write_gridmap_to_tilemap<Data: IdentifiableTileData>(
     gridmap: &GridMap2D<Data>,
     tilemap: &mut Gd<TileMap>,
@@ -107,6 +111,139 @@ pub fn write_gridmap_to_tilemap<Data: IdentifiableTileData>(
     Ok(())
 }
 
+/// Writes [`GridMap2D`] to [`TileMap`], placing the tiles from [`TileSetAtlasSource`](godot::classes::TileSetAtlasSource)
+/// according to the `mapping` from `tile_type_id` to the source id and atlas coordinates of the tile.
+///
+/// Contrary to [`write_gridmap_to_tilemap`], doesn't need [`GodotTileMapCollection`], which makes it handy when the
+/// atlas layout is known upfront. Tiles with `tile_type_id` missing from the `mapping` are reported with
+/// [`godot_warn`] and skipped. Tiles are written into the layer specified by their [`GridPosition::get_godot_layer`],
+/// defaulting to `layer = 0`.
+pub fn write_gridmap_to_tilemap_with_mapping<Data: IdentifiableTileData>(
+    gridmap: &GridMap2D<Data>,
+    tilemap: &mut Gd<TileMap>,
+    mapping: &HashMap<u64, (i32, Vector2i)>,
+) {
+    for tile in gridmap.iter_tiles() {
+        let position = tile.grid_position();
+        let tile_type_id = tile.as_ref().tile_type_id();
+
+        let Some((source_id, atlas_coords)) = mapping.get(&tile_type_id) else {
+            godot_warn!(
+                "cannot get source and atlas coordinates for `tile_type_id`: {tile_type_id}, skipping tile at position: {position:?}"
+            );
+            continue;
+        };
+
+        tilemap
+            .set_cell_ex(
+                position.get_godot_layer().unwrap_or(0),
+                position.get_godot_coords(),
+            )
+            .source_id(*source_id)
+            .atlas_coords(*atlas_coords)
+            .done();
+    }
+}
+
+/// Loads [`GridMap3D`] from all layers of [`TileMap`], automatically loading read tiles into
+/// [`GodotTileMapCollection`].
+///
+/// Each [`TileMap`] layer becomes the map layer with the same `z` coordinate, with positions created via
+/// [`GridPosition::from_godot_v2i_layer`]. Cells not used in given layer are left empty. Apart from that, works the
+/// same as [`load_gridmap_from_tilemap_auto`].
+pub fn load_gridmap3d_from_tilemap_auto<Data: IdentifiableTileData, B: IdentTileBuilder<Data>>(
+    tilemap: &Gd<TileMap>,
+    collection: &mut GodotTileMapCollection,
+    builder: &B,
+) -> Result<GridMap3D<Data>, GodotTileError> {
+    let mut grid_map = GridMap3D::new(size_from_tilemap_layers(tilemap));
+    let mut sources = GodotInfoBuilder::default();
+
+    for layer in 0..tilemap.get_layers_count() {
+        for coords in tilemap.get_used_cells(layer).iter_shared() {
+            let source_id = tilemap.get_cell_source_id(layer, coords);
+
+            let info = sources.build_from_tilemap_layer(source_id, tilemap, coords, layer);
+            let tile_type_id = info.get_tile_type_id();
+
+            collection.add_tile_data(tile_type_id, info);
+
+            let tile = builder.build_tile(
+                GridPosition::from_godot_v2i_layer(coords, layer),
+                tile_type_id,
+            )?;
+            grid_map.insert_tile(tile);
+        }
+    }
+    Ok(grid_map)
+}
+
+/// Loads [`GridMap3D`] from all layers of [`TileMap`], using tiles collected in [`GodotTileMapCollection`].
+///
+/// Each [`TileMap`] layer becomes the map layer with the same `z` coordinate, with positions created via
+/// [`GridPosition::from_godot_v2i_layer`]. Cells not used in given layer are left empty. Apart from that, works the
+/// same as [`load_gridmap_from_tilemap_manual`].
+pub fn load_gridmap3d_from_tilemap_manual<Data: IdentifiableTileData, B: IdentTileBuilder<Data>>(
+    tilemap: &Gd<TileMap>,
+    collection: &GodotTileMapCollection,
+    builder: &B,
+) -> Result<GridMap3D<Data>, GodotTileError> {
+    let mut grid_map = GridMap3D::new(size_from_tilemap_layers(tilemap));
+    let mut sources = GodotInfoBuilder::default();
+
+    for layer in 0..tilemap.get_layers_count() {
+        for coords in tilemap.get_used_cells(layer).iter_shared() {
+            let source_id = tilemap.get_cell_source_id(layer, coords);
+
+            let info = sources.build_from_tilemap_layer(source_id, tilemap, coords, layer);
+
+            if let Some(tile_type_id) = collection.get_tile_type_id(&info) {
+                let tile = builder.build_tile(
+                    GridPosition::from_godot_v2i_layer(coords, layer),
+                    tile_type_id,
+                )?;
+                grid_map.insert_tile(tile);
+            } else {
+                return Err(GodotTileError::new_no_id_for_info(info));
+            }
+        }
+    }
+
+    Ok(grid_map)
+}
+
+/// Writes [`GridMap3D`] to [`TileMap`], using [`GodotTileMapTileInfo`](crate::godot::GodotTileMapTileInfo) from [`GodotTileMapCollection`].
+///
+/// Each map layer is written into the [`TileMap`] layer given by [`GridPosition::get_godot_layer`], so the layers
+/// need to be present in the [`TileMap`] beforehand. Empty positions are left untouched.
+pub fn write_gridmap3d_to_tilemap<Data: IdentifiableTileData>(
+    gridmap: &GridMap3D<Data>,
+    tilemap: &mut Gd<TileMap>,
+    collection: &GodotTileMapCollection,
+) -> Result<(), GodotTileError> {
+    for tile in gridmap.iter_tiles() {
+        let position = tile.grid_position();
+
+        if let Some(godot_info) = collection.get_tile_data(&tile.as_ref().tile_type_id()) {
+            godot_info.insert_to_tilemap(
+                tilemap,
+                position.get_godot_coords(),
+                position.get_godot_layer().unwrap_or(0),
+            )
+        } else {
+            return Err(GodotTileError::new_no_info_for_id(
+                tile.as_ref().tile_type_id(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn size_from_tilemap_layers(tilemap: &Gd<TileMap>) -> GridSize {
+    let size = size_from_rect(tilemap.get_used_rect());
+    GridSize::new_xyz(size.x(), size.y(), tilemap.get_layers_count().max(1) as u32)
+}
+
 fn size_from_rect(rect: Rect2i) -> GridSize {
     GridSize::new_xy(
         (rect.size.x - rect.position.x) as u32,
@@ -1,2 +1,5 @@
 #[cfg(feature = "godot")]
 pub(crate) mod godot;
+
+#[cfg(feature = "serde")]
+mod serde;
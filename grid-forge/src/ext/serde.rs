@@ -0,0 +1,405 @@
+//! [`serde`](::serde) support for the structs which can't simply derive it.
+//!
+//! [`GridSize`] is stored without its cached center, while [`GridMap2D`] is stored as its size and a sparse list of
+//! occupied positions alongside their data, so the empty positions aren't written at all. The same goes for the
+//! `CollapsedGrid`, with `tile_type_id`s stored as the data. Singular `AdjacencyRules` are stored as a map of
+//! `tile_type_id`s to the adjacent ones in each direction, and singular `FrequencyHints` as a map of `tile_type_id`s
+//! to their weights.
+
+use ::serde::de::Error as _;
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::map::{GridMap2D, GridSize};
+use crate::tile::{GridPosition, TileData};
+
+#[derive(Serialize, Deserialize)]
+struct GridSizeRepr {
+    x: u32,
+    y: u32,
+    z: Option<u32>,
+}
+
+impl Serialize for GridSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GridSizeRepr {
+            x: self.x(),
+            y: self.y(),
+            z: self.z(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GridSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = GridSizeRepr::deserialize(deserializer)?;
+        Ok(match repr.z {
+            Some(z) => GridSize::new_xyz(repr.x, repr.y, z),
+            None => GridSize::new_xy(repr.x, repr.y),
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct GridMapRef<'a, Data: Serialize> {
+    size: GridSize,
+    tiles: Vec<(GridPosition, &'a Data)>,
+}
+
+#[derive(Deserialize)]
+struct GridMapRepr<Data> {
+    size: GridSize,
+    tiles: Vec<(GridPosition, Data)>,
+}
+
+impl<Data: TileData + Serialize> Serialize for GridMap2D<Data> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GridMapRef {
+            size: self.size,
+            tiles: self
+                .tiles
+                .indexed_iter()
+                .filter_map(|((x, y), tile)| {
+                    tile.as_ref()
+                        .map(|data| (GridPosition::new_xy(x as u32, y as u32), data))
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, Data: TileData + Deserialize<'de>> Deserialize<'de> for GridMap2D<Data> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = GridMapRepr::<Data>::deserialize(deserializer)?;
+        let mut map = GridMap2D::new(repr.size);
+        for (position, data) in repr.tiles {
+            if !map.insert_data(&position, data) {
+                return Err(D::Error::custom(format!(
+                    "tile position: {position:?} is outside of the map of size: {:?}",
+                    repr.size
+                )));
+            }
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(feature = "gen")]
+mod collapsed {
+    use ::serde::de::Error as _;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use std::collections::BTreeMap;
+
+    use crate::gen::collapse::private::AdjacencyTable;
+    use crate::gen::collapse::singular::{AdjacencyRules, FrequencyHints};
+    use crate::gen::collapse::{CollapsedGrid, CollapsedTileData};
+    use crate::map::{GridDir, GridSize};
+    use crate::tile::identifiable::IdentifiableTileData;
+    use crate::tile::{GridPosition, TileContainer};
+
+    #[derive(Serialize, Deserialize)]
+    struct CollapsedGridRepr {
+        size: GridSize,
+        tiles: Vec<(GridPosition, u64)>,
+    }
+
+    impl Serialize for CollapsedGrid {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            CollapsedGridRepr {
+                size: *self.as_ref().size(),
+                tiles: self
+                    .as_ref()
+                    .iter_tiles()
+                    .map(|tile| (tile.grid_position(), tile.as_ref().tile_type_id()))
+                    .collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CollapsedGrid {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = CollapsedGridRepr::deserialize(deserializer)?;
+            let mut grid = CollapsedGrid::new(repr.size);
+            for (position, tile_type_id) in repr.tiles {
+                if !grid.insert_data(&position, CollapsedTileData::new(tile_type_id)) {
+                    return Err(D::Error::custom(format!(
+                        "tile position: {position:?} is outside of the grid of size: {:?}",
+                        repr.size
+                    )));
+                }
+            }
+            Ok(grid)
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Default)]
+    #[serde(default, deny_unknown_fields)]
+    struct AdjacentRepr {
+        #[serde(rename = "UP")]
+        up: Vec<u64>,
+        #[serde(rename = "DOWN")]
+        down: Vec<u64>,
+        #[serde(rename = "LEFT")]
+        left: Vec<u64>,
+        #[serde(rename = "RIGHT")]
+        right: Vec<u64>,
+    }
+
+    impl AdjacentRepr {
+        fn get_mut(&mut self, direction: GridDir) -> &mut Vec<u64> {
+            match direction {
+                GridDir::UP => &mut self.up,
+                GridDir::DOWN => &mut self.down,
+                GridDir::LEFT => &mut self.left,
+                GridDir::RIGHT => &mut self.right,
+                _ => unreachable!("only two-dimensional directions are stored"),
+            }
+        }
+
+        fn into_entries(self) -> impl Iterator<Item = (GridDir, u64)> {
+            [
+                (GridDir::UP, self.up),
+                (GridDir::DOWN, self.down),
+                (GridDir::LEFT, self.left),
+                (GridDir::RIGHT, self.right),
+            ]
+            .into_iter()
+            .flat_map(|(direction, adjacent)| adjacent.into_iter().map(move |id| (direction, id)))
+        }
+    }
+
+    type AdjacencyRulesRepr = BTreeMap<u64, AdjacentRepr>;
+
+    impl<Data: IdentifiableTileData> Serialize for AdjacencyRules<Data> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.inner()
+                .as_ref()
+                .iter()
+                .map(|(tile_id, adjacencies)| {
+                    let mut repr = AdjacentRepr::default();
+                    for direction in GridDir::ALL_2D {
+                        let adjacent = repr.get_mut(*direction);
+                        adjacent.extend(adjacencies[*direction].iter().copied());
+                        adjacent.sort_unstable();
+                    }
+                    (*tile_id, repr)
+                })
+                .collect::<AdjacencyRulesRepr>()
+                .serialize(serializer)
+        }
+    }
+
+    impl<'de, Data: IdentifiableTileData> Deserialize<'de> for AdjacencyRules<Data> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = AdjacencyRulesRepr::deserialize(deserializer)?;
+            let mut table = AdjacencyTable::default();
+            for (tile_id, adjacent) in repr {
+                table.insert_element(tile_id);
+                for (direction, adjacent_id) in adjacent.into_entries() {
+                    table.insert_adjacency(tile_id, direction, adjacent_id);
+                }
+            }
+            Ok(AdjacencyRules::from_table(table))
+        }
+    }
+
+    impl<Data: IdentifiableTileData> AdjacencyRules<Data> {
+        /// Writes the rules as JSON, allowing to inspect or author them with external tools.
+        ///
+        /// The JSON object is keyed by `tile_type_id`s, with each of them mapped to the object listing sorted
+        /// `tile_type_id`s allowed as its neighbours in each of the `UP`, `DOWN`, `LEFT` and `RIGHT` directions:
+        ///
+        /// ```json
+        /// {"1": {"UP": [2], "DOWN": [2], "LEFT": [], "RIGHT": []}, "2": {"UP": [1], "DOWN": [1], "LEFT": [], "RIGHT": []}}
+        /// ```
+        pub fn to_json(&self) -> String {
+            serde_json::to_string(self).expect("adjacency rules should always be serializable")
+        }
+
+        /// Reads the rules from JSON following the schema described in [`to_json`](Self::to_json). Missing directions
+        /// are treated as empty. Returns error if the JSON doesn't follow the schema, eg. contains unknown direction.
+        pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+            serde_json::from_str(json)
+        }
+    }
+
+    impl<Data: IdentifiableTileData> Serialize for FrequencyHints<Data> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.weights().serialize(serializer)
+        }
+    }
+
+    impl<'de, Data: IdentifiableTileData> Deserialize<'de> for FrequencyHints<Data> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let weights = BTreeMap::<u64, u32>::deserialize(deserializer)?;
+            if let Some((tile_id, _)) = weights.iter().find(|(_, weight)| **weight == 0) {
+                return Err(D::Error::custom(format!(
+                    "weight of tile with `tile_type_id`: {tile_id} needs to be positive"
+                )));
+            }
+            Ok(FrequencyHints::from_weights(weights))
+        }
+    }
+
+    impl<Data: IdentifiableTileData> FrequencyHints<Data> {
+        /// Writes the weights as JSON, allowing to inspect or author them with external tools.
+        ///
+        /// The JSON object maps `tile_type_id`s to their weights:
+        ///
+        /// ```json
+        /// {"1": 3, "2": 1}
+        /// ```
+        pub fn to_json(&self) -> String {
+            serde_json::to_string(self).expect("frequency hints should always be serializable")
+        }
+
+        /// Reads the weights from JSON following the schema described in [`to_json`](Self::to_json). Returns error if
+        /// the JSON doesn't follow the schema or any of the weights is not positive.
+        pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+            serde_json::from_str(json)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+
+    use crate::map::{GridDir, GridMap2D, GridSize};
+    use crate::tile::{GridPosition, TileContainer, TileData};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TestData(u32);
+
+    impl TileData for TestData {}
+
+    #[test]
+    fn map_round_trip() {
+        let mut map = GridMap2D::new(GridSize::new_xy(4, 3));
+        map.insert_data(&GridPosition::new_xy(0, 0), TestData(1));
+        map.insert_data(&GridPosition::new_xy(3, 1), TestData(2));
+        map.insert_data(&GridPosition::new_xy(2, 2), TestData(3));
+
+        let json = serde_json::to_string(&map).unwrap();
+        let restored: GridMap2D<TestData> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(map.size(), restored.size());
+        assert_eq!(map.get_all_positions(), restored.get_all_positions());
+        for tile in map.iter_tiles() {
+            let position = tile.grid_position();
+            assert_eq!(
+                tile.as_ref(),
+                restored.get_tile_at_position(&position).unwrap().as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn map_out_of_bounds_position() {
+        let json = r#"{"size":{"x":2,"y":2,"z":null},"tiles":[[{"x":5,"y":0,"z":null},1]]}"#;
+
+        assert!(serde_json::from_str::<GridMap2D<TestData>>(json).is_err());
+    }
+
+    #[cfg(feature = "gen")]
+    #[test]
+    fn collapsed_grid_round_trip() {
+        use crate::gen::collapse::{CollapsedGrid, CollapsedTileData};
+        use crate::tile::identifiable::IdentifiableTileData;
+
+        let mut grid = CollapsedGrid::new(GridSize::new_xy(3, 3));
+        grid.insert_data(&GridPosition::new_xy(1, 2), CollapsedTileData::new(42));
+
+        let json = serde_json::to_string(&grid).unwrap();
+        let restored: CollapsedGrid = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(grid.as_ref().size(), restored.as_ref().size());
+        let tile = restored
+            .as_ref()
+            .get_tile_at_position(&GridPosition::new_xy(1, 2))
+            .unwrap();
+        assert_eq!(42, tile.as_ref().tile_type_id());
+        assert_eq!(1, restored.as_ref().get_all_positions().len());
+    }
+
+    #[cfg(feature = "gen")]
+    #[test]
+    fn adjacency_rules_round_trip() {
+        use crate::gen::collapse::singular::AdjacencyRules;
+        use crate::tile::identifiable::BasicIdentTileData;
+
+        let rules = AdjacencyRules::<BasicIdentTileData>::from_raw([
+            (1, GridDir::UP, 2),
+            (1, GridDir::UP, 1),
+            (2, GridDir::LEFT, 1),
+        ]);
+
+        let json = serde_json::to_string(&rules).unwrap();
+        let restored: AdjacencyRules<BasicIdentTileData> = serde_json::from_str(&json).unwrap();
+
+        for tile_id in [1, 2] {
+            for direction in GridDir::ALL_2D {
+                for adjacent_id in [1, 2] {
+                    assert_eq!(
+                        rules.is_valid_raw(tile_id, *direction, adjacent_id),
+                        restored.is_valid_raw(tile_id, *direction, adjacent_id)
+                    );
+                }
+            }
+        }
+        assert_eq!(json, serde_json::to_string(&restored).unwrap());
+    }
+
+    #[cfg(feature = "gen")]
+    #[test]
+    fn rules_json_schema() {
+        use crate::gen::collapse::singular::{AdjacencyRules, FrequencyHints};
+        use crate::tile::identifiable::builders::ConstructableViaIdentifierTile;
+        use crate::tile::identifiable::BasicIdentTileData;
+        use crate::tile::GridTile;
+
+        let rules = AdjacencyRules::<BasicIdentTileData>::from_raw([
+            (1, GridDir::UP, 2),
+            (1, GridDir::DOWN, 2),
+            (2, GridDir::UP, 1),
+            (2, GridDir::DOWN, 1),
+        ]);
+        let json = r#"{"1":{"UP":[2],"DOWN":[2],"LEFT":[],"RIGHT":[]},"2":{"UP":[1],"DOWN":[1],"LEFT":[],"RIGHT":[]}}"#;
+        assert_eq!(json, rules.to_json());
+
+        let restored =
+            AdjacencyRules::<BasicIdentTileData>::from_json(r#"{"1":{"RIGHT":[1,2]}}"#).unwrap();
+        assert!(restored.is_valid_raw(1, GridDir::RIGHT, 2));
+        assert!(!restored.is_valid_raw(1, GridDir::UP, 1));
+        assert!(AdjacencyRules::<BasicIdentTileData>::from_json(r#"{"1":{"NORTH":[1]}}"#).is_err());
+
+        let mut frequency = FrequencyHints::<BasicIdentTileData>::default();
+        for (id, weight) in [(1, 3), (2, 1)] {
+            let tile = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(id));
+            frequency.set_weight_for_tile(&tile, weight);
+        }
+        assert_eq!(r#"{"1":3,"2":1}"#, frequency.to_json());
+        let restored =
+            FrequencyHints::<BasicIdentTileData>::from_json(&frequency.to_json()).unwrap();
+        assert_eq!(frequency.to_json(), restored.to_json());
+
+        let err = FrequencyHints::<BasicIdentTileData>::from_json(r#"{"1":3,"2":0}"#).unwrap_err();
+        assert!(err.to_string().contains("`tile_type_id`: 2"));
+        assert!(FrequencyHints::<BasicIdentTileData>::from_json(r#"{"1":-1}"#).is_err());
+    }
+
+    #[test]
+    fn size_and_dir_round_trip() {
+        let size = GridSize::new_xyz(5, 7, 2);
+        let json = serde_json::to_string(&size).unwrap();
+        let restored: GridSize = serde_json::from_str(&json).unwrap();
+        assert_eq!(size, restored);
+        assert_eq!(size.center(), restored.center());
+
+        let json = serde_json::to_string(&GridDir::UP).unwrap();
+        assert_eq!(GridDir::UP, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -1,6 +1,11 @@
 use std::{error::Error, fmt::Display};
 
-use crate::{map::GridSize, tile::GridPosition};
+use crate::{
+    map::{GridDir, GridSize},
+    tile::GridPosition,
+};
+
+use super::CollapsedGrid;
 
 /// Error occuring during collapse process.
 ///
@@ -17,16 +22,45 @@ use crate::{map::GridSize, tile::GridPosition};
 /// - [`CollapseError::is_probabilistic()`] returns `true` if the error can be solved by retrying the operation. If the error
 /// occurs at the sole beginning of the process, before first successful collapse it is deemed not probabilistic and is most likely caused
 /// by placing some incompatible pre-collapsed tiles in *collapsible grid* provided to the *resolver*.
+/// - [`CollapseError::failure_snapshot()`] returns the tiles collapsed before the error occured, if the *resolver* was
+/// configured to capture them.
+/// - [`CollapseError::failed_options()`] and [`CollapseError::conflicting_directions()`] describe which options were
+///   ruled out at the failed position, and by the neighbours in which directions.
 #[derive(Debug)]
 pub struct CollapseError {
     pos: GridPosition,
     kind: CollapseErrorKind,
     iter: u32,
+    snapshot: Option<Box<CollapsedGrid>>,
+    failed_options: Vec<u64>,
+    conflicting_directions: Vec<GridDir>,
 }
 
 impl CollapseError {
     pub(crate) fn new(pos: GridPosition, kind: CollapseErrorKind, iter: u32) -> Self {
-        Self { pos, kind, iter }
+        Self {
+            pos,
+            kind,
+            iter,
+            snapshot: None,
+            failed_options: Vec::new(),
+            conflicting_directions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_conflicts(
+        mut self,
+        failed_options: Vec<u64>,
+        conflicting_directions: Vec<GridDir>,
+    ) -> Self {
+        self.failed_options = failed_options;
+        self.conflicting_directions = conflicting_directions;
+        self
+    }
+
+    pub(crate) fn with_snapshot(mut self, snapshot: CollapsedGrid) -> Self {
+        self.snapshot = Some(Box::new(snapshot));
+        self
     }
 
     #[inline(always)]
@@ -41,6 +75,17 @@ impl CollapseError {
         }
     }
 
+    pub(crate) fn new_uncovered(uncovered: Vec<GridPosition>) -> Self {
+        Self {
+            pos: uncovered[0],
+            kind: CollapseErrorKind::Coverage(uncovered),
+            iter: 0,
+            snapshot: None,
+            failed_options: Vec::new(),
+            conflicting_directions: Vec::new(),
+        }
+    }
+
     /// Returns [`GridPosition`] of tile which caused the error.
     pub fn failed_pos(&self) -> GridPosition {
         self.pos
@@ -48,18 +93,61 @@ impl CollapseError {
 
     /// Returns `true` if the error can be solved by retrying the operation.
     pub fn is_probabilistic(&self) -> bool {
-        !matches!(self.kind, CollapseErrorKind::Init)
+        !matches!(
+            self.kind,
+            CollapseErrorKind::Init | CollapseErrorKind::Coverage(_)
+        )
+    }
+
+    /// Returns `true` if the error was caused by some tile running out of options.
+    pub(crate) fn is_contradiction(&self) -> bool {
+        matches!(
+            self.kind,
+            CollapseErrorKind::Collapse | CollapseErrorKind::Init | CollapseErrorKind::Propagation
+        )
     }
 
     /// Returns iteration number when the error occured.
     pub fn failed_iter(&self) -> u32 {
         self.iter
     }
+
+    /// Returns the tiles which were collapsed at the moment of the failure. Available only if the *resolver* was configured
+    /// to capture it, eg. with [`singular::Resolver::with_failure_snapshot`](crate::gen::collapse::singular::Resolver::with_failure_snapshot).
+    pub fn failure_snapshot(&self) -> Option<&CollapsedGrid> {
+        self.snapshot.as_deref()
+    }
+
+    /// Takes the snapshot of the tiles collapsed at the moment of the failure out of the error.
+    pub fn take_failure_snapshot(&mut self) -> Option<CollapsedGrid> {
+        self.snapshot.take().map(|snapshot| *snapshot)
+    }
+
+    /// Returns `tile_type_id`s which were possible at the failed position according to the rules alone, but were ruled
+    /// out by its neighbours. Empty if the failure wasn't caused by the tile running out of options, or the *resolver*
+    /// doesn't gather this information.
+    pub fn failed_options(&self) -> &[u64] {
+        &self.failed_options
+    }
+
+    /// Returns the directions of the neighbours which ruled out some of the [`failed_options`](Self::failed_options).
+    pub fn conflicting_directions(&self) -> &[GridDir] {
+        &self.conflicting_directions
+    }
+
+    /// If the error originates from the provided positions not covering all empty tiles in the grid, returns
+    /// all uncovered [`GridPosition`]s.
+    pub fn uncovered_positions(&self) -> Option<&[GridPosition]> {
+        match &self.kind {
+            CollapseErrorKind::Coverage(uncovered) => Some(uncovered),
+            _ => None,
+        }
+    }
 }
 
 impl Display for CollapseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.kind {
+        match &self.kind {
             CollapseErrorKind::Collapse => write!(
                 f,
                 "tile at position: {:?} have no options left while collapsing on iteration {}!",
@@ -75,6 +163,22 @@ impl Display for CollapseError {
                 "tile at position: {:?} have no options left during propagation on iteration {}!",
                 self.pos, self.iter
             ),
+            CollapseErrorKind::Requirement => write!(
+                f,
+                "tile at position: {:?} is missing its required neighbour after collapsing on iteration {}!",
+                self.pos, self.iter
+            ),
+            CollapseErrorKind::Override(tile_type_id) => write!(
+                f,
+                "forced option: {tile_type_id} is not available for tile at position: {:?} on iteration {}!",
+                self.pos, self.iter
+            ),
+            CollapseErrorKind::Coverage(uncovered) => write!(
+                f,
+                "{} empty tiles are not covered by provided positions, first one at position: {:?}!",
+                uncovered.len(),
+                self.pos,
+            ),
         }
     }
 }
@@ -86,6 +190,83 @@ pub(crate) enum CollapseErrorKind {
     Collapse,
     Init,
     Propagation,
+    Requirement,
+    Override(u64),
+    Coverage(Vec<GridPosition>),
+}
+
+/// Error occuring while decoding [`CollapsedGrid`](crate::gen::collapse::CollapsedGrid) with
+/// [`CollapsedGrid::from_bytes`](crate::gen::collapse::CollapsedGrid::from_bytes).
+#[derive(Debug, Clone)]
+pub struct CollapsedGridDecodeError {
+    kind: CollapsedGridDecodeErrorKind,
+}
+
+impl CollapsedGridDecodeError {
+    pub(crate) fn new_length(expected: u64, actual: usize) -> Self {
+        Self {
+            kind: CollapsedGridDecodeErrorKind::Length { expected, actual },
+        }
+    }
+
+    pub(crate) fn new_tile_count(count: u32, size: GridSize) -> Self {
+        Self {
+            kind: CollapsedGridDecodeErrorKind::TileCount { count, size },
+        }
+    }
+
+    pub(crate) fn new_size(size: GridSize) -> Self {
+        Self {
+            kind: CollapsedGridDecodeErrorKind::Size(size),
+        }
+    }
+
+    pub(crate) fn new_out_of_bounds(position: GridPosition, size: GridSize) -> Self {
+        Self {
+            kind: CollapsedGridDecodeErrorKind::OutOfBounds { position, size },
+        }
+    }
+}
+
+impl Display for CollapsedGridDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            CollapsedGridDecodeErrorKind::Length { expected, actual } => write!(
+                f,
+                "expected {expected} bytes of encoded `CollapsedGrid`, got {actual}"
+            ),
+            CollapsedGridDecodeErrorKind::OutOfBounds { position, size } => write!(
+                f,
+                "encoded tile at position: {position:?} is outside of the stored grid size: {size:?}"
+            ),
+            CollapsedGridDecodeErrorKind::TileCount { count, size } => write!(
+                f,
+                "encoded number of tiles: {count} exceeds the number of positions in the stored grid size: {size:?}"
+            ),
+            CollapsedGridDecodeErrorKind::Size(size) => {
+                write!(f, "stored grid size: {size:?} is too large to be allocated")
+            }
+        }
+    }
+}
+
+impl Error for CollapsedGridDecodeError {}
+
+#[derive(Debug, Clone)]
+enum CollapsedGridDecodeErrorKind {
+    Length {
+        expected: u64,
+        actual: usize,
+    },
+    OutOfBounds {
+        position: GridPosition,
+        size: GridSize,
+    },
+    TileCount {
+        count: u32,
+        size: GridSize,
+    },
+    Size(GridSize),
 }
 
 /// Error occuring during the operations on *collapsible grids*.
@@ -8,15 +8,24 @@ use crate::{
     },
 };
 
-use super::{error::CollapsibleGridError, CollapsedTileData, CollapsibleTileData};
+use super::{
+    error::{CollapsedGridDecodeError, CollapsibleGridError},
+    CollapsedTileData, CollapsibleTileData,
+};
 
 /// [`GridMap2D`] containing data of [`CollapsedTileData`].
+#[derive(Debug)]
 pub struct CollapsedGrid {
     grid: GridMap2D<CollapsedTileData>,
     tile_type_ids: HashSet<u64>,
 }
 
 impl CollapsedGrid {
+    /// Length of the encoded size and number of tiles.
+    const HEADER_LEN: usize = 12;
+    /// Length of the single encoded tile.
+    const TILE_LEN: usize = 16;
+
     /// Creates new [`CollapsedGrid`] with the given size.
     pub fn new(size: GridSize) -> Self {
         Self {
@@ -45,6 +54,69 @@ impl CollapsedGrid {
     pub fn tile_type_ids(&self) -> impl Iterator<Item = &u64> {
         self.tile_type_ids.iter()
     }
+
+    /// Encodes the grid into compact bytes, which can be decoded back with [`from_bytes`](Self::from_bytes).
+    ///
+    /// The encoding consists of the `x` and `y` of the grid size and the number of tiles, followed by the `x`, `y` and
+    /// `tile_type_id` of each tile - all in little-endian order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let tiles = self.grid.iter_tiles().collect::<Vec<_>>();
+        let mut bytes = Vec::with_capacity(Self::HEADER_LEN + tiles.len() * Self::TILE_LEN);
+        bytes.extend_from_slice(&self.grid.size().x().to_le_bytes());
+        bytes.extend_from_slice(&self.grid.size().y().to_le_bytes());
+        bytes.extend_from_slice(&(tiles.len() as u32).to_le_bytes());
+        for tile in tiles {
+            let position = tile.grid_position();
+            bytes.extend_from_slice(&position.x().to_le_bytes());
+            bytes.extend_from_slice(&position.y().to_le_bytes());
+            bytes.extend_from_slice(&tile.as_ref().tile_type_id().to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes the grid from bytes created by [`to_bytes`](Self::to_bytes).
+    ///
+    /// Returns [`CollapsedGridDecodeError`] if the length of `bytes` is inconsistent with the encoded number of tiles,
+    /// the encoded number of tiles exceeds the number of positions in the encoded grid size, the grid of encoded size
+    /// cannot be allocated or if any encoded tile lies outside of the encoded grid size.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CollapsedGridDecodeError> {
+        if bytes.len() < Self::HEADER_LEN {
+            return Err(CollapsedGridDecodeError::new_length(
+                Self::HEADER_LEN as u64,
+                bytes.len(),
+            ));
+        }
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        let size = GridSize::new_xy(read_u32(0), read_u32(4));
+        let count = read_u32(8);
+        let expected = Self::HEADER_LEN as u64 + count as u64 * Self::TILE_LEN as u64;
+        if bytes.len() as u64 != expected {
+            return Err(CollapsedGridDecodeError::new_length(expected, bytes.len()));
+        }
+        let positions = size.x() as u64 * size.y() as u64;
+        if count as u64 > positions {
+            return Err(CollapsedGridDecodeError::new_tile_count(count, size));
+        }
+        usize::try_from(positions)
+            .ok()
+            .and_then(|positions| {
+                positions.checked_mul(std::mem::size_of::<Option<CollapsedTileData>>())
+            })
+            .filter(|len| *len <= isize::MAX as usize)
+            .ok_or_else(|| CollapsedGridDecodeError::new_size(size))?;
+
+        let mut grid = Self::new(size);
+        for offset in (Self::HEADER_LEN..bytes.len()).step_by(Self::TILE_LEN) {
+            let position = GridPosition::new_xy(read_u32(offset), read_u32(offset + 4));
+            let tile_type_id =
+                u64::from_le_bytes(bytes[offset + 8..offset + 16].try_into().unwrap());
+            if !grid.insert_data(&position, CollapsedTileData::new(tile_type_id)) {
+                return Err(CollapsedGridDecodeError::new_out_of_bounds(position, size));
+            }
+        }
+        Ok(grid)
+    }
 }
 
 impl AsRef<GridMap2D<CollapsedTileData>> for CollapsedGrid {
@@ -72,6 +144,26 @@ pub trait CollapsibleGrid<IT: IdentifiableTileData, CT: CollapsibleTileData>:
         self._grid().get_all_empty_positions()
     }
 
+    /// Counts collapsed tiles neighbouring given position in all four directions.
+    ///
+    /// Useful for prioritizing positions surrounded by the already collapsed tiles, as collapsing them first often reduces
+    /// the chance of contradictions.
+    fn collapsed_neighbour_count(&self, position: &GridPosition) -> usize {
+        self._grid()
+            .get_neighbours(position)
+            .iter()
+            .filter(|tile| tile.as_ref().is_collapsed())
+            .count()
+    }
+
+    /// Returns identifiers of options which can never be placed, as the rules don't allow any neighbour for them in at
+    /// least one direction - `tile_type_id`s for the *singular* grid and `pattern_id`s for the *overlap* one.
+    ///
+    /// Such options are pruned before the generation, but finding them usually points to a broken ruleset.
+    fn impossible_options(&self) -> Vec<u64> {
+        self._option_data().impossible_options()
+    }
+
     /// Returns all possitions in the internal grid holds collapsed or uncollapsed tiles are either collapsed.
     fn retrieve_positions(&self, collapsed: bool) -> Vec<GridPosition> {
         let func = if collapsed {
@@ -125,3 +217,72 @@ pub(crate) mod private {
         fn _get_initial_propagate_items(&self, to_collapse: &[GridPosition]) -> Vec<PropagateItem>;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::map::GridSize;
+    use crate::tile::identifiable::IdentifiableTileData;
+    use crate::tile::{GridPosition, TileContainer};
+
+    use super::{CollapsedGrid, CollapsedTileData};
+
+    fn sample_grid() -> CollapsedGrid {
+        let mut grid = CollapsedGrid::new(GridSize::new_xy(4, 3));
+        grid.insert_data(&GridPosition::new_xy(0, 0), CollapsedTileData::new(7));
+        grid.insert_data(
+            &GridPosition::new_xy(3, 2),
+            CollapsedTileData::new(u64::MAX),
+        );
+        grid
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let grid = sample_grid();
+        let decoded = CollapsedGrid::from_bytes(&grid.to_bytes()).unwrap();
+
+        assert_eq!(grid.as_ref().size(), decoded.as_ref().size());
+        assert_eq!(
+            grid.as_ref().get_all_positions(),
+            decoded.as_ref().get_all_positions()
+        );
+        for tile in grid.as_ref().iter_tiles() {
+            let decoded_tile = decoded
+                .as_ref()
+                .get_tile_at_position(&tile.grid_position())
+                .unwrap();
+            assert_eq!(
+                tile.as_ref().tile_type_id(),
+                decoded_tile.as_ref().tile_type_id()
+            );
+        }
+    }
+
+    #[test]
+    fn bytes_invalid() {
+        let bytes = sample_grid().to_bytes();
+        assert!(CollapsedGrid::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(CollapsedGrid::from_bytes(&bytes[..5]).is_err());
+
+        let mut out_of_bounds = bytes.clone();
+        out_of_bounds[12..16].copy_from_slice(&4u32.to_le_bytes());
+        let err = CollapsedGrid::from_bytes(&out_of_bounds).unwrap_err();
+        assert!(err.to_string().contains("outside"));
+
+        let mut too_many = bytes.clone();
+        too_many[..8].copy_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0]);
+        let err = CollapsedGrid::from_bytes(&too_many).unwrap_err();
+        assert!(err.to_string().contains("exceeds"));
+
+        let mut count_overflow = bytes.clone();
+        count_overflow[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(CollapsedGrid::from_bytes(&count_overflow).is_err());
+
+        let mut huge = Vec::new();
+        huge.extend_from_slice(&u32::MAX.to_le_bytes());
+        huge.extend_from_slice(&u32::MAX.to_le_bytes());
+        huge.extend_from_slice(&0u32.to_le_bytes());
+        let err = CollapsedGrid::from_bytes(&huge).unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
+}
@@ -46,7 +46,7 @@ mod tile;
 use std::{collections::HashSet, ops::Index};
 
 // Flattened reexports
-pub use error::CollapseError;
+pub use error::{CollapseError, CollapsedGridDecodeError};
 pub use grid::{CollapsedGrid, CollapsibleGrid};
 pub use queue::*;
 pub use tile::*;
@@ -71,16 +71,57 @@ impl Adjacencies {
 
     #[inline(always)]
     pub fn add_at_dir(&mut self, direction: GridDir, id: u64) {
-        let v = self.inner.get_mut(direction as usize).unwrap();
+        let v = self.inner.get_mut(direction.index()).unwrap();
         v.insert(id);
     }
+
+    /// Returns adjacencies present in both `self` and `other`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .zip(other.inner.iter())
+                .map(|(first, second)| first.intersection(second).copied().collect())
+                .collect(),
+        }
+    }
+
+    /// Adds all adjacencies present in `other`.
+    pub fn union(&mut self, other: &Self) {
+        for (set, other_set) in self.inner.iter_mut().zip(other.inner.iter()) {
+            set.extend(other_set.iter().copied());
+        }
+    }
+
+    /// Retains only the adjacent ids for which `f` returns `true`.
+    pub fn retain(&mut self, f: impl Fn(&u64) -> bool) {
+        for set in self.inner.iter_mut() {
+            set.retain(&f);
+        }
+    }
+
+    /// Removes the adjacent ids for which `f` returns `true`, returning them.
+    pub fn split_off(&mut self, f: impl Fn(&u64) -> bool) -> Self {
+        Self {
+            inner: self
+                .inner
+                .iter_mut()
+                .map(|set| {
+                    let removed = set.iter().copied().filter(&f).collect::<HashSet<_>>();
+                    set.retain(|id| !removed.contains(id));
+                    removed
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Index<GridDir> for Adjacencies {
     type Output = HashSet<u64>;
 
     fn index(&self, index: GridDir) -> &Self::Output {
-        &self.inner[index as usize]
+        &self.inner[index.index()]
     }
 }
 
@@ -110,7 +151,7 @@ impl overlap::Subscriber for DebugSubscriber {
 }
 
 pub(crate) mod private {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
     use crate::map::GridDir;
 
@@ -141,6 +182,68 @@ pub(crate) mod private {
             }
         }
 
+        /// Returns table containing only the adjacencies present in both `self` and `other`.
+        pub(crate) fn intersect(&self, other: &Self) -> Self {
+            Self {
+                inner: self
+                    .inner
+                    .iter()
+                    .filter_map(|(el_id, adjacencies)| {
+                        other
+                            .inner
+                            .get(el_id)
+                            .map(|other_adj| (*el_id, adjacencies.intersect(other_adj)))
+                    })
+                    .collect(),
+            }
+        }
+
+        /// Adds all elements and adjacencies present in `other`.
+        pub(crate) fn merge(&mut self, other: &Self) {
+            for (el_id, adjacencies) in other.inner.iter() {
+                match self.inner.entry(*el_id) {
+                    std::collections::hash_map::Entry::Occupied(mut e) => {
+                        e.get_mut().union(adjacencies)
+                    }
+                    std::collections::hash_map::Entry::Vacant(e) => {
+                        e.insert(adjacencies.clone());
+                    }
+                }
+            }
+        }
+
+        /// Makes sure that the element is present in the table, even without any adjacencies.
+        #[cfg(feature = "serde")]
+        pub(crate) fn insert_element(&mut self, el_id: u64) {
+            if let std::collections::hash_map::Entry::Vacant(e) = self.inner.entry(el_id) {
+                e.insert(Adjacencies::new());
+            }
+        }
+
+        /// Removes all elements contained in `ids`, alongside all adjacencies pointing to them. Removed adjacencies
+        /// are returned, so they can be restored with [`merge`](Self::merge).
+        pub(crate) fn take_ids(&mut self, ids: &HashSet<u64>) -> Self {
+            let mut taken = HashMap::new();
+            for id in ids {
+                if let Some(adjacencies) = self.inner.remove(id) {
+                    taken.insert(*id, adjacencies);
+                }
+            }
+            for (el_id, adjacencies) in self.inner.iter_mut() {
+                let removed = adjacencies.split_off(|adj_id| ids.contains(adj_id));
+                taken.insert(*el_id, removed);
+            }
+            Self { inner: taken }
+        }
+
+        /// Removes all elements not contained in `ids`, alongside all adjacencies pointing to them.
+        pub(crate) fn retain_ids(&mut self, ids: &HashSet<u64>) {
+            self.inner.retain(|el_id, _| ids.contains(el_id));
+            for adjacencies in self.inner.values_mut() {
+                adjacencies.retain(|adj_id| ids.contains(adj_id));
+            }
+        }
+
         pub(crate) fn get_all_adjacencies_in_direction(
             &self,
             el_id: &u64,
@@ -1,8 +1,10 @@
 use std::{
     collections::{BTreeMap, HashMap},
-    ops::{Index, IndexMut},
+    ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign},
 };
 
+use rand::Rng;
+
 use crate::{
     map::{DirectionTable, GridDir},
     tile::identifiable::collection::IdentTileCollection,
@@ -49,13 +51,94 @@ impl<T> AsMut<Vec<T>> for PerOptionTable<T> {
     }
 }
 
+/// Weight of the option, or the sum of weights of multiple options.
+///
+/// Integer weights provided by [`FrequencyHints`](crate::gen::collapse::singular::FrequencyHints) are kept as [`u32`],
+/// so their arithmetic is exact. Only the weights provided by
+/// [`FrequencyHintsF`](crate::gen::collapse::singular::FrequencyHintsF) are [`f32`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Weight {
+    Integer(u32),
+    Fractional(f32),
+}
+
+impl Weight {
+    /// Neutral element of the weight sum.
+    pub const ZERO: Self = Self::Integer(0);
+
+    pub fn as_f32(self) -> f32 {
+        match self {
+            Self::Integer(weight) => weight as f32,
+            Self::Fractional(weight) => weight,
+        }
+    }
+
+    pub fn is_positive(self) -> bool {
+        match self {
+            Self::Integer(weight) => weight > 0,
+            Self::Fractional(weight) => weight > 0.,
+        }
+    }
+}
+
+impl Add for Weight {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (Self::Integer(first), Self::Integer(second)) => Self::Integer(first + second),
+            (first, second) => Self::Fractional(first.as_f32() + second.as_f32()),
+        }
+    }
+}
+
+impl AddAssign for Weight {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Weight {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (Self::Integer(first), Self::Integer(second)) => Self::Integer(first - second),
+            (first, second) => Self::Fractional(first.as_f32() - second.as_f32()),
+        }
+    }
+}
+
+impl SubAssign for Weight {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Weight of the option, which can be consumed by [`PerOptionData`].
+pub trait OptionWeight: Copy {
+    fn as_weight(self) -> Weight;
+}
+
+impl OptionWeight for u32 {
+    fn as_weight(self) -> Weight {
+        Weight::Integer(self)
+    }
+}
+
+impl OptionWeight for f32 {
+    fn as_weight(self) -> Weight {
+        Weight::Fractional(self)
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct PerOptionData {
     option_map: HashMap<u64, usize>,
     option_map_rev: HashMap<u64, u64>,
     adjacencies: PerOptionTable<DirectionTable<Vec<usize>>>,
     ways_to_be_option: WaysToBeOption,
-    opt_with_weight: PerOptionTable<(u32, f32)>,
+    opt_with_weight: PerOptionTable<(Weight, f32)>,
     option_count: usize,
     possible_options_count: usize,
 }
@@ -81,18 +164,19 @@ impl IdentTileCollection for PerOptionData {
 }
 
 impl PerOptionData {
-    pub fn populate(
+    pub fn populate<W: OptionWeight>(
         &mut self,
-        options_with_weights: &BTreeMap<u64, u32>,
+        options_with_weights: &BTreeMap<u64, W>,
         adjacencies: &AdjacencyTable,
     ) {
         for (n, (option_id, option_weight)) in options_with_weights.iter().enumerate() {
             self.add_tile_data(*option_id, n);
 
-            self.opt_with_weight.as_mut().push((
-                *option_weight,
-                (*option_weight as f32) * (*option_weight as f32).log2(),
-            ));
+            let weight = option_weight.as_weight();
+            let weight_f32 = weight.as_f32();
+            self.opt_with_weight
+                .as_mut()
+                .push((weight, weight_f32 * weight_f32.log2()));
         }
 
         self.option_count = self.option_map.len();
@@ -112,14 +196,52 @@ impl PerOptionData {
         &self.adjacencies[option_id][direction]
     }
 
-    pub fn iter_weights(&self) -> impl Iterator<Item = (usize, &(u32, f32))> {
+    pub fn iter_weights(&self) -> impl Iterator<Item = (usize, &(Weight, f32))> {
         self.opt_with_weight.table.iter().enumerate()
     }
 
-    pub fn get_weights(&self, option_idx: usize) -> (u32, f32) {
+    pub fn get_weights(&self, option_idx: usize) -> (Weight, f32) {
         self.opt_with_weight.table[option_idx]
     }
 
+    /// Chooses one of the options possible in `ways`, with probability proportional to their weights. `weight_sum`
+    /// needs to be the sum of weights of all possible options.
+    pub(crate) fn choose_option<R: Rng>(
+        &self,
+        rng: &mut R,
+        weight_sum: Weight,
+        ways: &WaysToBeOption,
+    ) -> Option<usize> {
+        match weight_sum {
+            Weight::Integer(weight_sum) => {
+                let random = rng.gen_range(0..weight_sum);
+                let mut current_sum = 0;
+                ways.iter_possible().find(|option_idx| {
+                    if let Weight::Integer(weight) = self.get_weights(*option_idx).0 {
+                        current_sum += weight;
+                    }
+                    random <= current_sum
+                })
+            }
+            Weight::Fractional(_) => {
+                // Sum kept by the tile can drift from the sum of the weights due to rounding, so the total is
+                // calculated anew in the same order as the options are checked, guaranteeing that one is chosen.
+                let total = ways.iter_possible().fold(0., |sum, option_idx| {
+                    sum + self.get_weights(option_idx).0.as_f32()
+                });
+                if total <= 0. {
+                    return None;
+                }
+                let random = rng.gen_range(0.0..total);
+                let mut current_sum = 0.;
+                ways.iter_possible().find(|option_idx| {
+                    current_sum += self.get_weights(*option_idx).0.as_f32();
+                    random < current_sum
+                })
+            }
+        }
+    }
+
     pub fn num_options(&self) -> usize {
         self.option_count
     }
@@ -128,6 +250,21 @@ impl PerOptionData {
         self.possible_options_count
     }
 
+    /// Returns identifiers of all options, which can never be placed because they have no valid neighbours in at least
+    /// one direction.
+    pub fn impossible_options(&self) -> Vec<u64> {
+        let mut out = self
+            .adjacencies
+            .table
+            .iter()
+            .enumerate()
+            .filter(|(_, adj)| GridDir::ALL_2D.iter().any(|dir| adj[*dir].is_empty()))
+            .map(|(option_idx, _)| self.get_tile_type_id(&option_idx).unwrap())
+            .collect::<Vec<_>>();
+        out.sort_unstable();
+        out
+    }
+
     pub fn get_ways_to_become_option(&self) -> &WaysToBeOption {
         &self.ways_to_be_option
     }
@@ -3,10 +3,11 @@ use crate::tile::identifiable::collection::IdentTileCollection;
 use crate::tile::identifiable::IdentifiableTileData;
 
 use super::pattern::{
-    OverlappingPattern, OverlappingPatternGrid, PatternCollection, PatternTileData,
+    OverlappingPattern, OverlappingPatternGrid, PatternCollection, PatternSymmetries,
+    PatternTileData,
 };
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::marker::PhantomData;
 
 use crate::gen::collapse::private::AdjacencyTable;
@@ -24,6 +25,8 @@ where
     collection: PatternCollection<P>,
     frequency: FrequencyHints<P, Data>,
     adjacency: AdjacencyRules<P, Data>,
+    wrapping: bool,
+    symmetries: PatternSymmetries,
 }
 
 impl<P: OverlappingPattern, Data: IdentifiableTileData> Default for Analyzer<P, Data>
@@ -35,24 +38,117 @@ where
             collection: Default::default(),
             frequency: Default::default(),
             adjacency: Default::default(),
+            wrapping: false,
+            symmetries: PatternSymmetries::NONE,
         }
     }
 }
 
 impl<P: OverlappingPattern, Data: IdentifiableTileData> Analyzer<P, Data> {
+    /// If set to `true`, patterns extending past the border of analyzed maps wrap around to their opposite side,
+    /// so the tiles at the edges contribute patterns as the ones in the interior. Useful for tileable (toroidal)
+    /// samples. Defaults to `false`.
+    pub fn with_wrapping(mut self, wrapping: bool) -> Self {
+        self.wrapping = wrapping;
+        self
+    }
+
+    /// Selects the symmetries used to synthesize additional variants of each extracted pattern, eg. to make patterns
+    /// found in the sample in one orientation available also in the rotated ones. Each variant is counted as many
+    /// times as its source pattern was found. Defaults to [`PatternSymmetries::NONE`].
+    pub fn with_symmetries(mut self, symmetries: PatternSymmetries) -> Self {
+        self.symmetries = symmetries;
+        self
+    }
+
     /// Analyzes the [`GridMap2D`] of [`IdentifiableTileData`], gathering elements necessary for creation of new
     /// [`CollapsiblePatternGrid`](crate::gen::collapse::overlap::CollapsiblePatternGrid) to collapse.
     ///
     /// Returns [`OverlappingPatternGrid`], which is a transformed source map if more insights about which patterns
     /// were discoveren in specific positions on the map.
     pub fn analyze(&mut self, map: &GridMap2D<Data>) -> OverlappingPatternGrid<P> {
-        let grid = OverlappingPatternGrid::from_map(map, &mut self.collection);
+        let grid = OverlappingPatternGrid::from_map_with_wrapping(
+            map,
+            &mut self.collection,
+            self.wrapping,
+        );
         self.frequency.analyze_pattern_grid(&grid);
+        self.add_symmetric_variants(&grid);
         self.adjacency.analyze_collection(&self.collection);
 
         grid
     }
 
+    /// Prunes the analyzed data, keeping only `keep_top_n` most frequent patterns. Pruned patterns are consistently
+    /// removed from the [`PatternCollection`], [`FrequencyHints`] and [`AdjacencyRules`]. Patterns with equal frequency
+    /// are ordered by their `pattern_id`.
+    ///
+    /// Useful for big samples, which yield many rare patterns slowing down the generation and adding noise to it.
+    pub fn prune_patterns(&mut self, keep_top_n: usize) {
+        let mut by_frequency = self
+            .frequency
+            .weights
+            .iter()
+            .map(|(pattern_id, weight)| (*pattern_id, *weight))
+            .collect::<Vec<_>>();
+        by_frequency.sort_by(|(id_a, weight_a), (id_b, weight_b)| {
+            weight_b.cmp(weight_a).then(id_a.cmp(id_b))
+        });
+
+        let kept = by_frequency
+            .iter()
+            .take(keep_top_n)
+            .map(|(pattern_id, _)| *pattern_id)
+            .collect::<HashSet<_>>();
+
+        for (pattern_id, _) in by_frequency.iter().skip(keep_top_n) {
+            self.collection.remove_tile_data(pattern_id);
+        }
+        self.frequency
+            .weights
+            .retain(|pattern_id, _| kept.contains(pattern_id));
+        self.adjacency.inner.retain_ids(&kept);
+    }
+
+    /// Adds variants of the patterns found in the `grid` to the collection, counting them for each occurence of their
+    /// source pattern.
+    fn add_symmetric_variants(&mut self, grid: &OverlappingPatternGrid<P>) {
+        if self.symmetries.is_empty() {
+            return;
+        }
+        let mut variants = BTreeMap::<u64, Vec<P>>::new();
+        for tile in grid.inner().iter_tiles() {
+            let PatternTileData::WithPattern { pattern_id, .. } = tile.as_ref() else {
+                continue;
+            };
+            let pattern_variants = variants.entry(*pattern_id).or_insert_with(|| {
+                let source = self
+                    .collection
+                    .get_tile_data(pattern_id)
+                    .expect("cannot get analyzed pattern");
+                let mut out = Vec::<P>::new();
+                for variant in self
+                    .symmetries
+                    .iter()
+                    .filter_map(|symmetry| source.transformed(symmetry))
+                {
+                    if variant.pattern_id() != *pattern_id
+                        && !out.iter().any(|v| v.pattern_id() == variant.pattern_id())
+                    {
+                        out.push(variant);
+                    }
+                }
+                out
+            });
+            for variant in pattern_variants.iter() {
+                self.frequency.count_pattern(variant.pattern_id());
+            }
+        }
+        for variant in variants.into_values().flatten() {
+            self.collection.add_tile(variant);
+        }
+    }
+
     pub fn get_collection(&self) -> &PatternCollection<P> {
         &self.collection
     }
@@ -230,7 +326,7 @@ where
         let Some(adj) = self.inner().as_ref().get(&pattern_id) else {
             return false;
         };
-        adj.inner[direction as usize].contains(&other_pattern_id)
+        adj.inner[direction.index()].contains(&other_pattern_id)
     }
 }
 
@@ -54,6 +54,64 @@ where
     fn secondary_tile_positions(anchor_pos: &GridPosition) -> Vec<GridPosition>;
 }
 
+/// Set of symmetries used to synthesize additional variants of the patterns extracted from sample maps, set with
+/// [`Analyzer::with_symmetries`](crate::gen::collapse::overlap::Analyzer::with_symmetries).
+///
+/// Flags can be combined with `|` operator. Rotations are clockwise and are applied only to patterns with equal sizes
+/// on `x` and `y` axes, with the exception of the rotation by 180 degrees.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PatternSymmetries(u8);
+
+impl PatternSymmetries {
+    /// No additional variants.
+    pub const NONE: Self = Self(0);
+    /// Rotation by 90 degrees.
+    pub const ROTATE_90: Self = Self(1);
+    /// Rotation by 180 degrees.
+    pub const ROTATE_180: Self = Self(1 << 1);
+    /// Rotation by 270 degrees.
+    pub const ROTATE_270: Self = Self(1 << 2);
+    /// Mirroring along the `x` axis, swapping left and right side of the pattern.
+    pub const MIRROR_X: Self = Self(1 << 3);
+    /// Mirroring along the `y` axis, swapping top and bottom side of the pattern.
+    pub const MIRROR_Y: Self = Self(1 << 4);
+    /// All rotations and mirrorings.
+    pub const ALL: Self = Self(0b11111);
+
+    const EACH: [Self; 5] = [
+        Self::ROTATE_90,
+        Self::ROTATE_180,
+        Self::ROTATE_270,
+        Self::MIRROR_X,
+        Self::MIRROR_Y,
+    ];
+
+    /// Checks if all symmetries from `other` are contained.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Checks if no symmetry is selected.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the single symmetries contained.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Self> + '_ {
+        Self::EACH
+            .into_iter()
+            .filter(|symmetry| self.contains(*symmetry))
+    }
+}
+
+impl std::ops::BitOr for PatternSymmetries {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
 /// [OverlappingPattern] for two-dimensional grids.
 pub type OverlappingPattern2D<const X_LEN: usize, const Y_LEN: usize> =
     OverlappingPattern3D<X_LEN, Y_LEN, 1>;
@@ -107,6 +165,8 @@ impl<const P_X: usize, const P_Y: usize, const P_Z: usize> OverlappingPattern
             GridDir::DOWN => self.compare_down(other),
             GridDir::LEFT => self.compare_left(other),
             GridDir::RIGHT => self.compare_right(other),
+            // Patterns are placed only within two-dimensional grids.
+            GridDir::ABOVE | GridDir::BELOW => false,
         }
     }
 
@@ -121,7 +181,9 @@ impl<const P_X: usize, const P_Y: usize, const P_Z: usize> OverlappingPattern
                     out.push({
                         let mut pos = *anchor_pos;
                         pos.add_xy((x_off as u32, y_off as u32));
-                        pos.add_z(z_off as u32);
+                        if P_Z > 1 {
+                            pos.add_z(z_off as u32);
+                        }
                         pos
                     })
                 }
@@ -260,6 +322,10 @@ impl<P: OverlappingPattern> PatternCollection<P> {
         }
     }
 
+    /// Iterates over `tile_type_id`s of the primary tiles of all contained patterns.
+    ///
+    /// Shadows [`IdentTileCollection::iter_tile_types`], which iterates over `pattern_id`s of the patterns, as they
+    /// are the keys of the collection.
     pub fn iter_tile_types(&self) -> impl Iterator<Item = &u64> {
         self.by_tile_id.keys()
     }
@@ -327,6 +393,18 @@ impl<P: OverlappingPattern> OverlappingPatternGrid<P> {
     pub fn from_map<Data: IdentifiableTileData>(
         map: &GridMap2D<Data>,
         collection: &mut PatternCollection<P>,
+    ) -> Self {
+        Self::from_map_with_wrapping(map, collection, false)
+    }
+
+    /// Prepare new instance out of [`GridMap2D`], populating provided [`PatternCollection`] in the process.
+    ///
+    /// If `wrapping` is `true`, patterns extending past the border of the map wrap around to its opposite side, so
+    /// every tile of the map is the main tile of some pattern. Useful for tileable (toroidal) inputs.
+    pub fn from_map_with_wrapping<Data: IdentifiableTileData>(
+        map: &GridMap2D<Data>,
+        collection: &mut PatternCollection<P>,
+        wrapping: bool,
     ) -> Self {
         let mut instance = Self {
             inner: GridMap2D::new(*map.size()),
@@ -334,7 +412,12 @@ impl<P: OverlappingPattern> OverlappingPatternGrid<P> {
         };
 
         for position in map.get_all_positions() {
-            if let Some(pattern) = instance.create_pattern(map, &position) {
+            let pattern = if wrapping {
+                Some(Self::create_wrapped_pattern(map, &position))
+            } else {
+                instance.create_pattern(map, &position)
+            };
+            if let Some(pattern) = pattern {
                 let tile = PatternTileData::WithPattern {
                     tile_type_id: pattern.tile_type_id(),
                     pattern_id: pattern.pattern_id(),
@@ -380,6 +463,47 @@ impl<P: OverlappingPattern> OverlappingPatternGrid<P> {
         None
     }
 
+    fn create_wrapped_pattern<Data: IdentifiableTileData>(
+        map: &GridMap2D<Data>,
+        anchor_pos: &GridPosition,
+    ) -> P {
+        let mut pattern = P::empty();
+        for (offset, position) in Self::generate_wrapped_pattern_positions(anchor_pos, map.size()) {
+            if let Some(tile) = map.get_tile_at_position(&position) {
+                pattern.set_id_for_offset(offset, tile.as_ref().tile_type_id());
+            }
+        }
+        pattern.finalize();
+        pattern
+    }
+
+    /// Generates positions of all tiles of the pattern anchored at `from`, alongside their offsets within the pattern,
+    /// wrapping the coordinates modulo the grid `size`.
+    fn generate_wrapped_pattern_positions(
+        from: &GridPosition,
+        size: &GridSize,
+    ) -> Vec<((usize, usize, usize), GridPosition)> {
+        let layers = size.z().unwrap_or(1);
+        // Positions without `z` can't represent the deeper layers of the pattern.
+        let z_len = if from.z().is_some() { P::Z_LEN } else { 1 };
+
+        let mut out = Vec::with_capacity(P::X_LEN * P::Y_LEN * z_len);
+        for z_off in 0..z_len {
+            for y_off in 0..P::Y_LEN {
+                for x_off in 0..P::X_LEN {
+                    let x = (*from.x() + x_off as u32) % size.x();
+                    let y = (*from.y() + y_off as u32) % size.y();
+                    let position = match from.z() {
+                        Some(z) => GridPosition::new_xyz(x, y, (z + z_off as u32) % layers),
+                        None => GridPosition::new_xy(x, y),
+                    };
+                    out.push(((x_off, y_off, z_off), position));
+                }
+            }
+        }
+        out
+    }
+
     fn generate_pattern_positions(
         &self,
         from: &GridPosition,
@@ -409,7 +533,15 @@ mod private {
             tile_type_id: u64,
         );
 
+        fn set_id_for_offset(&mut self, offset: (usize, usize, usize), tile_type_id: u64);
+
         fn finalize(&mut self);
+
+        /// Creates variant of the pattern transformed by single symmetry. Returns [`None`] if the pattern can't be
+        /// transformed that way.
+        fn transformed(&self, symmetry: PatternSymmetries) -> Option<Self>
+        where
+            Self: Sized;
     }
 
     impl<const P_X: usize, const P_Y: usize, const P_Z: usize> Sealed
@@ -440,12 +572,41 @@ mod private {
             }
         }
 
+        fn set_id_for_offset(&mut self, offset: (usize, usize, usize), tile_type_id: u64) {
+            let (x_off, y_off, z_off) = offset;
+            self.tile_type_ids[z_off][y_off][x_off] = tile_type_id;
+        }
+
         fn finalize(&mut self) {
             let mut hasher = DefaultHasher::default();
             self.hash(&mut hasher);
             self.pattern_id = hasher.finish();
             self.tile_type_id = self.tile_type_ids[0][0][0];
         }
+
+        fn transformed(&self, symmetry: PatternSymmetries) -> Option<Self> {
+            let square = P_X == P_Y;
+            let source: fn(usize, usize) -> (usize, usize) = match symmetry {
+                PatternSymmetries::ROTATE_90 if square => |x, y| (y, P_Y - 1 - x),
+                PatternSymmetries::ROTATE_180 => |x, y| (P_X - 1 - x, P_Y - 1 - y),
+                PatternSymmetries::ROTATE_270 if square => |x, y| (P_X - 1 - y, x),
+                PatternSymmetries::MIRROR_X => |x, y| (P_X - 1 - x, y),
+                PatternSymmetries::MIRROR_Y => |x, y| (x, P_Y - 1 - y),
+                _ => return None,
+            };
+
+            let mut out = Self::empty();
+            for z in 0..P_Z {
+                for y in 0..P_Y {
+                    for x in 0..P_X {
+                        let (source_x, source_y) = source(x, y);
+                        out.tile_type_ids[z][y][x] = self.tile_type_ids[z][source_y][source_x];
+                    }
+                }
+            }
+            out.finalize();
+            Some(out)
+        }
     }
 }
 
@@ -454,11 +615,12 @@ mod test {
     use crate::{
         gen::collapse::{overlap::Analyzer, CollapsedTileData},
         map::{GridDir, GridMap2D, GridSize},
-        tile::{GridPosition, GridTile},
+        tile::{identifiable::collection::IdentTileCollection, GridPosition, GridTile},
     };
 
     use super::{
-        OverlappingPattern, OverlappingPattern2D, OverlappingPatternGrid, PatternTileData,
+        OverlappingPattern, OverlappingPattern2D, OverlappingPatternGrid, PatternSymmetries,
+        PatternTileData,
     };
 
     /// ```
@@ -534,4 +696,112 @@ mod test {
         assert!(!adjacency_rules.is_valid_at_dir(p0000.1, GridDir::UP, p0101.1));
         assert!(!adjacency_rules.is_valid_at_dir(p0000.1, GridDir::DOWN, p0101.1));
     }
+
+    #[test]
+    fn prune_keeps_top_patterns() {
+        let mut analyzer = Analyzer::<OverlappingPattern2D<2, 2>, _>::default();
+        analyzer.analyze(&test_grid_2d_2x2());
+        assert!(analyzer.get_collection().inner().len() > 3);
+
+        analyzer.prune_patterns(3);
+
+        let kept = analyzer
+            .get_collection()
+            .inner()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(3, kept.len());
+        assert_eq!(3, analyzer.get_frequency().get_all_weights_cloned().len());
+
+        let adjacency = analyzer.get_adjacency().inner().as_ref();
+        assert_eq!(3, adjacency.len());
+        for (pattern_id, adjacencies) in adjacency {
+            assert!(kept.contains(pattern_id));
+            assert!(analyzer.get_frequency().get_weight_for_pattern(*pattern_id) > 0);
+            for dir in GridDir::ALL_2D {
+                assert!(adjacencies[*dir].iter().all(|id| kept.contains(id)));
+            }
+        }
+    }
+
+    #[test]
+    fn wrapping_covers_edges() {
+        // Tileable checkerboard - each tile is different from its neighbours, also across the edges.
+        let size = GridSize::new_xy(4, 4);
+        let mut map = GridMap2D::new(size);
+        for position in size.get_all_possible_positions() {
+            map.insert_data(
+                &position,
+                CollapsedTileData::new(((position.x() + position.y()) % 2) as u64),
+            );
+        }
+
+        let mut analyzer = Analyzer::<OverlappingPattern2D<2, 2>, _>::default();
+        let grid = analyzer.analyze(&map);
+        let edge = GridPosition::new_xy(3, 3);
+        assert!(matches!(
+            grid.inner.get_tile_at_position(&edge).unwrap().as_ref(),
+            PatternTileData::OnlyId { .. }
+        ));
+
+        let mut analyzer = Analyzer::<OverlappingPattern2D<2, 2>, _>::default().with_wrapping(true);
+        let grid = analyzer.analyze(&map);
+        assert_eq!(2, analyzer.get_collection().inner().len());
+        let (_, interior) = retrieve_pattern(&GridPosition::new_xy(1, 1), &grid);
+        let (_, edge) = retrieve_pattern(&edge, &grid);
+        assert_eq!(interior, edge);
+        for (pattern_id, _) in analyzer.get_collection().inner().iter() {
+            assert_eq!(
+                8,
+                analyzer.get_frequency().get_weight_for_pattern(*pattern_id)
+            );
+        }
+    }
+
+    #[test]
+    fn rotated_variants_follow_source_rules() {
+        // Vertical stripes - only rules for vertical stripes can be learned from the sample itself.
+        let size = GridSize::new_xy(4, 4);
+        let mut map = GridMap2D::new(size);
+        for position in size.get_all_possible_positions() {
+            map.insert_data(&position, CollapsedTileData::new((position.x() % 2) as u64));
+        }
+
+        let mut analyzer = Analyzer::<OverlappingPattern2D<2, 2>, _>::default()
+            .with_wrapping(true)
+            .with_symmetries(PatternSymmetries::ROTATE_90 | PatternSymmetries::ROTATE_180);
+        analyzer.analyze(&map);
+        // 180 degrees rotation of vertical stripe pattern is the other vertical stripe pattern.
+        assert_eq!(4, analyzer.get_collection().inner().len());
+
+        let find_pattern = |ids: [[u64; 2]; 2]| {
+            analyzer
+                .get_collection()
+                .inner()
+                .values()
+                .find(|pattern| pattern.tile_type_ids[0] == ids)
+                .map(|pattern| pattern.pattern_id())
+                .expect("cannot find pattern")
+        };
+        let vertical = find_pattern([[0, 1], [0, 1]]);
+        let top = find_pattern([[0, 0], [1, 1]]);
+        let bottom = find_pattern([[1, 1], [0, 0]]);
+
+        let frequency = analyzer.get_frequency();
+        // Counted both for itself and as the rotation of the other vertical stripe pattern.
+        assert_eq!(16, frequency.get_weight_for_pattern(vertical));
+        assert_eq!(8, frequency.get_weight_for_pattern(top));
+        assert_eq!(8, frequency.get_weight_for_pattern(bottom));
+
+        let adjacency = analyzer.get_adjacency();
+        assert!(!adjacency.is_valid_at_dir(vertical, GridDir::LEFT, vertical));
+        assert!(adjacency.is_valid_at_dir(vertical, GridDir::UP, vertical));
+        // Horizontal stripes need to alternate vertically, as vertical stripes alternate horizontally.
+        assert!(adjacency.is_valid_at_dir(top, GridDir::UP, bottom));
+        assert!(adjacency.is_valid_at_dir(top, GridDir::DOWN, bottom));
+        assert!(!adjacency.is_valid_at_dir(top, GridDir::UP, top));
+        assert!(adjacency.is_valid_at_dir(top, GridDir::LEFT, top));
+        assert!(!adjacency.is_valid_at_dir(top, GridDir::LEFT, bottom));
+    }
 }
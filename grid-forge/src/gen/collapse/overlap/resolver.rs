@@ -1,13 +1,14 @@
 use std::marker::PhantomData;
 
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 
 use crate::gen::collapse::error::{CollapseError, CollapseErrorKind};
 use crate::gen::collapse::grid::private::Sealed;
 use crate::gen::collapse::overlap::CollapsiblePattern;
 use crate::gen::collapse::queue::CollapseQueue;
 use crate::gen::collapse::tile::CollapsibleTileData;
-use crate::gen::collapse::{EntrophyQueue, PositionQueue, PropagateItem, Propagator};
+use crate::gen::collapse::{EntrophyQueue, PropagateItem, Propagator};
+use crate::gen::DefaultRng;
 
 use crate::tile::identifiable::collection::IdentTileCollection;
 use crate::tile::identifiable::IdentifiableTileData;
@@ -54,10 +55,23 @@ where
     }
 
     pub fn generate_entrophy<R>(
+        &mut self,
+        grid: CollapsiblePatternGrid<P, Data>,
+        rng: &mut R,
+        positions: &[GridPosition],
+    ) -> Result<CollapsiblePatternGrid<P, Data>, CollapseError>
+    where
+        R: Rng,
+    {
+        self.generate_entrophy_with_queue(grid, rng, positions, EntrophyQueue::default())
+    }
+
+    fn generate_entrophy_with_queue<R>(
         &mut self,
         mut grid: CollapsiblePatternGrid<P, Data>,
         rng: &mut R,
         positions: &[GridPosition],
+        mut queue: EntrophyQueue,
     ) -> Result<CollapsiblePatternGrid<P, Data>, CollapseError>
     where
         R: Rng,
@@ -66,7 +80,6 @@ where
         use crate::gen::collapse::tile::private::Sealed as _;
 
         let mut iter = 0;
-        let mut queue = EntrophyQueue::default();
 
         if let Some(subscriber) = self.subscriber.as_mut() {
             subscriber.on_generation_start();
@@ -135,17 +148,39 @@ where
         Ok(grid)
     }
 
-    pub fn generate_position<R>(
+    /// Collapse the grid using [`DefaultRng`] seeded with `seed`, making the generation reproducible.
+    ///
+    /// If propagating `queue` ([`EntrophyQueue`]) is provided, the collapse is done the same way as in
+    /// [`generate_entrophy`](Self::generate_entrophy), otherwise as in [`generate_position`](Self::generate_position).
+    /// Provided [`EntrophyQueue`] is used as is, keeping its tie breaking configuration.
+    pub fn generate_seeded<Q>(
+        &mut self,
+        grid: CollapsiblePatternGrid<P, Data>,
+        seed: [u8; 32],
+        positions: &[GridPosition],
+        queue: Q,
+    ) -> Result<CollapsiblePatternGrid<P, Data>, CollapseError>
+    where
+        Q: CollapseQueue,
+    {
+        let mut rng = DefaultRng::from_seed(seed);
+        match queue.into_entrophy() {
+            Ok(queue) => self.generate_entrophy_with_queue(grid, &mut rng, positions, queue),
+            Err(queue) => self.generate_position(grid, &mut rng, positions, queue),
+        }
+    }
+
+    pub fn generate_position<R, Q>(
         &mut self,
         mut grid: CollapsiblePatternGrid<P, Data>,
         rng: &mut R,
         position: &[GridPosition],
-        mut queue: PositionQueue,
+        mut queue: Q,
     ) -> Result<CollapsiblePatternGrid<P, Data>, CollapseError>
     where
         R: Rng,
+        Q: CollapseQueue,
     {
-        use crate::gen::collapse::queue::private::Sealed as _;
         use crate::gen::collapse::tile::private::Sealed as _;
         let mut iter = 0;
 
@@ -186,6 +221,9 @@ where
 
             to_collapse.as_mut().collapse_basic(rng, &grid.option_data);
             let collapsed_idx = to_collapse.as_ref().collapse_idx().unwrap();
+            if queue.needs_update_after_collapse() {
+                queue.update_queue(&to_collapse);
+            }
             CollapsiblePattern::purge_options_for_neighbours(
                 &mut grid.pattern_grid,
                 collapsed_idx,
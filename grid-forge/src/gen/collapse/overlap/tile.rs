@@ -6,7 +6,7 @@ use rand::distributions::Distribution;
 use rand::Rng;
 
 use crate::gen::collapse::error::CollapsibleGridError;
-use crate::gen::collapse::option::{PerOptionData, WaysToBeOption};
+use crate::gen::collapse::option::{PerOptionData, WaysToBeOption, Weight};
 use crate::gen::collapse::{tile::*, CollapsedGrid, CollapsibleGrid, PropagateItem};
 use crate::map::{GridMap2D, GridSize};
 use crate::tile::identifiable::builders::IdentTileBuilder;
@@ -23,7 +23,7 @@ pub struct CollapsiblePattern<P: OverlappingPattern> {
     collapsed_pattern: Option<usize>,
     num_possible_patterns: usize,
     ways_to_be_pattern: WaysToBeOption,
-    weight_sum: u32,
+    weight_sum: Weight,
     pub(crate) weight_log_sum: f32,
     pub(crate) entrophy_noise: f32,
     pattern_type: PhantomData<P>,
@@ -36,7 +36,7 @@ impl<P: OverlappingPattern> private::Sealed for CollapsiblePattern<P> {
         position: GridPosition,
         num_options: usize,
         ways_to_be_option: WaysToBeOption,
-        weight_sum: u32,
+        weight_sum: Weight,
         weight_log_sum: f32,
         entrophy_noise: f32,
     ) -> GridTile<Self>
@@ -65,7 +65,7 @@ impl<P: OverlappingPattern> private::Sealed for CollapsiblePattern<P> {
         &mut self.ways_to_be_pattern
     }
 
-    fn remove_option(&mut self, weights: (u32, f32)) {
+    fn remove_option(&mut self, weights: (Weight, f32)) {
         self.num_possible_patterns -= 1;
         self.weight_sum -= weights.0;
         self.weight_log_sum -= weights.1;
@@ -76,23 +76,17 @@ impl<P: OverlappingPattern> private::Sealed for CollapsiblePattern<P> {
         rng: &mut R,
         options_data: &crate::gen::collapse::option::PerOptionData,
     ) -> Option<Vec<usize>> {
-        assert!(self.weight_sum > 0);
-        let random = rng.gen_range(0..self.weight_sum);
-        let mut current_sum = 0;
-        let mut chosen = None;
-        let mut out = Vec::new();
-        for option_idx in self.ways_to_be_option().iter_possible() {
-            current_sum += options_data.get_weights(option_idx).0;
-            if chosen.is_some() || random > current_sum {
-                out.push(option_idx);
-                continue;
-            }
-            chosen = Some(option_idx);
-        }
+        assert!(self.weight_sum.is_positive());
+        let chosen = options_data.choose_option(rng, self.weight_sum, self.ways_to_be_option());
         assert!(chosen.is_some(), "option should always be chosen!");
+        let out = self
+            .ways_to_be_option()
+            .iter_possible()
+            .filter(|option_idx| Some(*option_idx) != chosen)
+            .collect();
         self.collapsed_pattern = chosen;
         self.num_possible_patterns = 0;
-        self.weight_sum = 0;
+        self.weight_sum = Weight::ZERO;
         self.weight_log_sum = 0.;
         Some(out)
     }
@@ -100,11 +94,11 @@ impl<P: OverlappingPattern> private::Sealed for CollapsiblePattern<P> {
     fn mark_collapsed(&mut self, collapsed_idx: usize) {
         self.collapsed_pattern = Some(collapsed_idx);
         self.num_possible_patterns = 0;
-        self.weight_sum = 0;
+        self.weight_sum = Weight::ZERO;
         self.weight_log_sum = 0.;
     }
 
-    fn weight_sum(&self) -> u32 {
+    fn weight_sum(&self) -> Weight {
         self.weight_sum
     }
 
@@ -127,7 +121,7 @@ impl<P: OverlappingPattern> CollapsibleTileData for CollapsiblePattern<P> {
             collapsed_pattern: Some(option_idx),
             num_possible_patterns: 0,
             ways_to_be_pattern: WaysToBeOption::default(),
-            weight_sum: 0,
+            weight_sum: Weight::ZERO,
             weight_log_sum: 0.,
             entrophy_noise: 0.,
             pattern_type: PhantomData,
@@ -135,7 +129,7 @@ impl<P: OverlappingPattern> CollapsibleTileData for CollapsiblePattern<P> {
     }
 
     fn calc_entrophy(&self) -> f32 {
-        Self::calc_entrophy_ext(self.weight_sum, self.weight_log_sum) + self.entrophy_noise
+        Self::calc_entrophy_ext(self.weight_sum.as_f32(), self.weight_log_sum) + self.entrophy_noise
     }
 }
 
@@ -274,7 +268,7 @@ where
 
             let num_options = possible_patterns.len();
 
-            let mut weights = (0u32, 0f32);
+            let mut weights = (Weight::ZERO, 0f32);
             for pattern in possible_patterns {
                 let (w, wl) = options.get_weights(pattern);
                 weights.0 += w;
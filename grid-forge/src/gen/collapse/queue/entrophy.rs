@@ -7,18 +7,33 @@ use rand::Rng;
 
 use super::CollapseQueue;
 use crate::gen::collapse::{option::PerOptionData, tile::CollapsibleTileData};
-use crate::map::GridMap2D;
+use crate::map::{GridDir, GridMap2D, GridSize};
 use crate::tile::{GridPosition, GridTile, TileContainer};
 
+/// Entrophies closer than this are considered equal while comparing the degree of the tiles. Larger than the random
+/// noise added to the tile entrophies.
+const DEGREE_TIE_TOLERANCE: f32 = 0.0001;
+
 #[derive(Clone, Copy)]
 pub(crate) struct EntrophyItem {
     pos: GridPosition,
     entrophy: f32,
+    degree: u8,
+    tie: u64,
 }
 
 impl EntrophyItem {
     pub fn new(pos: GridPosition, entrophy: f32) -> Self {
-        Self { pos, entrophy }
+        Self {
+            pos,
+            entrophy,
+            degree: 0,
+            tie: 0,
+        }
+    }
+
+    fn entrophy_bucket(&self) -> i64 {
+        (self.entrophy / DEGREE_TIE_TOLERANCE).floor() as i64
     }
 }
 
@@ -38,6 +53,16 @@ impl PartialOrd for EntrophyItem {
 
 impl Ord for EntrophyItem {
     fn cmp(&self, other: &Self) -> Ordering {
+        // Degree is taken into account only between the items with almost equal entrophy. As the buckets are monotonic,
+        // the order is the same as for entrophy alone if all degrees are equal.
+        let by_degree = self
+            .entrophy_bucket()
+            .cmp(&other.entrophy_bucket())
+            .then_with(|| other.degree.cmp(&self.degree))
+            .then_with(|| self.tie.cmp(&other.tie));
+        if by_degree != Ordering::Equal {
+            return by_degree;
+        }
         match self.entrophy.partial_cmp(&other.entrophy) {
             Some(Ordering::Equal) | None => self.pos.cmp(&other.pos),
             Some(order) => order,
@@ -48,16 +73,65 @@ impl Ord for EntrophyItem {
 /// Select next position to collapse using smallest entrophy condition.
 ///
 /// Its state will be updated every time after tile entrophy changed by removing some of its options.
-#[derive(Default)]
+///
+/// Optionally, ties between the tiles with (almost) the same entrophy can be broken by choosing the one with the most
+/// collapsed neighbours first, which is the most constrained one - see [`with_degree_tie_break`](Self::with_degree_tie_break).
+/// Remaining ties are resolved by the random noise added to the entrophy of each tile, or - if the queue was created
+/// with [`new_seeded`](Self::new_seeded) - by the value derived from the seed and the position of the tile.
+#[derive(Clone, Default)]
 pub struct EntrophyQueue {
     by_entrophy: BTreeSet<EntrophyItem>,
-    by_pos: HashMap<GridPosition, f32>,
+    by_pos: HashMap<GridPosition, EntrophyItem>,
+    degree_tie_break: bool,
+    tie_seed: Option<u64>,
+    size: Option<GridSize>,
 }
 
 impl EntrophyQueue {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Creates the queue breaking ties between the tiles with (almost) the same entrophy using a stable value derived
+    /// from the `seed` and the position of each tile, making the order of collapse fully deterministic.
+    ///
+    /// `peek` is the expected number of positions to be queued, used to preallocate the inner storage.
+    pub fn new_seeded(peek: usize, seed: u64) -> Self {
+        Self {
+            by_pos: HashMap::with_capacity(peek),
+            tie_seed: Some(seed),
+            ..Default::default()
+        }
+    }
+
+    /// If set to `true`, from the tiles with the lowest entrophy the one with the most collapsed neighbours will be
+    /// chosen first. It can reduce the number of contradictions on hard rulesets. Defaults to `false`.
+    pub fn with_degree_tie_break(mut self, degree_tie_break: bool) -> Self {
+        self.degree_tie_break = degree_tie_break;
+        self
+    }
+
+    fn insert(&mut self, item: EntrophyItem) {
+        if let Some(existing) = self.by_pos.insert(item.pos, item) {
+            self.by_entrophy.remove(&existing);
+        }
+        self.by_entrophy.insert(item);
+    }
+
+    fn increment_neighbours_degree(&mut self, position: &GridPosition) {
+        let Some(size) = self.size else {
+            return;
+        };
+        for direction in GridDir::ALL_2D {
+            let Some(neighbour) = direction.march_step(position, &size) else {
+                continue;
+            };
+            if let Some(mut item) = self.by_pos.get(&neighbour).copied() {
+                item.degree += 1;
+                self.insert(item);
+            }
+        }
+    }
 }
 
 impl CollapseQueue for EntrophyQueue {
@@ -74,13 +148,22 @@ impl CollapseQueue for EntrophyQueue {
         Tile: TileContainer + AsRef<Data>,
         Data: CollapsibleTileData,
     {
-        let item = EntrophyItem::new(tile.grid_position(), tile.as_ref().calc_entrophy());
-        if let Some(existing_entrophy) = self.by_pos.remove(&item.pos) {
-            self.by_entrophy
-                .remove(&EntrophyItem::new(item.pos, existing_entrophy));
+        let position = tile.grid_position();
+        if self.degree_tie_break && tile.as_ref().is_collapsed() {
+            if let Some(existing) = self.by_pos.remove(&position) {
+                self.by_entrophy.remove(&existing);
+            }
+            self.increment_neighbours_degree(&position);
+            return;
         }
-        self.by_pos.insert(item.pos, item.entrophy);
-        self.by_entrophy.insert(item);
+        let mut item = EntrophyItem::new(position, tile.as_ref().calc_entrophy());
+        if let Some(seed) = self.tie_seed {
+            item.tie = tie_value(seed, &position);
+        }
+        if let Some(existing) = self.by_pos.get(&position) {
+            item.degree = existing.degree;
+        }
+        self.insert(item);
     }
 
     fn len(&self) -> usize {
@@ -98,6 +181,18 @@ impl CollapseQueue for EntrophyQueue {
     }
 }
 
+/// Derives stable tie-breaking value for the position, using the SplitMix64 finalizer.
+fn tie_value(seed: u64, position: &GridPosition) -> u64 {
+    let mut value = seed
+        ^ (*position.x() as u64)
+        ^ ((*position.y() as u64) << 32)
+        ^ (position.z().unwrap_or(0) as u64).rotate_left(16);
+    value = value.wrapping_add(0x9e3779b97f4a7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+    value ^ (value >> 31)
+}
+
 impl super::private::Sealed for EntrophyQueue {
     fn populate_inner_grid<R: Rng, Data: CollapsibleTileData>(
         &mut self,
@@ -113,6 +208,22 @@ impl super::private::Sealed for EntrophyQueue {
         for tile in tiles {
             grid.insert_tile(tile);
         }
+
+        if !self.degree_tie_break {
+            return;
+        }
+        self.size = Some(*grid.size());
+        for position in positions {
+            let Some(mut item) = self.by_pos.get(position).copied() else {
+                continue;
+            };
+            item.degree = grid
+                .get_neighbours(position)
+                .iter()
+                .filter(|tile| tile.as_ref().is_collapsed())
+                .count() as u8;
+            self.insert(item);
+        }
     }
 
     fn needs_update_after_options_change(&self) -> bool {
@@ -122,4 +233,80 @@ impl super::private::Sealed for EntrophyQueue {
     fn propagating(&self) -> bool {
         true
     }
+
+    fn needs_update_after_collapse(&self) -> bool {
+        self.degree_tie_break
+    }
+
+    fn into_entrophy(self) -> Result<EntrophyQueue, Self> {
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::gen::collapse::queue::private::Sealed;
+    use crate::gen::collapse::singular::{AdjacencyRules, CollapsibleTileGrid, FrequencyHints};
+    use crate::gen::collapse::{CollapseQueue, CollapsedGrid, CollapsedTileData};
+    use crate::map::{GridDir, GridSize};
+    use crate::tile::identifiable::builders::ConstructableViaIdentifierTile;
+    use crate::tile::identifiable::BasicIdentTileData;
+    use crate::tile::{GridPosition, GridTile};
+
+    use super::EntrophyQueue;
+
+    fn first_position(seed: u64, queue: EntrophyQueue) -> GridPosition {
+        let tile = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&tile, 1);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&tile, &tile, *dir);
+        }
+
+        let size = GridSize::new_xy(5, 5);
+        let mut collapsed = CollapsedGrid::new(size);
+        collapsed.insert_data(&GridPosition::new_xy(4, 4), CollapsedTileData::new(1));
+        let mut grid =
+            CollapsibleTileGrid::new_from_collapsed(&collapsed, &frequency, &adjacency).unwrap();
+        let mut positions = size.get_all_possible_positions();
+        positions.retain(|pos| pos != &GridPosition::new_xy(4, 4));
+
+        let mut queue = queue;
+        let mut rng = ChaChaRng::seed_from_u64(seed);
+        queue.populate_inner_grid(&mut rng, &mut grid.grid, &positions, &grid.option_data);
+        queue.get_next_position().unwrap()
+    }
+
+    #[test]
+    fn seeded_tie_break_is_stable() {
+        // Same tie seed yields the same first position regardless of the rng used for the noise.
+        let first = first_position(0, EntrophyQueue::new_seeded(25, 5));
+        for seed in 1..10 {
+            assert_eq!(
+                first,
+                first_position(seed, EntrophyQueue::new_seeded(25, 5))
+            );
+        }
+    }
+
+    #[test]
+    fn degree_tie_break_prefers_constrained() {
+        // All tiles have the same entrophy, so only the random noise decides by default.
+        let constrained = [GridPosition::new_xy(3, 4), GridPosition::new_xy(4, 3)];
+        let mut default_differs = false;
+
+        for seed in 0..10 {
+            let with_degree =
+                first_position(seed, EntrophyQueue::new().with_degree_tie_break(true));
+            assert!(constrained.contains(&with_degree));
+
+            let default = first_position(seed, EntrophyQueue::new());
+            default_differs |= !constrained.contains(&default);
+        }
+        assert!(default_differs);
+    }
 }
@@ -0,0 +1,125 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use rand::Rng;
+
+use super::CollapseQueue;
+use crate::gen::collapse::{option::PerOptionData, tile::CollapsibleTileData};
+use crate::map::{GridDir, GridMap2D, GridSize};
+use crate::tile::{GridPosition, GridTile, TileContainer};
+
+/// Select next position to collapse following the collapse wavefront.
+///
+/// Always pops an uncollapsed position adjacent to the already collapsed region, growing it outwards from the seeds
+/// provided via [`new`](Self::new). If no seed is provided, the positions neighbouring the tiles collapsed before the
+/// generation are used, and if there are none, the collapse starts from the first position. Whenever the region can't
+/// grow any further (eg. the positions to collapse are not connected), next remaining position is used as a new seed.
+///
+/// It often yields more coherent growth than [`EntrophyQueue`](super::EntrophyQueue) or
+/// [`PositionQueue`](super::PositionQueue).
+#[derive(Default)]
+pub struct FrontierQueue {
+    seeds: Vec<GridPosition>,
+    frontier: VecDeque<GridPosition>,
+    pending: BTreeSet<GridPosition>,
+    size: Option<GridSize>,
+}
+
+impl FrontierQueue {
+    pub fn new(seeds: &[GridPosition]) -> Self {
+        Self {
+            seeds: seeds.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn push_pending_neighbours(&mut self, position: &GridPosition) {
+        let Some(size) = self.size else {
+            return;
+        };
+        for direction in GridDir::ALL_2D {
+            if let Some(neighbour) = direction.march_step(position, &size) {
+                if self.pending.remove(&neighbour) {
+                    self.frontier.push_back(neighbour);
+                }
+            }
+        }
+    }
+}
+
+impl CollapseQueue for FrontierQueue {
+    fn get_next_position(&mut self) -> Option<GridPosition> {
+        if let Some(position) = self.frontier.pop_front() {
+            return Some(position);
+        }
+        self.pending.pop_first()
+    }
+
+    fn initialize_queue<T: CollapsibleTileData>(&mut self, tiles: &[GridTile<T>]) {
+        for tile in tiles {
+            self.update_queue(tile)
+        }
+    }
+
+    fn update_queue<Tile, Data>(&mut self, tile: &Tile)
+    where
+        Tile: TileContainer + AsRef<Data>,
+        Data: CollapsibleTileData,
+    {
+        if tile.as_ref().is_collapsed() {
+            self.push_pending_neighbours(&tile.grid_position());
+        } else if !self.frontier.contains(&tile.grid_position()) {
+            self.pending.insert(tile.grid_position());
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.frontier.len() + self.pending.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.frontier.is_empty() && self.pending.is_empty()
+    }
+}
+
+impl super::private::Sealed for FrontierQueue {
+    fn populate_inner_grid<R: Rng, Data: CollapsibleTileData>(
+        &mut self,
+        _rng: &mut R,
+        grid: &mut GridMap2D<Data>,
+        positions: &[GridPosition],
+        options_data: &PerOptionData,
+    ) {
+        self.size = Some(*grid.size());
+        self.frontier.clear();
+        self.pending.clear();
+
+        let collapsed = grid
+            .iter_tiles()
+            .filter(|tile| tile.as_ref().is_collapsed())
+            .map(|tile| tile.grid_position())
+            .collect::<Vec<_>>();
+
+        let tiles = Data::new_from_frequency(positions, options_data);
+        self.initialize_queue(&tiles);
+        for tile in tiles {
+            grid.insert_tile(tile);
+        }
+
+        for seed in self.seeds.iter() {
+            if self.pending.remove(seed) {
+                self.frontier.push_back(*seed);
+            }
+        }
+        if self.frontier.is_empty() {
+            for position in collapsed.iter() {
+                if !self.pending.contains(position) {
+                    self.push_pending_neighbours(position);
+                }
+            }
+        }
+    }
+
+    fn needs_update_after_collapse(&self) -> bool {
+        true
+    }
+}
@@ -1,10 +1,12 @@
 use crate::tile::{GridPosition, GridTile, TileContainer};
 
 pub(crate) mod entrophy;
+pub(crate) mod frontier;
 pub(crate) mod position;
 mod propagator;
 
 pub use entrophy::EntrophyQueue;
+pub use frontier::FrontierQueue;
 pub use position::*;
 pub(crate) use propagator::*;
 
@@ -61,5 +63,17 @@ pub(crate) mod private {
         fn propagating(&self) -> bool {
             false
         }
+
+        fn needs_update_after_collapse(&self) -> bool {
+            false
+        }
+
+        /// Returns the queue as [`EntrophyQueue`](super::EntrophyQueue) if it is one, or gives it back otherwise.
+        fn into_entrophy(self) -> Result<super::EntrophyQueue, Self>
+        where
+            Self: Sized,
+        {
+            Err(self)
+        }
     }
 }
@@ -21,6 +21,9 @@ pub enum PositionQueueStartingPoint {
     DownLeft,
     /// Starts at the `(max, max)` position.
     DownRight,
+    /// Starts at the [`center`](crate::map::GridSize::center) of the grid, progressing outwards ring by ring.
+    /// Always uses [`Spiral`](PositionQueueDirection::Spiral) direction.
+    Center,
 }
 
 /// Enum defining the direction in which the tiles will be collapsed.
@@ -31,6 +34,9 @@ pub enum PositionQueueDirection {
     Rowwise,
     /// Collapses tiles in a columnwise fashion.
     Columnwise,
+    /// Collapses tiles in a clockwise spiral, ring by ring outwards from the [`center`](crate::map::GridSize::center)
+    /// of the grid. Always starts at the [`Center`](PositionQueueStartingPoint::Center).
+    Spiral,
 }
 
 /// A queue that collapses tiles consecutively in a fixed direction, based solely on their position.
@@ -40,6 +46,7 @@ pub struct PositionQueue {
     progress_direction: PositionQueueDirection,
     positions: Vec<GridPosition>,
     changed: bool,
+    center: (u32, u32),
 }
 
 impl PositionQueue {
@@ -52,6 +59,16 @@ impl PositionQueue {
     }
 
     pub fn sort_elements(&mut self) {
+        if self.starting_point == PositionQueueStartingPoint::Center
+            || self.progress_direction == PositionQueueDirection::Spiral
+        {
+            let center = self.center;
+            self.positions
+                .sort_by_key(|position| (*position.z(), spiral_key(center, position)));
+            self.positions.reverse();
+            return;
+        }
+
         let cmp_fun = match (&self.starting_point, &self.progress_direction) {
             (PositionQueueStartingPoint::UpLeft, PositionQueueDirection::Rowwise) => {
                 compare_upleft_rowwise
@@ -77,6 +94,9 @@ impl PositionQueue {
             (PositionQueueStartingPoint::DownRight, PositionQueueDirection::Columnwise) => {
                 compare_downright_columnwise
             }
+            (PositionQueueStartingPoint::Center, _) | (_, PositionQueueDirection::Spiral) => {
+                unreachable!("spiral ordering is handled above")
+            }
         };
 
         self.positions.sort_by(cmp_fun);
@@ -126,6 +146,7 @@ impl super::private::Sealed for PositionQueue {
         positions: &[GridPosition],
         options_data: &PerOptionData,
     ) {
+        self.center = grid.size().center();
         let tiles = Data::new_from_frequency(positions, options_data);
         self.initialize_queue(&tiles);
         for tile in tiles {
@@ -135,6 +156,26 @@ impl super::private::Sealed for PositionQueue {
 }
 
 // --- Comparison functions --- //
+
+/// Key ordering positions in a clockwise spiral around the `center`: first by the ring (Chebyshev distance from
+/// the center), then by the position along the ring, starting from its upper-left corner.
+fn spiral_key(center: (u32, u32), position: &GridPosition) -> (i64, i64) {
+    let dx = *position.x() as i64 - center.0 as i64;
+    let dy = *position.y() as i64 - center.1 as i64;
+    let ring = dx.abs().max(dy.abs());
+
+    let along = if dy == -ring {
+        dx + ring
+    } else if dx == ring {
+        2 * ring + dy + ring
+    } else if dy == ring {
+        4 * ring + ring - dx
+    } else {
+        6 * ring + ring - dy
+    };
+    (ring, along)
+}
+
 fn compare_upleft_columnwise(a: &GridPosition, b: &GridPosition) -> Ordering {
     let cmp_z = a.z().cmp(b.z());
     if cmp_z != Ordering::Equal {
@@ -241,8 +282,12 @@ fn compare_downright_rowwise(a: &GridPosition, b: &GridPosition) -> Ordering {
 
 #[cfg(test)]
 mod test {
+    use crate::gen::collapse::queue::CollapseQueue;
+    use crate::map::GridSize;
     use crate::{gen::collapse::queue::position::compare_downleft_columnwise, tile::GridPosition};
 
+    use super::{PositionQueue, PositionQueueDirection, PositionQueueStartingPoint};
+
     #[test]
     fn check_sort_default() {
         let mut tiles = GridPosition::generate_rect_area(
@@ -252,4 +297,34 @@ mod test {
 
         tiles.sort_by(compare_downleft_columnwise);
     }
+
+    #[test]
+    fn spiral_covers_all_positions() {
+        let size = GridSize::new_xy(6, 5);
+        let mut queue = PositionQueue::new(
+            PositionQueueStartingPoint::Center,
+            PositionQueueDirection::Spiral,
+        );
+        queue.center = size.center();
+        queue.positions = size.get_all_possible_positions();
+        queue.changed = true;
+
+        let mut emitted = Vec::new();
+        while let Some(position) = queue.get_next_position() {
+            emitted.push(position);
+        }
+
+        let (cx, cy) = size.center();
+        assert_eq!(GridPosition::new_xy(cx, cy), emitted[0]);
+        let rings = emitted
+            .iter()
+            .map(|pos| pos.x().abs_diff(cx).max(pos.y().abs_diff(cy)))
+            .collect::<Vec<_>>();
+        assert!(rings.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let mut expected = size.get_all_possible_positions();
+        expected.sort();
+        emitted.sort();
+        assert_eq!(expected, emitted);
+    }
 }
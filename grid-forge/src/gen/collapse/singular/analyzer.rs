@@ -1,6 +1,8 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::marker::PhantomData;
 
+use crate::error::RuleParseError;
+use crate::gen::collapse::option::PerOptionData;
 use crate::gen::collapse::private::AdjacencyTable;
 use crate::map::{DirectionTable, GridDir, GridMap2D};
 use crate::tile::identifiable::IdentifiableTileData;
@@ -74,25 +76,307 @@ where
         )
     }
 
+    /// Creates adjacency rules from the text lines in format `<tile> <DIRECTION> <neighbour>,<neighbour>...`, eg.
+    /// `grass RIGHT grass,sand`. Names of the tiles are translated to `tile_type_id` using provided `name_map`, while
+    /// direction names are the [`GridDir`] variants, case-insensitive. Empty lines are skipped.
+    ///
+    /// As with [`add_adjacency`](Self::add_adjacency), the created rules are symmetrical.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use grid_forge::GridDir;
+    /// use grid_forge::identifiable::BasicIdentTileData;
+    /// use grid_forge::gen::collapse::singular::AdjacencyRules;
+    ///
+    /// let names = HashMap::from([("grass", 1), ("sand", 2)]);
+    /// let rules = AdjacencyRules::<BasicIdentTileData>::from_rule_lines(
+    ///     &["grass RIGHT grass,sand", "sand UP sand"],
+    ///     &names
+    /// ).unwrap();
+    ///
+    /// assert!(rules.is_valid_raw(1, GridDir::RIGHT, 2));
+    /// assert!(rules.is_valid_raw(2, GridDir::LEFT, 1));
+    /// assert!(!rules.is_valid_raw(1, GridDir::UP, 2));
+    ///
+    /// let err = AdjacencyRules::<BasicIdentTileData>::from_rule_lines(&["grass NORTH sand"], &names).unwrap_err();
+    /// assert_eq!(1, err.line());
+    /// ```
+    pub fn from_rule_lines(
+        lines: &[&str],
+        name_map: &HashMap<&str, u64>,
+    ) -> Result<Self, RuleParseError> {
+        let mut rules = Self::default();
+
+        for (line_idx, line) in lines.iter().enumerate() {
+            let mut parts = line.split_whitespace();
+            let Some(tile_name) = parts.next() else {
+                continue;
+            };
+            let (Some(dir_name), Some(neighbours)) = (parts.next(), parts.next()) else {
+                return Err(RuleParseError::new_malformed(line_idx + 1, line));
+            };
+            if parts.next().is_some() {
+                return Err(RuleParseError::new_malformed(line_idx + 1, line));
+            }
+
+            let get_id = |name: &str| {
+                name_map
+                    .get(name)
+                    .copied()
+                    .ok_or_else(|| RuleParseError::new_unknown_name(line_idx + 1, line, name))
+            };
+
+            let tile_id = get_id(tile_name)?;
+            let direction = GridDir::ALL_2D
+                .iter()
+                .find(|dir| format!("{dir:?}").eq_ignore_ascii_case(dir_name))
+                .ok_or_else(|| {
+                    RuleParseError::new_unknown_direction(line_idx + 1, line, dir_name)
+                })?;
+
+            for neighbour in neighbours.split(',').filter(|n| !n.is_empty()) {
+                let neighbour_id = get_id(neighbour)?;
+                rules.add_adjacency_raw(tile_id, neighbour_id, *direction);
+                rules.add_adjacency_raw(neighbour_id, tile_id, direction.opposite());
+            }
+        }
+
+        Ok(rules)
+    }
+
+    /// Checks if the tile with `tile_type_id` can be adjacent to tile with `adjacent_id` in given `direction`.
+    pub fn is_valid_raw(&self, tile_type_id: u64, direction: GridDir, adjacent_id: u64) -> bool {
+        self.inner
+            .as_ref()
+            .get(&tile_type_id)
+            .map(|adj| adj[direction].contains(&adjacent_id))
+            .unwrap_or(false)
+    }
+
+    /// Checks if the tile with `tile_type_id` can be adjacent to tile with `adjacent_id` in given `direction`. Same as
+    /// [`is_valid_raw`](Self::is_valid_raw), with the `direction` passed last.
+    pub fn is_valid(&self, tile_type_id: u64, adjacent_id: u64, direction: GridDir) -> bool {
+        self.is_valid_raw(tile_type_id, direction, adjacent_id)
+    }
+
+    /// Returns sorted `tile_type_id`s of all tiles which can be adjacent to the tile with `tile_type_id` in given
+    /// `direction`.
+    pub fn allowed_neighbours(&self, tile_type_id: u64, direction: GridDir) -> Vec<u64> {
+        let mut neighbours = self
+            .inner
+            .as_ref()
+            .get(&tile_type_id)
+            .map(|adj| adj[direction].iter().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        neighbours.sort_unstable();
+        neighbours
+    }
+
+    /// Creates adjacency rules from raw `(tile_type_id, direction, adjacent_id)` entries. Contrary to
+    /// [`add_adjacency`](Self::add_adjacency), each entry is added only in the provided direction.
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::GridDir;
+    /// use grid_forge::identifiable::BasicIdentTileData;
+    /// use grid_forge::gen::collapse::singular::AdjacencyRules;
+    ///
+    /// let rules = AdjacencyRules::<BasicIdentTileData>::from_raw([(1, GridDir::UP, 2), (2, GridDir::DOWN, 1)]);
+    ///
+    /// assert!(rules.is_valid_raw(1, GridDir::UP, 2));
+    /// assert!(!rules.is_valid_raw(1, GridDir::DOWN, 2));
+    /// ```
+    pub fn from_raw<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (u64, GridDir, u64)>,
+    {
+        let mut rules = Self::default();
+        for (tile_id, direction, adjacent_id) in entries {
+            rules.add_adjacency_raw(tile_id, adjacent_id, direction);
+        }
+        rules
+    }
+
+    /// Adds all adjacencies present in `other` to these rules. As the adjacencies are kept in sets, merging the same
+    /// rules multiple times changes nothing.
+    pub fn merge(&mut self, other: &AdjacencyRules<Data>) {
+        self.inner.merge(&other.inner);
+    }
+
     fn add_adjacency_raw(&mut self, tile_id: u64, adjacent_id: u64, direction: GridDir) {
         self.inner.insert_adjacency(tile_id, direction, adjacent_id);
     }
 
+    /// Creates new rules containing only the adjacencies present both in `self` and in `other`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            inner: self.inner.intersect(&other.inner),
+            id_type: PhantomData::<Data>,
+        }
+    }
+
+    /// Creates new rules containing only the adjacencies present in all provided `rules`, eg. produced by multiple
+    /// analyzers for different sample sets. Returns empty rules if none are provided.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use grid_forge::GridDir;
+    /// use grid_forge::identifiable::BasicIdentTileData;
+    /// use grid_forge::gen::collapse::singular::AdjacencyRules;
+    ///
+    /// let names = HashMap::from([("grass", 1), ("sand", 2)]);
+    /// let first = AdjacencyRules::<BasicIdentTileData>::from_rule_lines(&["grass UP grass,sand"], &names).unwrap();
+    /// let second = AdjacencyRules::<BasicIdentTileData>::from_rule_lines(&["grass UP sand"], &names).unwrap();
+    ///
+    /// let common = AdjacencyRules::intersect_all([&first, &second]);
+    /// assert!(common.is_valid_raw(1, GridDir::UP, 2));
+    /// assert!(!common.is_valid_raw(1, GridDir::UP, 1));
+    /// ```
+    pub fn intersect_all<'a, I>(rules: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Data: 'a,
+    {
+        let mut rules = rules.into_iter();
+        let Some(first) = rules.next() else {
+            return Self::default();
+        };
+        rules.fold(first.clone(), |acc, next| acc.intersect(next))
+    }
+
+    /// Removes the tiles with given `tile_type_ids` alongside all adjacencies pointing to them, returning removed
+    /// adjacencies.
+    fn take_tiles(&mut self, tile_type_ids: &HashSet<u64>) -> Self {
+        Self {
+            inner: self.inner.take_ids(tile_type_ids),
+            id_type: PhantomData::<Data>,
+        }
+    }
+
     pub(crate) fn inner(&self) -> &AdjacencyTable {
         &self.inner
     }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_table(inner: AdjacencyTable) -> Self {
+        Self {
+            inner,
+            id_type: PhantomData::<Data>,
+        }
+    }
+}
+
+/// Required adjacencies for singular collapse algorithm.
+///
+/// Contrary to permissive [`AdjacencyRules`], describing which tiles *may* be placed next to each other, these describe
+/// which neighbours a tile *must* have, eg. a door tile requiring a wall above and below it. If multiple neighbours are
+/// required for the same tile and direction, any one of them satisfies the requirement.
+///
+/// They are enforced by the [`singular::Resolver`](super::Resolver) configured
+/// [`with_required_adjacency`](super::Resolver::with_required_adjacency) - collapsing a tile that doesn't have the
+/// required neighbour is a contradiction. Requirements towards positions outside of the map can never be satisfied, while
+/// the ones towards empty positions, not being collapsed in the process, are ignored.
+#[derive(Debug)]
+pub struct RequiredAdjacency<Data>
+where
+    Data: IdentifiableTileData,
+{
+    inner: HashMap<(u64, GridDir), HashSet<u64>>,
+    id_type: PhantomData<Data>,
+}
+
+impl<Data> Clone for RequiredAdjacency<Data>
+where
+    Data: IdentifiableTileData,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            id_type: PhantomData::<Data>,
+        }
+    }
+}
+
+impl<Data> Default for RequiredAdjacency<Data>
+where
+    Data: IdentifiableTileData,
+{
+    fn default() -> Self {
+        Self {
+            inner: HashMap::new(),
+            id_type: PhantomData::<Data>,
+        }
+    }
+}
+
+impl<Data> RequiredAdjacency<Data>
+where
+    Data: IdentifiableTileData,
+{
+    /// Requires the `tile` to have `neighbour` in the given `direction`. Contrary to
+    /// [`AdjacencyRules::add_adjacency`], it is not symmetrical.
+    pub fn add_required_neighbour<Tile: AsRef<Data>>(
+        &mut self,
+        tile: &Tile,
+        neighbour: &Tile,
+        direction: GridDir,
+    ) {
+        self.add_required_neighbour_raw(
+            tile.as_ref().tile_type_id(),
+            direction,
+            neighbour.as_ref().tile_type_id(),
+        )
+    }
+
+    /// Requires the tile with `tile_type_id` to have tile with `neighbour_id` in the given `direction`.
+    pub fn add_required_neighbour_raw(
+        &mut self,
+        tile_type_id: u64,
+        direction: GridDir,
+        neighbour_id: u64,
+    ) {
+        self.inner
+            .entry((tile_type_id, direction))
+            .or_default()
+            .insert(neighbour_id);
+    }
+
+    /// Checks if the tile with `tile_type_id` have its requirement in `direction` satisfied by the neighbour with
+    /// `neighbour_id`, or by the lack of neighbour if `None`.
+    pub fn is_satisfied_raw(
+        &self,
+        tile_type_id: u64,
+        direction: GridDir,
+        neighbour_id: Option<u64>,
+    ) -> bool {
+        match self.inner.get(&(tile_type_id, direction)) {
+            None => true,
+            Some(required) => neighbour_id.is_some_and(|id| required.contains(&id)),
+        }
+    }
+
+    /// Returns `true` if there are no requirements.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
 }
 
 /// Analyzer creating exact adjacency rules on basis of sample map.
 ///
 /// Rules generated by it are in general more restrictive than the ones produced by [`BorderAnalyzer`], as the tiles are deemed to be
 /// adjacent only if they are adjacent in the sample grid, while the latter can produce more rules based by the borders.
+///
+/// Tiles occuring only sporadically in the samples can be excluded from the rules with
+/// [`with_min_tile_occurrences`](Self::with_min_tile_occurrences).
 pub struct IdentityAnalyzer<Data>
 where
     Data: IdentifiableTileData,
 {
     tiles: Vec<u64>,
     adjacency_rules: AdjacencyRules<Data>,
+    occurrences: TileOccurrences<Data>,
 }
 
 impl<Data> Default for IdentityAnalyzer<Data>
@@ -103,6 +387,7 @@ where
         Self {
             tiles: Vec::new(),
             adjacency_rules: AdjacencyRules::default(),
+            occurrences: TileOccurrences::default(),
         }
     }
 }
@@ -111,6 +396,24 @@ impl<Data> IdentityAnalyzer<Data>
 where
     Data: IdentifiableTileData,
 {
+    /// Excludes tile types occuring fewer than `min_occurrences` times across all analyzed maps from the
+    /// [`tiles`](Analyzer::tiles) and the [`adjacency`](Analyzer::adjacency) rules. Useful for ignoring accidental tiles
+    /// in the samples, such as stray pixels.
+    ///
+    /// Use the same threshold with [`FrequencyHints::with_min_tile_occurrences`] to exclude the tiles from frequency
+    /// analysis, so the frequency hints match the adjacency rules. Excluded tiles can be retrieved with
+    /// [`excluded_tiles`](Self::excluded_tiles). Defaults to `0`, which keeps all tiles.
+    pub fn with_min_tile_occurrences(mut self, min_occurrences: usize) -> Self {
+        self.occurrences.min = min_occurrences;
+        self
+    }
+
+    /// Returns `tile_type_id`s of the analyzed tiles excluded due to
+    /// [`with_min_tile_occurrences`](Self::with_min_tile_occurrences).
+    pub fn excluded_tiles(&self) -> &[u64] {
+        self.occurrences.excluded()
+    }
+
     fn analyze_tile_at_pos(&mut self, map: &GridMap2D<Data>, pos: GridPosition) {
         if let Some(tile) = map.get_tile_at_position(&pos) {
             if !self.tiles.contains(&tile.as_ref().tile_type_id()) {
@@ -135,9 +438,12 @@ where
     Data: IdentifiableTileData,
 {
     fn analyze(&mut self, map: &GridMap2D<Data>) {
+        self.occurrences.count(map);
         for position in map.get_all_positions() {
             self.analyze_tile_at_pos(map, position);
         }
+        self.occurrences
+            .filter(&mut self.tiles, &mut self.adjacency_rules);
     }
 
     fn adjacency(&self) -> &AdjacencyRules<Data> {
@@ -156,12 +462,16 @@ where
 /// and the tile is a viable neighbour option if their borders in given direction have the same identifier.
 ///
 /// This analyzer additionally to analyzing the map, also provides method to add the tile adjacency manually.
+///
+/// Tiles occuring only sporadically in the samples can be excluded from the rules with
+/// [`with_min_tile_occurrences`](Self::with_min_tile_occurrences).
 pub struct BorderAnalyzer<Data>
 where
     Data: IdentifiableTileData,
 {
     tiles: Vec<u64>,
     adjacency_rules: AdjacencyRules<Data>,
+    occurrences: TileOccurrences<Data>,
     /// TileId key
     inner: HashMap<u64, TileBordersAdjacency<Data>>,
     /// BorderId key; (TileId; GridDir)
@@ -176,6 +486,7 @@ where
         Self {
             tiles: Vec::new(),
             adjacency_rules: AdjacencyRules::default(),
+            occurrences: TileOccurrences::default(),
             inner: HashMap::new(),
             border_types: HashMap::new(),
         }
@@ -188,6 +499,7 @@ where
 {
     fn analyze(&mut self, map: &GridMap2D<Data>) {
         self.adjacency_rules = AdjacencyRules::default();
+        self.occurrences.count(map);
         for position in map.get_all_positions() {
             self.analyze_tile_at_pos(map, position);
         }
@@ -207,6 +519,25 @@ impl<Data> BorderAnalyzer<Data>
 where
     Data: IdentifiableTileData,
 {
+    /// Excludes tile types occuring fewer than `min_occurrences` times across all analyzed maps from the
+    /// [`tiles`](Analyzer::tiles) and the [`adjacency`](Analyzer::adjacency) rules. Useful for ignoring accidental tiles
+    /// in the samples, such as stray pixels. Borders shared with the excluded tiles are still taken into account while
+    /// generating the rules for the remaining ones.
+    ///
+    /// Use the same threshold with [`FrequencyHints::with_min_tile_occurrences`] to exclude the tiles from frequency
+    /// analysis, so the frequency hints match the adjacency rules. Excluded tiles can be retrieved with
+    /// [`excluded_tiles`](Self::excluded_tiles). Defaults to `0`, which keeps all tiles.
+    pub fn with_min_tile_occurrences(mut self, min_occurrences: usize) -> Self {
+        self.occurrences.min = min_occurrences;
+        self
+    }
+
+    /// Returns `tile_type_id`s of the analyzed tiles excluded due to
+    /// [`with_min_tile_occurrences`](Self::with_min_tile_occurrences).
+    pub fn excluded_tiles(&self) -> &[u64] {
+        self.occurrences.excluded()
+    }
+
     /// Manually add adjacency between two tiles.
     ///
     /// After addition of new adjacencies, the [`prepare`](Self::prepare) method should be called to generate the rules.
@@ -279,6 +610,8 @@ where
                 }
             }
         }
+        self.occurrences
+            .filter(&mut self.tiles, &mut self.adjacency_rules);
     }
 
     pub(crate) fn add_adjacency_raw(
@@ -352,6 +685,74 @@ where
     }
 }
 
+/// Counts the occurences of the tiles across all analyzed maps, excluding the rare ones from the analysis results.
+struct TileOccurrences<Data>
+where
+    Data: IdentifiableTileData,
+{
+    min: usize,
+    counts: HashMap<u64, usize>,
+    excluded: Vec<u64>,
+    /// Adjacencies of the excluded tiles, restored if they become frequent enough in the next analyzed maps.
+    excluded_rules: AdjacencyRules<Data>,
+}
+
+impl<Data> Default for TileOccurrences<Data>
+where
+    Data: IdentifiableTileData,
+{
+    fn default() -> Self {
+        Self {
+            min: 0,
+            counts: HashMap::new(),
+            excluded: Vec::new(),
+            excluded_rules: AdjacencyRules::default(),
+        }
+    }
+}
+
+impl<Data> TileOccurrences<Data>
+where
+    Data: IdentifiableTileData,
+{
+    fn is_enabled(&self) -> bool {
+        self.min > 1
+    }
+
+    fn count(&mut self, map: &GridMap2D<Data>) {
+        if !self.is_enabled() {
+            return;
+        }
+        for tile in map.iter_tiles() {
+            *self.counts.entry(tile.as_ref().tile_type_id()).or_default() += 1;
+        }
+    }
+
+    /// Moves the rare tiles out of `tiles` and `adjacency_rules`, restoring the ones excluded previously first.
+    fn filter(&mut self, tiles: &mut Vec<u64>, adjacency_rules: &mut AdjacencyRules<Data>) {
+        if !self.is_enabled() {
+            return;
+        }
+        for tile_type_id in self.excluded.drain(..) {
+            if !tiles.contains(&tile_type_id) {
+                tiles.push(tile_type_id);
+            }
+        }
+        adjacency_rules.merge(&self.excluded_rules);
+
+        let (retained, excluded) = tiles
+            .iter()
+            .partition(|id| self.counts.get(id).copied().unwrap_or_default() >= self.min);
+        *tiles = retained;
+        self.excluded = excluded;
+        self.excluded_rules = adjacency_rules.take_tiles(&self.excluded.iter().copied().collect());
+    }
+
+    fn excluded(&self) -> &[u64] {
+        &self.excluded
+    }
+}
+
 struct TileBordersAdjacency<Data>
 where
     Data: IdentifiableTileData,
@@ -395,6 +796,9 @@ where
     Data: IdentifiableTileData,
 {
     weights: BTreeMap<u64, u32>,
+    min_occurrences: u32,
+    /// Counts of the analyzed tiles occuring fewer than `min_occurrences` times so far.
+    rare: BTreeMap<u64, u32>,
     id_type: PhantomData<Data>,
 }
 
@@ -405,6 +809,8 @@ where
     fn clone(&self) -> Self {
         Self {
             weights: self.weights.clone(),
+            min_occurrences: self.min_occurrences,
+            rare: self.rare.clone(),
             id_type: PhantomData::<Data>,
         }
     }
@@ -417,6 +823,8 @@ where
     fn default() -> Self {
         Self {
             weights: BTreeMap::new(),
+            min_occurrences: 0,
+            rare: BTreeMap::new(),
             id_type: PhantomData::<T>,
         }
     }
@@ -426,10 +834,22 @@ impl<Data> FrequencyHints<Data>
 where
     Data: IdentifiableTileData,
 {
+    /// Excludes tile types counted fewer than `min_occurrences` times across all analyzed maps from the weights. Their
+    /// counts are kept, so they are added once they occur often enough in the next analyzed maps. Weights set manually
+    /// with [`set_weight_for_tile`](Self::set_weight_for_tile) are not affected.
+    ///
+    /// Counterpart of [`IdentityAnalyzer::with_min_tile_occurrences`] and
+    /// [`BorderAnalyzer::with_min_tile_occurrences`]. Defaults to `0`, which keeps all tiles.
+    pub fn with_min_tile_occurrences(mut self, min_occurrences: usize) -> Self {
+        self.min_occurrences = min_occurrences.try_into().unwrap_or(u32::MAX);
+        self
+    }
+
     pub fn set_weight_for_tile<Tile>(&mut self, tile: &Tile, weight: u32)
     where
         Tile: TileContainer + AsRef<Data>,
     {
+        self.rare.remove(&tile.as_ref().tile_type_id());
         let entry = self
             .weights
             .entry(tile.as_ref().tile_type_id())
@@ -441,15 +861,165 @@ where
     where
         Tile: TileContainer + AsRef<Data>,
     {
-        if let Some(count) = self.weights.get_mut(&tile.as_ref().tile_type_id()) {
+        let tile_type_id = tile.as_ref().tile_type_id();
+        if let Some(count) = self.weights.get_mut(&tile_type_id) {
             *count += 1;
-        } else {
-            self.weights.insert(tile.as_ref().tile_type_id(), 1);
+            return;
+        }
+        let count = self.rare.entry(tile_type_id).or_default();
+        *count += 1;
+        if *count >= self.min_occurrences {
+            let count = *count;
+            self.rare.remove(&tile_type_id);
+            self.weights.insert(tile_type_id, count);
+        }
+    }
+
+    pub fn analyze(&mut self, map: &GridMap2D<Data>) {
+        for position in map.get_all_positions() {
+            let reference = map.get_tile_at_position(&position).unwrap();
+            self.count_tile(&reference)
         }
     }
 
-    pub(crate) fn get_all_weights_cloned(&self) -> BTreeMap<u64, u32> {
-        self.weights.clone()
+    /// Removes weights of all tiles not contained in `tile_type_ids`. Pass [`Analyzer::tiles`] to exclude the tiles
+    /// filtered out by the analyzer.
+    pub fn retain_tiles(&mut self, tile_type_ids: &[u64]) {
+        self.weights.retain(|id, _| tile_type_ids.contains(id));
+        self.rare.retain(|id, _| tile_type_ids.contains(id));
+    }
+
+    /// Multiplies the weight of given tile by `factor`. Resulting weight is rounded and won't be lower than `1`. Does
+    /// nothing if there is no weight for the tile.
+    pub fn scale_weight(&mut self, tile_type_id: u64, factor: f32) {
+        if let Some(weight) = self.weights.get_mut(&tile_type_id) {
+            *weight = (*weight as f32 * factor).round().max(1.) as u32;
+        }
+    }
+
+    /// Bounds weights of all tiles to the `min..=max` range.
+    ///
+    /// # Panics
+    /// Panics if `min` is greater than `max`.
+    pub fn clamp_weights(&mut self, min: u32, max: u32) {
+        for weight in self.weights.values_mut() {
+            *weight = (*weight).clamp(min, max);
+        }
+    }
+
+    /// Removes the weight of given tile, excluding it from the generation.
+    pub fn remove_tile(&mut self, tile_type_id: u64) {
+        self.weights.remove(&tile_type_id);
+        self.rare.remove(&tile_type_id);
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn weights(&self) -> &BTreeMap<u64, u32> {
+        &self.weights
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_weights(weights: BTreeMap<u64, u32>) -> Self {
+        Self {
+            weights,
+            ..Default::default()
+        }
+    }
+}
+
+/// Frequency hints for the *adjacency-based* generative algorithm, using [`f32`] weights.
+///
+/// Works the same way as [`FrequencyHints`], but allows specifying fractional weights for the tiles, which is handy
+/// when fine-grained control over relative frequencies is needed, without scaling all the weights up to integers.
+///
+/// # Precision
+/// Options are selected by drawing a fractional value from the sum of their weights, so the seeded results differ
+/// from the ones produced by [`FrequencyHints`] even if all provided weights are whole numbers. The outcome is still
+/// reproducible for the given seed and weights, as the options are always ordered by the tile type identifiers.
+/// [`FrequencyHints`] keep their weights as exact integers, while fractional weights are subject to floating point
+/// rounding as the weights of the removed options are subtracted from the tile. To always select one of the possible
+/// options, the fractional value is drawn from the sum of their weights recomputed at the moment of collapse.
+#[derive(Debug)]
+pub struct FrequencyHintsF<Data>
+where
+    Data: IdentifiableTileData,
+{
+    weights: BTreeMap<u64, f32>,
+    id_type: PhantomData<Data>,
+}
+
+impl<Data> Clone for FrequencyHintsF<Data>
+where
+    Data: IdentifiableTileData,
+{
+    fn clone(&self) -> Self {
+        Self {
+            weights: self.weights.clone(),
+            id_type: PhantomData::<Data>,
+        }
+    }
+}
+
+impl<T> Default for FrequencyHintsF<T>
+where
+    T: IdentifiableTileData,
+{
+    fn default() -> Self {
+        Self {
+            weights: BTreeMap::new(),
+            id_type: PhantomData::<T>,
+        }
+    }
+}
+
+impl<Data> From<FrequencyHints<Data>> for FrequencyHintsF<Data>
+where
+    Data: IdentifiableTileData,
+{
+    fn from(value: FrequencyHints<Data>) -> Self {
+        Self {
+            weights: value
+                .weights
+                .into_iter()
+                .map(|(id, weight)| (id, weight as f32))
+                .collect(),
+            id_type: PhantomData::<Data>,
+        }
+    }
+}
+
+impl<Data> FrequencyHintsF<Data>
+where
+    Data: IdentifiableTileData,
+{
+    /// Sets the weight for given tile.
+    ///
+    /// # Panics
+    /// Panics if `weight` is not a finite, positive number.
+    pub fn set_weight_for_tile<Tile>(&mut self, tile: &Tile, weight: f32)
+    where
+        Tile: TileContainer + AsRef<Data>,
+    {
+        assert!(
+            weight.is_finite() && weight > 0.,
+            "weight should be finite and positive, got: {weight}"
+        );
+        let entry = self
+            .weights
+            .entry(tile.as_ref().tile_type_id())
+            .or_default();
+        *entry = weight;
+    }
+
+    pub fn count_tile<Tile>(&mut self, tile: &Tile)
+    where
+        Tile: TileContainer + AsRef<Data>,
+    {
+        if let Some(count) = self.weights.get_mut(&tile.as_ref().tile_type_id()) {
+            *count += 1.;
+        } else {
+            self.weights.insert(tile.as_ref().tile_type_id(), 1.);
+        }
     }
 
     pub fn analyze(&mut self, map: &GridMap2D<Data>) {
@@ -458,4 +1028,256 @@ where
             self.count_tile(&reference)
         }
     }
+
+    /// Removes weights of all tiles not contained in `tile_type_ids`. Pass [`Analyzer::tiles`] to exclude the tiles
+    /// filtered out by the analyzer.
+    pub fn retain_tiles(&mut self, tile_type_ids: &[u64]) {
+        self.weights.retain(|id, _| tile_type_ids.contains(id));
+    }
+}
+
+/// Frequency hints accepted by [`CollapsibleTileGrid`](super::CollapsibleTileGrid). Implemented by both
+/// [`FrequencyHints`] and [`FrequencyHintsF`].
+pub trait Frequencies<Data>: private::Sealed
+where
+    Data: IdentifiableTileData,
+{
+}
+
+impl<Data: IdentifiableTileData> Frequencies<Data> for FrequencyHints<Data> {}
+impl<Data: IdentifiableTileData> Frequencies<Data> for FrequencyHintsF<Data> {}
+
+impl<Data: IdentifiableTileData> private::Sealed for FrequencyHints<Data> {
+    fn _populate_option_data(&self, option_data: &mut PerOptionData, adjacencies: &AdjacencyTable) {
+        option_data.populate(&self.weights, adjacencies);
+    }
+}
+
+impl<Data: IdentifiableTileData> private::Sealed for FrequencyHintsF<Data> {
+    fn _populate_option_data(&self, option_data: &mut PerOptionData, adjacencies: &AdjacencyTable) {
+        option_data.populate(&self.weights, adjacencies);
+    }
+}
+
+pub(crate) mod private {
+    use crate::gen::collapse::option::PerOptionData;
+    use crate::gen::collapse::private::AdjacencyTable;
+
+    pub trait Sealed {
+        #[doc(hidden)]
+        fn _populate_option_data(
+            &self,
+            option_data: &mut PerOptionData,
+            adjacencies: &AdjacencyTable,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::map::{GridDir, GridMap2D, GridSize};
+    use crate::tile::identifiable::builders::ConstructableViaIdentifierTile;
+    use crate::tile::identifiable::BasicIdentTileData;
+    use crate::tile::{GridPosition, GridTile};
+
+    use super::{
+        AdjacencyRules, Analyzer, BorderAnalyzer, FrequencyHints, FrequencyHintsF, IdentityAnalyzer,
+    };
+
+    fn map_with_stray_tile() -> GridMap2D<BasicIdentTileData> {
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 3));
+        for position in map.size().get_all_possible_positions() {
+            map.insert_data(&position, BasicIdentTileData::tile_new(1));
+        }
+        map.insert_data(&GridPosition::new_xy(1, 1), BasicIdentTileData::tile_new(2));
+        map
+    }
+
+    fn assert_stray_excluded<A: Analyzer<BasicIdentTileData>>(
+        mut analyzer: A,
+        excluded: impl Fn(&A) -> &[u64],
+    ) {
+        let map = map_with_stray_tile();
+        analyzer.analyze(&map);
+
+        assert_eq!(&[1], analyzer.tiles());
+        assert_eq!(&[2], excluded(&analyzer));
+        for dir in GridDir::ALL_2D {
+            assert!(analyzer.adjacency().is_valid_raw(1, *dir, 1));
+            assert!(!analyzer.adjacency().is_valid_raw(1, *dir, 2));
+            assert!(!analyzer.adjacency().is_valid_raw(2, *dir, 1));
+        }
+
+        // Second occurence in another map brings the tile back.
+        analyzer.analyze(&map);
+        assert!(analyzer.tiles().contains(&2));
+        assert!(excluded(&analyzer).is_empty());
+        assert!(analyzer.adjacency().is_valid_raw(1, GridDir::UP, 2));
+    }
+
+    #[test]
+    fn min_tile_occurrences_excludes_rare_tiles() {
+        assert_stray_excluded(
+            IdentityAnalyzer::default().with_min_tile_occurrences(2),
+            IdentityAnalyzer::excluded_tiles,
+        );
+        assert_stray_excluded(
+            BorderAnalyzer::default().with_min_tile_occurrences(2),
+            BorderAnalyzer::excluded_tiles,
+        );
+
+        let mut frequency = FrequencyHints::default().with_min_tile_occurrences(2);
+        let map = map_with_stray_tile();
+        frequency.analyze(&map);
+        assert_eq!(
+            vec![(1, 8)],
+            frequency.weights.clone().into_iter().collect::<Vec<_>>()
+        );
+        frequency.analyze(&map);
+        assert_eq!(
+            vec![(1, 16), (2, 2)],
+            frequency.weights.clone().into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn min_tile_occurrences_restores_earlier_adjacencies() {
+        let mut analyzer = IdentityAnalyzer::default().with_min_tile_occurrences(2);
+        analyzer.analyze(&map_with_stray_tile());
+        let mut corner = GridMap2D::new(GridSize::new_xy(3, 3));
+        for position in corner.size().get_all_possible_positions() {
+            corner.insert_data(&position, BasicIdentTileData::tile_new(1));
+        }
+        corner.insert_data(&GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        analyzer.analyze(&corner);
+
+        // Only the first map contains the tile with neighbours above and to the left.
+        assert!(analyzer.adjacency().is_valid_raw(2, GridDir::UP, 1));
+        assert!(analyzer.adjacency().is_valid_raw(2, GridDir::LEFT, 1));
+        assert!(analyzer.adjacency().is_valid_raw(1, GridDir::DOWN, 2));
+    }
+
+    #[test]
+    fn weights_can_be_tuned() {
+        let mut frequency = FrequencyHints::default();
+        frequency.analyze(&map_with_stray_tile());
+
+        frequency.scale_weight(1, 0.5);
+        frequency.scale_weight(2, 0.1);
+        frequency.scale_weight(3, 2.);
+        assert_eq!(
+            vec![(1, 4), (2, 1)],
+            frequency.weights.clone().into_iter().collect::<Vec<_>>()
+        );
+
+        frequency.clamp_weights(2, 3);
+        assert_eq!(
+            vec![(1, 3), (2, 2)],
+            frequency.weights.clone().into_iter().collect::<Vec<_>>()
+        );
+
+        frequency.remove_tile(1);
+        assert_eq!(
+            vec![2],
+            frequency.weights.keys().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_finite_fractional_weight_panics() {
+        let tile = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        FrequencyHintsF::default().set_weight_for_tile(&tile, f32::NAN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_positive_fractional_weight_panics() {
+        let tile = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        FrequencyHintsF::default().set_weight_for_tile(&tile, 0.);
+    }
+
+    #[test]
+    fn allowed_neighbours_are_sorted() {
+        let rules = AdjacencyRules::<BasicIdentTileData>::from_raw([
+            (1, GridDir::UP, 3),
+            (1, GridDir::UP, 2),
+            (1, GridDir::LEFT, 1),
+        ]);
+
+        assert_eq!(vec![2, 3], rules.allowed_neighbours(1, GridDir::UP));
+        assert_eq!(vec![1], rules.allowed_neighbours(1, GridDir::LEFT));
+        assert!(rules.allowed_neighbours(1, GridDir::DOWN).is_empty());
+        assert!(rules.allowed_neighbours(4, GridDir::UP).is_empty());
+        assert!(rules.is_valid(1, 2, GridDir::UP));
+        assert!(!rules.is_valid(2, 1, GridDir::UP));
+    }
+
+    #[test]
+    fn intersect_keeps_common_adjacencies() {
+        let names = HashMap::from([("grass", 1), ("sand", 2), ("water", 3)]);
+        let first = AdjacencyRules::<BasicIdentTileData>::from_rule_lines(
+            &["grass RIGHT grass,sand", "sand DOWN water"],
+            &names,
+        )
+        .unwrap();
+        let second = AdjacencyRules::<BasicIdentTileData>::from_rule_lines(
+            &["grass RIGHT sand,water", "sand DOWN water,sand"],
+            &names,
+        )
+        .unwrap();
+
+        let common = first.intersect(&second);
+        assert!(common.is_valid_raw(1, GridDir::RIGHT, 2));
+        assert!(common.is_valid_raw(2, GridDir::LEFT, 1));
+        assert!(common.is_valid_raw(2, GridDir::DOWN, 3));
+        assert!(common.is_valid_raw(3, GridDir::UP, 2));
+        assert!(!common.is_valid_raw(1, GridDir::RIGHT, 1));
+        assert!(!common.is_valid_raw(1, GridDir::RIGHT, 3));
+        assert!(!common.is_valid_raw(2, GridDir::DOWN, 2));
+
+        let all = AdjacencyRules::intersect_all([&first, &second, &common]);
+        for (tile, dir, adjacent) in [(1, GridDir::RIGHT, 2), (2, GridDir::DOWN, 3)] {
+            assert!(all.is_valid_raw(tile, dir, adjacent));
+        }
+        assert!(
+            !AdjacencyRules::<BasicIdentTileData>::intersect_all([]).is_valid_raw(
+                1,
+                GridDir::RIGHT,
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn merge_is_idempotent_union() {
+        let mut merged = AdjacencyRules::<BasicIdentTileData>::from_raw([(1, GridDir::UP, 1)]);
+        let other = AdjacencyRules::from_raw([(1, GridDir::UP, 2), (3, GridDir::LEFT, 1)]);
+
+        merged.merge(&other);
+        let once = valid_entries(&merged);
+        merged.merge(&other);
+
+        assert_eq!(once, valid_entries(&merged));
+        assert!(merged.is_valid_raw(1, GridDir::UP, 1));
+        assert!(merged.is_valid_raw(1, GridDir::UP, 2));
+        assert!(merged.is_valid_raw(3, GridDir::LEFT, 1));
+        assert!(!merged.is_valid_raw(3, GridDir::UP, 1));
+    }
+
+    fn valid_entries(rules: &AdjacencyRules<BasicIdentTileData>) -> Vec<(u64, GridDir, u64)> {
+        let mut valid = Vec::new();
+        for tile in 1..=3 {
+            for dir in GridDir::ALL_2D {
+                for adjacent in 1..=3 {
+                    if rules.is_valid_raw(tile, *dir, adjacent) {
+                        valid.push((tile, *dir, adjacent));
+                    }
+                }
+            }
+        }
+        valid
+    }
 }
@@ -0,0 +1,292 @@
+use rand::Rng;
+
+use crate::gen::collapse::error::CollapseError;
+use crate::gen::collapse::grid::CollapsibleGrid;
+use crate::gen::collapse::{CollapsedGrid, CollapsedTileData};
+use crate::map::GridSize;
+use crate::tile::identifiable::IdentifiableTileData;
+use crate::tile::GridPosition;
+
+use super::{AdjacencyRules, CollapsibleTileGrid, Frequencies, Resolver};
+
+/// Resolver collapsing big maps piecewise, in rectangular chunks.
+///
+/// Wraps the singular [`Resolver`], keeping only the [`CollapsedGrid`] of the whole map in memory, while the
+/// [`CollapsibleTileGrid`] is created only for the chunk currently generated. Chunks can be generated in any order
+/// with [`generate_chunk`](Self::generate_chunk), and generation of the chunk can be retried if it fails.
+///
+/// # Overlap
+/// Each chunk is generated together with the margin of `overlap` tiles around it. Tiles within the margin that were
+/// already collapsed while generating neighbouring chunks are seeded into the grid via
+/// [`CollapsibleTileGrid::populate_from_collapsed`], so the tiles of the new chunk are always compatible with them.
+/// The rest of the margin is collapsed alongside the chunk, but discarded afterwards - it only makes sure that the
+/// tiles at the chunk edges can be continued.
+///
+/// Overlap of `0` is enough only for rulesets in which every tile can be placed between any two tiles. Overlap of `1`
+/// makes the seams consistent, but later chunks placed between multiple already generated ones can still run into
+/// contradictions if the rules constrain tiles further than their direct neighbours - in such case the overlap should
+/// be at least as wide as the longest chain of tiles forced by a single tile. Wider overlap lowers the chance of
+/// contradictions at the cost of collapsing more tiles per chunk.
+///
+/// Chunks which don't border any already generated tiles are generated independently, so for rulesets with such long
+/// constraints it is best to generate each next chunk next to already generated ones, as
+/// [`generate_all`](Self::generate_all) does.
+///
+/// # Examples
+/// ```
+/// use grid_forge::gen::collapse::singular::{AdjacencyRules, ChunkedResolver, FrequencyHints, Resolver};
+/// use grid_forge::{GridDir, GridSize};
+/// use grid_forge::identifiable::BasicIdentTileData;
+///
+/// let mut adjacency = AdjacencyRules::<BasicIdentTileData>::default();
+/// let mut frequency = FrequencyHints::<BasicIdentTileData>::default();
+/// # use grid_forge::{GridTile, GridPosition};
+/// # use grid_forge::identifiable::builders::ConstructableViaIdentifierTile;
+/// # let tile = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+/// # frequency.set_weight_for_tile(&tile, 1);
+/// # for dir in GridDir::ALL_2D {
+/// #     adjacency.add_adjacency(&tile, &tile, *dir);
+/// # }
+///
+/// let mut resolver = ChunkedResolver::new(
+///     Resolver::default(),
+///     frequency,
+///     adjacency,
+///     GridSize::new_xy(100, 100),
+///     (32, 32),
+/// )
+/// .with_overlap(2);
+///
+/// let mut rng = rand::thread_rng();
+/// resolver.generate_all(&mut rng).unwrap();
+/// assert_eq!(100 * 100, resolver.collapsed().as_ref().get_all_positions().len());
+/// ```
+pub struct ChunkedResolver<Data, F>
+where
+    Data: IdentifiableTileData,
+    F: Frequencies<Data>,
+{
+    resolver: Resolver<Data>,
+    frequencies: F,
+    adjacencies: AdjacencyRules<Data>,
+    chunk_size: (u32, u32),
+    overlap: u32,
+    collapsed: CollapsedGrid,
+}
+
+impl<Data, F> ChunkedResolver<Data, F>
+where
+    Data: IdentifiableTileData,
+    F: Frequencies<Data>,
+{
+    /// Creates new chunked resolver for the map of given `size`, divided into chunks of `chunk_size` tiles. Chunks at
+    /// the right and bottom edges of the map can be smaller.
+    ///
+    /// # Panics
+    /// Panics if any dimension of `chunk_size` is `0`.
+    pub fn new(
+        resolver: Resolver<Data>,
+        frequencies: F,
+        adjacencies: AdjacencyRules<Data>,
+        size: GridSize,
+        chunk_size: (u32, u32),
+    ) -> Self {
+        assert!(
+            chunk_size.0 > 0 && chunk_size.1 > 0,
+            "chunk size needs to be positive"
+        );
+        Self {
+            resolver,
+            frequencies,
+            adjacencies,
+            chunk_size,
+            overlap: 1,
+            collapsed: CollapsedGrid::new(size),
+        }
+    }
+
+    /// Sets the width of the margin around each chunk. Defaults to `1`. See [overlap](Self#overlap) section for
+    /// details.
+    pub fn with_overlap(mut self, overlap: u32) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Returns the number of chunks along `x` and `y` axes.
+    pub fn chunk_count(&self) -> (u32, u32) {
+        let size = self.collapsed.as_ref().size();
+        (
+            size.x().div_ceil(self.chunk_size.0),
+            size.y().div_ceil(self.chunk_size.1),
+        )
+    }
+
+    /// Returns the tiles collapsed so far.
+    pub fn collapsed(&self) -> &CollapsedGrid {
+        &self.collapsed
+    }
+
+    /// Consumes the resolver, returning the tiles collapsed so far.
+    pub fn into_collapsed(self) -> CollapsedGrid {
+        self.collapsed
+    }
+
+    /// Returns the wrapped resolver, eg. to retrieve its subscriber.
+    pub fn resolver_mut(&mut self) -> &mut Resolver<Data> {
+        &mut self.resolver
+    }
+
+    /// Collapses all tiles of the chunk with given coordinates that weren't collapsed yet. Chunk `(1, 0)` is the
+    /// second chunk along `x` axis.
+    ///
+    /// If the generation fails, no tiles of the chunk are kept, so the generation can be retried.
+    ///
+    /// # Panics
+    /// Panics if the chunk coordinates are outside of [`chunk_count`](Self::chunk_count).
+    pub fn generate_chunk<R: Rng>(
+        &mut self,
+        chunk: (u32, u32),
+        rng: &mut R,
+    ) -> Result<(), CollapseError> {
+        let (chunks_x, chunks_y) = self.chunk_count();
+        assert!(
+            chunk.0 < chunks_x && chunk.1 < chunks_y,
+            "chunk {chunk:?} is out of bounds"
+        );
+
+        let size = *self.collapsed.as_ref().size();
+        let core_from = (chunk.0 * self.chunk_size.0, chunk.1 * self.chunk_size.1);
+        let core_to = (
+            (core_from.0 + self.chunk_size.0).min(size.x()),
+            (core_from.1 + self.chunk_size.1).min(size.y()),
+        );
+        let from = (
+            core_from.0.saturating_sub(self.overlap),
+            core_from.1.saturating_sub(self.overlap),
+        );
+        let to = (
+            (core_to.0 + self.overlap).min(size.x()),
+            (core_to.1 + self.overlap).min(size.y()),
+        );
+
+        let local_size = GridSize::new_xy(to.0 - from.0, to.1 - from.1);
+        let mut seed = CollapsedGrid::new(local_size);
+        let mut positions = Vec::new();
+        for y in from.1..to.1 {
+            for x in from.0..to.0 {
+                let local = GridPosition::new_xy(x - from.0, y - from.1);
+                match self
+                    .collapsed
+                    .as_ref()
+                    .get_tile_at_position(&GridPosition::new_xy(x, y))
+                {
+                    Some(tile) => {
+                        seed.insert_data(
+                            &local,
+                            CollapsedTileData::new(tile.as_ref().tile_type_id()),
+                        );
+                    }
+                    None => positions.push(local),
+                }
+            }
+        }
+
+        let mut grid =
+            CollapsibleTileGrid::new_empty(local_size, &self.frequencies, &self.adjacencies);
+        grid.populate_from_collapsed(&seed)
+            .expect("collapsed tiles should be generated using the same rules");
+        self.resolver
+            .generate_entrophy(&mut grid, rng, &positions)?;
+
+        let generated = grid.retrieve_collapsed();
+        for y in core_from.1..core_to.1 {
+            for x in core_from.0..core_to.0 {
+                let local = GridPosition::new_xy(x - from.0, y - from.1);
+                if let Some(tile) = generated.as_ref().get_tile_at_position(&local) {
+                    self.collapsed.insert_data(
+                        &GridPosition::new_xy(x, y),
+                        CollapsedTileData::new(tile.as_ref().tile_type_id()),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generates all chunks row by row, stopping at the first failed one.
+    pub fn generate_all<R: Rng>(&mut self, rng: &mut R) -> Result<(), CollapseError> {
+        let (chunks_x, chunks_y) = self.chunk_count();
+        for y in 0..chunks_y {
+            for x in 0..chunks_x {
+                self.generate_chunk((x, y), rng)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::gen::collapse::singular::{AdjacencyRules, FrequencyHints, Resolver};
+    use crate::map::{GridDir, GridSize};
+    use crate::tile::identifiable::builders::ConstructableViaIdentifierTile;
+    use crate::tile::identifiable::{BasicIdentTileData, IdentifiableTileData};
+    use crate::tile::{GridPosition, GridTile};
+
+    use super::ChunkedResolver;
+
+    #[test]
+    fn chunk_seams_are_consistent() {
+        // Checkerboard - chunks generated independently would mismatch at half of the seams.
+        let tiles = [1, 2]
+            .map(|id| GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(id)));
+        let mut frequency = FrequencyHints::default();
+        let mut adjacency = AdjacencyRules::default();
+        for tile in tiles.iter() {
+            frequency.set_weight_for_tile(tile, 1);
+        }
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&tiles[0], &tiles[1], *dir);
+            adjacency.add_adjacency(&tiles[1], &tiles[0], *dir);
+        }
+
+        let size = GridSize::new_xy(20, 13);
+        let mut resolver =
+            ChunkedResolver::new(Resolver::default(), frequency, adjacency, size, (8, 5));
+        assert_eq!((3, 3), resolver.chunk_count());
+
+        let mut rng = ChaChaRng::seed_from_u64(7);
+        // Out of order, so some chunks are placed between already generated ones.
+        for chunk in [
+            (1, 1),
+            (0, 1),
+            (2, 1),
+            (1, 0),
+            (1, 2),
+            (0, 0),
+            (2, 0),
+            (0, 2),
+            (2, 2),
+        ] {
+            resolver.generate_chunk(chunk, &mut rng).unwrap();
+        }
+
+        let map = resolver.into_collapsed();
+        assert_eq!(20 * 13, map.as_ref().get_all_positions().len());
+        for position in size.get_all_possible_positions() {
+            let tile_type_id = map
+                .as_ref()
+                .get_tile_at_position(&position)
+                .unwrap()
+                .as_ref()
+                .tile_type_id();
+            for neighbour in map.as_ref().get_neighbours(&position) {
+                assert_ne!(tile_type_id, neighbour.as_ref().tile_type_id());
+            }
+        }
+    }
+}
@@ -11,16 +11,17 @@
 //! In general the types are described in the documentation for [`collapse`](crate::gen::collapse) module.
 //!
 //! - [`AdjacencyRules`] and [`FrequencyHints`] are self-descriptive. The latter are not produced by the *analyzer*, but the method
-//! for their derivation from the sample gridmap is exposed..
+//! for their derivation from the sample gridmap is exposed.. [`FrequencyHintsF`] are their counterpart accepting fractional weights.
 //! - [`Analyzer`] is a trait implemented by two distincts analyzers. The [`IdentityAnalyzer`] in general produced more restrictive rules,
 //! as it search for exact neigbours on the sample gridmap. The [`BorderAnalyzer`] is more liberal, as it takes an extra step and derives
 //! more rules based on the distinct tile borders, making additional options available if they *could be* placed on the sample gridmap
 //! next to each other.
 //! - [`CollapsibleTileGrid`] is the collection of [`CollapsibleTile`].
-//! - [`Resolver`] is the main executor of the algorithm.
+//! - [`Resolver`] is the main executor of the algorithm. [`ChunkedResolver`] wraps it to generate big maps piecewise.
 
 mod analyzer;
+mod chunked;
 mod resolver;
 mod tile;
 
-pub use {analyzer::*, resolver::*, tile::*};
+pub use {analyzer::*, chunked::*, resolver::*, tile::*};
@@ -1,34 +1,69 @@
 use std::any::Any;
+use std::collections::{HashSet, VecDeque};
 use std::marker::PhantomData;
+use std::time::{Duration, Instant};
 
 use crate::gen::collapse::grid::private::Sealed;
 use crate::gen::collapse::grid::CollapsibleGrid;
+use crate::gen::collapse::option::PerOptionData;
 use crate::gen::collapse::{
-    CollapsibleTileData, EntrophyQueue, PositionQueue, PropagateItem, Propagator,
+    CollapsedGrid, CollapsedTileData, CollapsibleTileData, EntrophyQueue, PropagateItem, Propagator,
 };
+use crate::map::{GridDir, GridMap2D, GridSize};
+use crate::tile::identifiable::builders::IdentTileBuilder;
 use crate::tile::identifiable::collection::IdentTileCollection;
 use crate::tile::identifiable::IdentifiableTileData;
 use crate::tile::GridPosition;
 
 use crate::gen::collapse::error::{CollapseError, CollapseErrorKind};
 use crate::gen::collapse::queue::CollapseQueue;
+use crate::gen::DefaultRng;
 
-use super::{CollapsibleTile, CollapsibleTileGrid};
+use super::{CollapsibleTile, CollapsibleTileGrid, RequiredAdjacency};
 
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 
 /// Resolver of the singular collapsible procedural algorithm.
 ///
-/// It uses either [`EntrophyQueue`] or [`PositionQueue`] to process the option collapsing process of the [`CollapsibleTileGrid`],
+/// It uses either [`EntrophyQueue`], [`PositionQueue`](crate::gen::collapse::PositionQueue) or [`FrontierQueue`](crate::gen::collapse::FrontierQueue) to process the option collapsing process of the [`CollapsibleTileGrid`],
 /// additionally providing an option to subscribe to the collapse process via [`singular::Subscriber`](Subscriber).
 pub struct Resolver<Data>
 where
     Data: IdentifiableTileData,
 {
     subscriber: Option<Box<dyn Subscriber>>,
+    require_full_coverage: bool,
+    record_timings: bool,
+    last_timings: Option<ResolveTimings>,
+    failure_snapshot: bool,
+    degree_tie_break: bool,
+    tie_seed: Option<u64>,
+    backtracking: usize,
+    required_adjacency: Option<RequiredAdjacency<Data>>,
+    collapse_override: Option<Box<CollapseOverride>>,
+    constraint: Option<Box<PrecollapseConstraint>>,
+    direction_bias: Option<Box<DirectionBias>>,
+    refinement: Option<(usize, Box<LocalScore>)>,
+    contradiction_callback: Option<Box<ContradictionCallback>>,
     tile_type: PhantomData<Data>,
 }
 
+/// Callback deciding the option to collapse the tile into, set with [`Resolver::with_collapse_override`].
+type CollapseOverride = dyn Fn(&GridPosition, &[u64]) -> Option<u64>;
+
+/// Callback restricting the options of the tile before collapse begins, set with [`Resolver::with_constraint`].
+type PrecollapseConstraint = dyn Fn(&GridPosition) -> Option<&[u64]>;
+
+/// Callback multiplying the weights of the options on basis of collapsed neighbours, set with
+/// [`Resolver::with_direction_bias`].
+type DirectionBias = dyn Fn(u64, GridDir) -> f32;
+
+/// Callback scoring the tile at given position of the collapsed grid, set with [`Resolver::with_local_refinement`].
+type LocalScore = dyn Fn(&GridMap2D<CollapsedTileData>, GridPosition) -> i64;
+
+/// Callback notified about contradictions, set with [`Resolver::on_contradiction`].
+type ContradictionCallback = dyn FnMut(&GridPosition, &[GridDir]);
+
 impl<Data> Default for Resolver<Data>
 where
     Data: IdentifiableTileData,
@@ -36,6 +71,19 @@ where
     fn default() -> Self {
         Self {
             subscriber: None,
+            require_full_coverage: false,
+            record_timings: false,
+            last_timings: None,
+            failure_snapshot: false,
+            degree_tie_break: false,
+            tie_seed: None,
+            backtracking: 0,
+            required_adjacency: None,
+            collapse_override: None,
+            constraint: None,
+            direction_bias: None,
+            refinement: None,
+            contradiction_callback: None,
             tile_type: PhantomData,
         }
     }
@@ -56,6 +104,551 @@ where
         self.subscriber.take()
     }
 
+    /// If set to `true`, the resolver will check if provided positions cover all empty tiles in the grid before
+    /// the collapse process begins, returning [`CollapseError`] listing all uncovered positions otherwise.
+    ///
+    /// Useful in multi-step generation, where omitting some empty positions would leave them silently uncollapsed.
+    /// Defaults to `false`.
+    pub fn with_full_coverage(mut self, require_full_coverage: bool) -> Self {
+        self.require_full_coverage = require_full_coverage;
+        self
+    }
+
+    /// If set to `true`, the resolver will measure the time spent in each phase of the generation process. Retrieve
+    /// them with [`last_timings`](Self::last_timings) after the generation. Defaults to `false`.
+    pub fn with_timings(mut self, record_timings: bool) -> Self {
+        self.record_timings = record_timings;
+        self
+    }
+
+    /// Returns the timings of the last successful generation. Available only if the resolver was configured
+    /// [`with_timings`](Self::with_timings).
+    pub fn last_timings(&self) -> Option<ResolveTimings> {
+        self.last_timings
+    }
+
+    /// If set to `true`, the resolver will capture all tiles collapsed before the contradiction occured and attach them
+    /// to the returned [`CollapseError`]. Retrieve them with [`CollapseError::failure_snapshot`] to inspect or visualize
+    /// the state of the grid at the moment of the failure. Defaults to `false`.
+    pub fn with_failure_snapshot(mut self, failure_snapshot: bool) -> Self {
+        self.failure_snapshot = failure_snapshot;
+        self
+    }
+
+    /// If set to `true`, the [`EntrophyQueue`] used by [`generate_entrophy`](Self::generate_entrophy) will break ties
+    /// between the tiles with the lowest entrophy by choosing the one with the most collapsed neighbours. See
+    /// [`EntrophyQueue::with_degree_tie_break`]. Defaults to `false`.
+    pub fn with_degree_tie_break(mut self, degree_tie_break: bool) -> Self {
+        self.degree_tie_break = degree_tie_break;
+        self
+    }
+
+    /// If set, the [`EntrophyQueue`] used by [`generate_entrophy`](Self::generate_entrophy) will break ties between the
+    /// tiles with the lowest entrophy using the value derived from the `seed` and tile position, instead of the random
+    /// noise. See [`EntrophyQueue::new_seeded`]. Defaults to `None`.
+    pub fn with_tie_seed(mut self, seed: Option<u64>) -> Self {
+        self.tie_seed = seed;
+        self
+    }
+
+    /// If set to non-zero value, on contradiction [`generate_entrophy`](Self::generate_entrophy) will undo up to
+    /// `max_depth` most recent collapses, removing the option chosen in the undone collapse and trying the alternative
+    /// ones, before giving up with [`CollapseError`]. Defaults to `0`, which disables backtracking.
+    ///
+    /// State of the grid is stored before each of the last `max_depth` collapses, so the memory usage grows with the
+    /// depth and the grid size. The subscriber is notified about every collapse, including the undone ones.
+    pub fn with_backtracking(mut self, max_depth: usize) -> Self {
+        self.backtracking = max_depth;
+        self
+    }
+
+    /// Attach [`RequiredAdjacency`] to be enforced alongside the permissive rules of the grid. After each collapse,
+    /// the requirements of the collapsed tile and its collapsed neighbours are checked, and the generation fails with
+    /// [`CollapseError`] if any of them is not met.
+    pub fn with_required_adjacency(mut self, required: RequiredAdjacency<Data>) -> Self {
+        self.required_adjacency = Some(required);
+        self
+    }
+
+    /// Attach a callback intercepting each collapse decision. It receives the position of the collapsed tile and the
+    /// `tile_type_id`s of its remaining options, and can return `Some(tile_type_id)` to force the tile into that option
+    /// or [`None`] to let it be chosen randomly according to the weights.
+    ///
+    /// If the forced option is not one of the remaining ones, the generation fails with [`CollapseError`].
+    pub fn with_collapse_override<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&GridPosition, &[u64]) -> Option<u64> + 'static,
+    {
+        self.collapse_override = Some(Box::new(callback));
+        self
+    }
+
+    /// Attach a callback restricting the `tile_type_id`s allowed at each position before the collapse begins. Positions
+    /// for which it returns `Some(tile_type_ids)` have all other options purged, while [`None`] leaves the position
+    /// unconstrained. Identifiers not present in the rules are ignored.
+    ///
+    /// Useful for biome masks and other constraints which shouldn't collapse the tiles outright, as with
+    /// [`CollapsedGrid`](crate::gen::collapse::CollapsedGrid). If no option is left for some position, the generation
+    /// fails with [`CollapseError`].
+    ///
+    /// As the returned slice can't borrow from the callback itself, it is easiest to declare allowed identifiers as
+    /// constants and pass a `fn` item.
+    pub fn with_constraint<F>(mut self, constraint: F) -> Self
+    where
+        F: Fn(&GridPosition) -> Option<&[u64]> + 'static,
+    {
+        self.constraint = Some(Box::new(constraint));
+        self
+    }
+
+    /// Attach a callback biasing the choice of the option on basis of the already collapsed neighbours. For each
+    /// collapsed neighbour of the tile, weight of each option is multiplied by the value returned for its
+    /// `tile_type_id` and the direction from the tile to the neighbour. Negative values are treated as `0`.
+    ///
+    /// Allows eg. ground tiles to be more likely placed above the already collapsed tiles, by returning a value
+    /// greater than `1` for them and [`GridDir::DOWN`]. Only the choice between the remaining options is affected - the
+    /// bias never allows options disallowed by the adjacency rules, and doesn't influence the entrophy of the tiles. If
+    /// all remaining options end up with zero weight, the tile is collapsed without the bias.
+    pub fn with_direction_bias<F>(mut self, bias: F) -> Self
+    where
+        F: Fn(u64, GridDir) -> f32 + 'static,
+    {
+        self.direction_bias = Some(Box::new(bias));
+        self
+    }
+
+    /// Attach a scoring function refining the grid after successful generation, allowing to express preferences which
+    /// cannot be expressed with the adjacency rules.
+    ///
+    /// Refinement is a hill-climbing process: in each of up to `passes` passes, every pair of adjacent generated tiles
+    /// of different types is swapped if it doesn't break the adjacency rules and increases the sum of `score` for both
+    /// of the tiles and their neighbours. Refinement stops early if no swap was made during the pass. Tiles collapsed
+    /// before the generation are never swapped.
+    ///
+    /// Swaps are not reported to the subscriber, and neither the [`RequiredAdjacency`] nor the constraint set with
+    /// [`with_constraint`](Self::with_constraint) is checked for them.
+    pub fn with_local_refinement<F>(mut self, passes: usize, score: F) -> Self
+    where
+        F: Fn(&GridMap2D<CollapsedTileData>, GridPosition) -> i64 + 'static,
+    {
+        self.refinement = Some((passes, Box::new(score)));
+        self
+    }
+
+    /// Attach a callback notified each time some tile runs out of options, with its position and the directions of the
+    /// neighbours which ruled out its options (same as in [`CollapseError::conflicting_directions`]).
+    ///
+    /// Contrary to the [`Subscriber`], which is notified about successful collapses, it allows gathering statistics
+    /// about the contradictions. The callback is invoked before the [`CollapseError`] is returned, and also for
+    /// contradictions which were recovered from with [`with_backtracking`](Self::with_backtracking).
+    pub fn on_contradiction<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&GridPosition, &[GridDir]) + 'static,
+    {
+        self.contradiction_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Notifies the contradiction callback, if the `err` was caused by the contradiction.
+    fn report_contradiction(&mut self, grid: &CollapsibleTileGrid<Data>, err: &CollapseError) {
+        let Some(callback) = self.contradiction_callback.as_mut() else {
+            return;
+        };
+        if !err.is_contradiction() {
+            return;
+        }
+        let position = err.failed_pos();
+        let (_, directions) = Self::conflicts(grid, &position);
+        callback(&position, &directions);
+    }
+
+    /// Swaps adjacent tiles at `positions` as long as it improves the score set with
+    /// [`with_local_refinement`](Self::with_local_refinement).
+    fn refine(&self, grid: &mut CollapsibleTileGrid<Data>, positions: &[GridPosition]) {
+        let Some((passes, score)) = self.refinement.as_ref() else {
+            return;
+        };
+        let mut collapsed = grid.retrieve_collapsed();
+        let refined = positions.iter().collect::<HashSet<_>>();
+        let area_score = |collapsed: &CollapsedGrid, pair: [GridPosition; 2]| {
+            let mut area = HashSet::from(pair);
+            for position in pair {
+                area.extend(
+                    GridDir::ALL_2D
+                        .iter()
+                        .filter_map(|dir| dir.march_step(&position, collapsed.as_ref().size())),
+                );
+            }
+            area.into_iter()
+                .map(|position| score(collapsed.as_ref(), position))
+                .sum::<i64>()
+        };
+
+        for _ in 0..*passes {
+            let mut swapped = false;
+            for first in positions {
+                for dir in [GridDir::RIGHT, GridDir::DOWN] {
+                    let Some(second) = dir.march_step(first, grid.grid.size()) else {
+                        continue;
+                    };
+                    if !refined.contains(&second) {
+                        continue;
+                    }
+                    let (Some(first_idx), Some(second_idx)) = (
+                        Self::collapsed_idx_at(grid, first),
+                        Self::collapsed_idx_at(grid, &second),
+                    ) else {
+                        continue;
+                    };
+                    if first_idx == second_idx
+                        || !Self::fits_at(grid, first, second_idx, &second, first_idx)
+                        || !Self::fits_at(grid, &second, first_idx, first, second_idx)
+                    {
+                        continue;
+                    }
+
+                    let before = area_score(&collapsed, [*first, second]);
+                    Self::swap_collapsed(grid, &mut collapsed, [*first, second]);
+                    if area_score(&collapsed, [*first, second]) > before {
+                        swapped = true;
+                    } else {
+                        Self::swap_collapsed(grid, &mut collapsed, [*first, second]);
+                    }
+                }
+            }
+            if !swapped {
+                break;
+            }
+        }
+    }
+
+    fn collapsed_idx_at(
+        grid: &CollapsibleTileGrid<Data>,
+        position: &GridPosition,
+    ) -> Option<usize> {
+        grid.grid
+            .get_tile_at_position(position)
+            .and_then(|tile| tile.as_ref().collapse_idx())
+    }
+
+    /// Checks if the `option_idx` placed at `position` would be compatible with all its collapsed neighbours, with the
+    /// neighbour at `swapped` holding `swapped_idx`.
+    fn fits_at(
+        grid: &CollapsibleTileGrid<Data>,
+        position: &GridPosition,
+        option_idx: usize,
+        swapped: &GridPosition,
+        swapped_idx: usize,
+    ) -> bool {
+        GridDir::ALL_2D.iter().all(|dir| {
+            let Some(neighbour) = dir.march_step(position, grid.grid.size()) else {
+                return true;
+            };
+            let neighbour_idx = if &neighbour == swapped {
+                swapped_idx
+            } else {
+                match Self::collapsed_idx_at(grid, &neighbour) {
+                    Some(neighbour_idx) => neighbour_idx,
+                    None => return true,
+                }
+            };
+            grid.option_data
+                .get_all_enabled_in_direction(option_idx, *dir)
+                .contains(&neighbour_idx)
+                && grid
+                    .option_data
+                    .get_all_enabled_in_direction(neighbour_idx, dir.opposite())
+                    .contains(&option_idx)
+        })
+    }
+
+    fn swap_collapsed(
+        grid: &mut CollapsibleTileGrid<Data>,
+        collapsed: &mut CollapsedGrid,
+        [first, second]: [GridPosition; 2],
+    ) {
+        let first_tile = grid
+            .grid
+            .get_tile_at_position(&first)
+            .unwrap()
+            .as_ref()
+            .clone();
+        let second_tile = grid
+            .grid
+            .get_tile_at_position(&second)
+            .unwrap()
+            .as_ref()
+            .clone();
+        for (position, tile) in [(first, second_tile), (second, first_tile)] {
+            let tile_type_id = grid
+                .option_data
+                .get_tile_type_id(&tile.collapse_idx().unwrap())
+                .expect("cannot get `tile_type_id`");
+            collapsed.insert_data(&position, CollapsedTileData::new(tile_type_id));
+            grid.grid.insert_data(&position, tile);
+        }
+    }
+
+    /// Calculates the multipliers of the weights of each option at `position` with the direction bias. Returns [`None`]
+    /// if the bias is not set.
+    fn direction_multipliers(
+        &self,
+        grid: &CollapsibleTileGrid<Data>,
+        position: &GridPosition,
+    ) -> Option<Vec<f32>> {
+        let bias = self.direction_bias.as_ref()?;
+        let mut multipliers = vec![1.; grid.option_data.num_options()];
+        for direction in GridDir::ALL_2D {
+            let is_collapsed = grid
+                .grid
+                .get_neighbour_at(position, direction)
+                .is_some_and(|tile| tile.as_ref().is_collapsed());
+            if !is_collapsed {
+                continue;
+            }
+            for (option_idx, multiplier) in multipliers.iter_mut().enumerate() {
+                let tile_type_id = grid
+                    .option_data
+                    .get_tile_type_id(&option_idx)
+                    .expect("cannot get `tile_type_id`");
+                *multiplier *= bias(tile_type_id, *direction);
+            }
+        }
+        Some(multipliers)
+    }
+
+    /// Purges the options disallowed by the constraint from the tiles at `positions`, returning the positions which
+    /// were constrained alongside the removed options.
+    fn apply_constraint(
+        &self,
+        grid: &mut CollapsibleTileGrid<Data>,
+        positions: &[GridPosition],
+    ) -> Result<Vec<(GridPosition, Vec<usize>)>, CollapseError> {
+        use crate::gen::collapse::tile::private::Sealed as _;
+
+        let Some(constraint) = self.constraint.as_ref() else {
+            return Ok(Vec::new());
+        };
+        let mut out = Vec::new();
+        for position in positions {
+            let Some(allowed_ids) = constraint(position) else {
+                continue;
+            };
+            let allowed = allowed_ids
+                .iter()
+                .filter_map(|tile_type_id| grid.option_index_for(*tile_type_id))
+                .collect::<Vec<_>>();
+            let Some(mut tile) = grid.grid.get_mut_tile_at_position(position) else {
+                continue;
+            };
+            if tile.as_ref().is_collapsed() {
+                continue;
+            }
+            let removed = tile
+                .as_ref()
+                .ways_to_be_option()
+                .iter_possible()
+                .filter(|option_idx| !allowed.contains(option_idx))
+                .collect::<Vec<_>>();
+            for option_idx in removed.iter() {
+                tile.as_mut()
+                    .remove_option(grid.option_data.get_weights(*option_idx));
+            }
+            tile.as_mut().mut_ways_to_be_option().purge_others(&allowed);
+            if !tile.as_ref().has_compatible_options() {
+                return Err(CollapseError::new(*position, CollapseErrorKind::Init, 0));
+            }
+            out.push((*position, removed));
+        }
+        Ok(out)
+    }
+
+    /// Asks the collapse override for the option index to collapse the `tile` into.
+    fn forced_option(
+        &self,
+        option_data: &PerOptionData,
+        tile: &CollapsibleTile,
+        position: &GridPosition,
+        iter: u32,
+    ) -> Result<Option<usize>, CollapseError> {
+        use crate::gen::collapse::tile::private::Sealed as _;
+
+        let Some(callback) = self.collapse_override.as_ref() else {
+            return Ok(None);
+        };
+        let remaining = tile.ways_to_be_option().iter_possible().collect::<Vec<_>>();
+        let remaining_ids = remaining
+            .iter()
+            .map(|option_idx| {
+                option_data
+                    .get_tile_type_id(option_idx)
+                    .expect("cannot get `tile_type_id`")
+            })
+            .collect::<Vec<_>>();
+        let Some(forced_id) = callback(position, &remaining_ids) else {
+            return Ok(None);
+        };
+        match remaining_ids.iter().position(|id| *id == forced_id) {
+            Some(n) => Ok(Some(remaining[n])),
+            None => Err(CollapseError::new(
+                *position,
+                CollapseErrorKind::Override(forced_id),
+                iter,
+            )),
+        }
+    }
+
+    /// Checks the requirements between the tile at `position` and all its neighbours.
+    fn check_required(
+        &self,
+        grid: &CollapsibleTileGrid<Data>,
+        position: &GridPosition,
+        iter: u32,
+    ) -> Result<(), CollapseError> {
+        let Some(required) = self.required_adjacency.as_ref() else {
+            return Ok(());
+        };
+        let collapsed_id = |position: &GridPosition| -> Option<u64> {
+            let tile = grid.grid.get_tile_at_position(position)?;
+            let collapse_idx = tile.as_ref().collapse_idx()?;
+            grid.option_data.get_tile_type_id(&collapse_idx)
+        };
+        let Some(tile_id) = collapsed_id(position) else {
+            return Ok(());
+        };
+        let size = grid.grid.size();
+
+        for direction in GridDir::ALL_2D {
+            let satisfied = match direction.march_step(position, size) {
+                None => required.is_satisfied_raw(tile_id, *direction, None),
+                Some(neighbour_pos) => {
+                    if grid.grid.get_tile_at_position(&neighbour_pos).is_none() {
+                        continue;
+                    }
+                    match collapsed_id(&neighbour_pos) {
+                        None => true,
+                        Some(neighbour_id) => {
+                            required.is_satisfied_raw(tile_id, *direction, Some(neighbour_id))
+                                && required.is_satisfied_raw(
+                                    neighbour_id,
+                                    direction.opposite(),
+                                    Some(tile_id),
+                                )
+                        }
+                    }
+                }
+            };
+            if !satisfied {
+                return Err(CollapseError::new(
+                    *position,
+                    CollapseErrorKind::Requirement,
+                    iter,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn attach_snapshot(
+        &self,
+        grid: &CollapsibleTileGrid<Data>,
+        result: Result<(), CollapseError>,
+    ) -> Result<(), CollapseError> {
+        let result = result.map_err(|err| Self::attach_conflicts(grid, err));
+        match result {
+            Err(err) if self.failure_snapshot => Err(err.with_snapshot(grid.retrieve_collapsed())),
+            result => result,
+        }
+    }
+
+    /// If the tile at the failed position ran out of options, gathers the options possible according to the rules
+    /// which were ruled out by its neighbours, alongside the directions of the neighbours responsible.
+    fn attach_conflicts(grid: &CollapsibleTileGrid<Data>, err: CollapseError) -> CollapseError {
+        let position = err.failed_pos();
+        let Some(tile) = grid.grid.get_tile_at_position(&position) else {
+            return err;
+        };
+        if tile.as_ref().is_collapsed() || tile.as_ref().has_compatible_options() {
+            return err;
+        }
+        let (failed_options, directions) = Self::conflicts(grid, &position);
+        err.with_conflicts(failed_options, directions)
+    }
+
+    /// Gathers the options possible according to the rules which are ruled out by the neighbours of the tile at
+    /// `position`, alongside the directions of the neighbours responsible.
+    fn conflicts(
+        grid: &CollapsibleTileGrid<Data>,
+        position: &GridPosition,
+    ) -> (Vec<u64>, Vec<GridDir>) {
+        use crate::gen::collapse::tile::private::Sealed as _;
+
+        let size = *grid.grid.size();
+        let mut failed = HashSet::new();
+        let mut directions = Vec::new();
+        for direction in GridDir::ALL_2D {
+            let Some(neighbour) = direction
+                .march_step(position, &size)
+                .and_then(|pos| grid.grid.get_tile_at_position(&pos))
+            else {
+                continue;
+            };
+            let neighbour_options = match neighbour.as_ref().collapse_idx() {
+                Some(collapsed_idx) => vec![collapsed_idx],
+                None => neighbour
+                    .as_ref()
+                    .ways_to_be_option()
+                    .iter_possible()
+                    .collect(),
+            };
+            let supported = neighbour_options
+                .iter()
+                .flat_map(|option_idx| {
+                    grid.option_data
+                        .get_all_enabled_in_direction(*option_idx, direction.opposite())
+                })
+                .collect::<HashSet<_>>();
+            let mut conflicting = false;
+            for option_idx in grid.option_data.get_ways_to_become_option().iter_possible() {
+                if !supported.contains(&option_idx) {
+                    failed.insert(option_idx);
+                    conflicting = true;
+                }
+            }
+            if conflicting {
+                directions.push(*direction);
+            }
+        }
+
+        let mut failed_options = failed
+            .into_iter()
+            .filter_map(|option_idx| grid.option_data.get_tile_type_id(&option_idx))
+            .collect::<Vec<_>>();
+        failed_options.sort_unstable();
+        (failed_options, directions)
+    }
+
+    fn check_coverage(
+        &self,
+        grid: &CollapsibleTileGrid<Data>,
+        positions: &[GridPosition],
+    ) -> Result<(), CollapseError> {
+        if !self.require_full_coverage {
+            return Ok(());
+        }
+        let provided = positions.iter().collect::<HashSet<_>>();
+        let uncovered = grid
+            .empty_positions()
+            .into_iter()
+            .filter(|pos| !provided.contains(pos))
+            .collect::<Vec<_>>();
+
+        if uncovered.is_empty() {
+            return Ok(());
+        }
+        Err(CollapseError::new_uncovered(uncovered))
+    }
+
     /// Collapse the [`CollapsibleTileGrid`] using [`EntrophyQueue`].
     ///
     /// Contrary to [`generate_position`](Self::generate_position), this method don't require providing the precreated
@@ -66,7 +659,8 @@ where
     /// removed on the beginning of the process.
     /// * `rng` - [`Rng`] to be used for randomness.
     /// * `positions` - [`GridPosition`]s to be collapsed. If any collapsed tile is present inside the provided `grid`
-    /// at one of the positions provided, the tile will be overwritten with uncollapsed one.
+    /// at one of the positions provided, the tile will be overwritten with uncollapsed one. If the resolver was configured
+    /// [`with_full_coverage`](Self::with_full_coverage), they need to cover all empty tiles in the grid.
     ///
     /// Provided `grid` can be translated into either a [`CollapsedGrid`](crate::gen::collapse::grid::CollapsedGrid)
     /// or [`GridMap2D`](crate::map::GridMap2D) of some [`IdentifiableTileData`] after the process.
@@ -76,104 +670,324 @@ where
         rng: &mut R,
         positions: &[GridPosition],
     ) -> Result<(), CollapseError>
+    where
+        R: Rng,
+    {
+        let queue = match self.tie_seed {
+            Some(seed) => EntrophyQueue::new_seeded(positions.len(), seed),
+            None => EntrophyQueue::new(),
+        }
+        .with_degree_tie_break(self.degree_tie_break);
+        self.generate_entrophy_with_queue(grid, rng, positions, queue)
+    }
+
+    fn generate_entrophy_with_queue<R>(
+        &mut self,
+        grid: &mut CollapsibleTileGrid<Data>,
+        rng: &mut R,
+        positions: &[GridPosition],
+        queue: EntrophyQueue,
+    ) -> Result<(), CollapseError>
+    where
+        R: Rng,
+    {
+        let result = self
+            .resolve_entrophy(grid, rng, positions, queue)
+            .map(|()| self.refine(grid, positions));
+        self.attach_snapshot(grid, result)
+    }
+
+    fn resolve_entrophy<R>(
+        &mut self,
+        grid: &mut CollapsibleTileGrid<Data>,
+        rng: &mut R,
+        positions: &[GridPosition],
+        mut queue: EntrophyQueue,
+    ) -> Result<(), CollapseError>
     where
         R: Rng,
     {
         use crate::gen::collapse::queue::private::Sealed as _;
-        use crate::gen::collapse::tile::private::Sealed as _;
 
         let mut iter = 0;
-        let mut queue = EntrophyQueue::default();
         let mut propagator = Propagator::default();
+        let mut timer = PhaseTimer::new(self.record_timings);
+        self.last_timings = None;
 
         if let Some(subscriber) = self.subscriber.as_mut() {
             subscriber.on_generation_start();
         }
 
         grid.remove_uncollapsed();
+        self.check_coverage(grid, positions)?;
 
         queue.populate_inner_grid(rng, &mut grid.grid, positions, &grid.option_data);
 
         for initial_propagate in grid._get_initial_propagate_items(positions) {
             propagator.push_propagate(initial_propagate);
         }
+        let constrained = self
+            .apply_constraint(grid, positions)
+            .inspect_err(|err| self.report_contradiction(grid, err))?;
+        for (position, removed_options) in constrained {
+            queue.update_queue(&grid.grid.get_tile_at_position(&position).unwrap());
+            for removed_option in removed_options {
+                propagator.push_propagate(PropagateItem::new(position, removed_option));
+            }
+        }
 
         CollapseError::from_result(
             propagator.propagate(&mut grid.grid, &grid.option_data, &mut queue),
             CollapseErrorKind::Init,
             iter,
-        )?;
+        )
+        .inspect_err(|err| self.report_contradiction(grid, err))?;
+        timer.lap(|t| &mut t.initialization);
 
         // Progress with collapse.
-        while let Some(collapse_position) = queue.get_next_position() {
-            let mut to_collapse = grid
-                .grid
-                .get_mut_tile_at_position(&collapse_position)
-                .unwrap();
+        let mut frames = VecDeque::new();
+        let mut pending = None;
+        while let Some(collapse_position) = pending.take().or_else(|| queue.get_next_position()) {
             // skip collapsed;
-            if to_collapse.as_ref().is_collapsed() {
+            if grid
+                .grid
+                .get_tile_at_position(&collapse_position)
+                .unwrap()
+                .as_ref()
+                .is_collapsed()
+            {
                 continue;
             }
-            if !to_collapse.as_ref().has_compatible_options() {
-                return Err(CollapseError::new(
-                    collapse_position,
-                    CollapseErrorKind::Collapse,
-                    iter,
-                ));
+            if self.backtracking > 0 {
+                frames.push_back(BacktrackFrame {
+                    grid: grid.grid.clone(),
+                    queue: queue.clone(),
+                    position: collapse_position,
+                });
+                if frames.len() > self.backtracking {
+                    frames.pop_front();
+                }
             }
-            let Some(removed_options) = to_collapse.as_mut().collapse(rng, &grid.option_data)
-            else {
-                return Err(CollapseError::new(
-                    collapse_position,
-                    CollapseErrorKind::Collapse,
-                    iter,
-                ));
-            };
-            let collapsed_idx = to_collapse.as_ref().collapse_idx().unwrap();
-            if let Some(subscriber) = self.subscriber.as_mut() {
-                let collapsed_id = grid
-                    ._option_data()
-                    .get_tile_type_id(&collapsed_idx)
-                    .unwrap();
-                subscriber
-                    .as_mut()
-                    .on_collapse(&collapse_position, collapsed_id);
+            match self.collapse_step(grid, rng, &mut queue, &mut timer, collapse_position, iter) {
+                Ok(()) => iter += 1,
+                Err(err) => {
+                    self.report_contradiction(grid, &err);
+                    pending = Some(Self::backtrack(grid, &mut queue, &mut frames).ok_or(err)?);
+                }
             }
-            for removed_option in removed_options.into_iter() {
-                propagator.push_propagate(PropagateItem::new(collapse_position, removed_option))
+        }
+        self.last_timings = timer.finish();
+        Ok(())
+    }
+
+    /// Collapses the tile at `collapse_position` and propagates the removed options.
+    fn collapse_step<R>(
+        &mut self,
+        grid: &mut CollapsibleTileGrid<Data>,
+        rng: &mut R,
+        queue: &mut EntrophyQueue,
+        timer: &mut PhaseTimer,
+        collapse_position: GridPosition,
+        iter: u32,
+    ) -> Result<(), CollapseError>
+    where
+        R: Rng,
+    {
+        use crate::gen::collapse::queue::private::Sealed as _;
+        use crate::gen::collapse::tile::private::Sealed as _;
+
+        let mut propagator = Propagator::default();
+        let multipliers = self.direction_multipliers(grid, &collapse_position);
+        let mut to_collapse = grid
+            .grid
+            .get_mut_tile_at_position(&collapse_position)
+            .unwrap();
+        if !to_collapse.as_ref().has_compatible_options() {
+            return Err(CollapseError::new(
+                collapse_position,
+                CollapseErrorKind::Collapse,
+                iter,
+            ));
+        }
+        let removed_options = match self.forced_option(
+            &grid.option_data,
+            to_collapse.as_ref(),
+            &collapse_position,
+            iter,
+        )? {
+            Some(forced_idx) => {
+                let removed = to_collapse
+                    .as_ref()
+                    .ways_to_be_option()
+                    .iter_possible()
+                    .filter(|option_idx| *option_idx != forced_idx)
+                    .collect::<Vec<_>>();
+                to_collapse.as_mut().mark_collapsed(forced_idx);
+                Some(removed)
             }
-            CollapseError::from_result(
-                propagator.propagate(&mut grid.grid, &grid.option_data, &mut queue),
-                CollapseErrorKind::Propagation,
+            None => match multipliers {
+                Some(multipliers) => Some(to_collapse.as_mut().collapse_biased(
+                    rng,
+                    &grid.option_data,
+                    &|option_idx| multipliers[option_idx],
+                )),
+                None => to_collapse.as_mut().collapse(rng, &grid.option_data),
+            },
+        };
+        let Some(removed_options) = removed_options else {
+            return Err(CollapseError::new(
+                collapse_position,
+                CollapseErrorKind::Collapse,
                 iter,
-            )?;
-            iter += 1;
+            ));
+        };
+        let collapsed_idx = to_collapse.as_ref().collapse_idx().unwrap();
+        if queue.needs_update_after_collapse() {
+            queue.update_queue(&to_collapse);
         }
-
+        self.check_required(grid, &collapse_position, iter)?;
+        timer.lap(|t| &mut t.collapse);
+        if let Some(subscriber) = self.subscriber.as_mut() {
+            let collapsed_id = grid
+                ._option_data()
+                .get_tile_type_id(&collapsed_idx)
+                .unwrap();
+            subscriber
+                .as_mut()
+                .on_collapse(&collapse_position, collapsed_id);
+        }
+        timer.skip();
+        for removed_option in removed_options.into_iter() {
+            propagator.push_propagate(PropagateItem::new(collapse_position, removed_option))
+        }
+        CollapseError::from_result(
+            propagator.propagate(&mut grid.grid, &grid.option_data, queue),
+            CollapseErrorKind::Propagation,
+            iter,
+        )?;
+        timer.lap(|t| &mut t.propagation);
         Ok(())
     }
 
-    pub fn generate_position<R>(
+    /// Reverts the grid to the state before the most recent collapse which can still be undone, and removes the
+    /// option chosen back then from the tile. Returns the position to collapse next, or [`None`] if all stored
+    /// collapses were exhausted.
+    fn backtrack(
+        grid: &mut CollapsibleTileGrid<Data>,
+        queue: &mut EntrophyQueue,
+        frames: &mut VecDeque<BacktrackFrame>,
+    ) -> Option<GridPosition> {
+        use crate::gen::collapse::tile::private::Sealed as _;
+
+        while let Some(frame) = frames.pop_back() {
+            // Option chosen in the failed attempt, if it came to choosing one.
+            let chosen = grid
+                .grid
+                .get_tile_at_position(&frame.position)
+                .and_then(|tile| tile.as_ref().collapse_idx());
+            grid.grid = frame.grid;
+            *queue = frame.queue;
+            let Some(chosen) = chosen else {
+                continue;
+            };
+
+            let mut tile = grid.grid.get_mut_tile_at_position(&frame.position).unwrap();
+            if tile.as_mut().mut_ways_to_be_option().purge_option(chosen) {
+                tile.as_mut()
+                    .remove_option(grid.option_data.get_weights(chosen));
+            }
+            if !tile.as_ref().has_compatible_options() {
+                continue;
+            }
+            queue.update_queue(&tile);
+
+            let mut propagator = Propagator::default();
+            propagator.push_propagate(PropagateItem::new(frame.position, chosen));
+            if propagator
+                .propagate(&mut grid.grid, &grid.option_data, queue)
+                .is_err()
+            {
+                continue;
+            }
+            return Some(frame.position);
+        }
+        None
+    }
+
+    /// Collapse the grid using [`DefaultRng`] seeded with `seed`, making the generation reproducible.
+    ///
+    /// If propagating `queue` ([`EntrophyQueue`]) is provided, the collapse is done the same way as in
+    /// [`generate_entrophy`](Self::generate_entrophy), otherwise as in [`generate_position`](Self::generate_position).
+    /// Provided [`EntrophyQueue`] is used as is, so its tie breaking configuration takes precedence over the one set
+    /// with [`with_tie_seed`](Self::with_tie_seed) and [`with_degree_tie_break`](Self::with_degree_tie_break).
+    pub fn generate_seeded<Q>(
+        &mut self,
+        grid: &mut CollapsibleTileGrid<Data>,
+        seed: [u8; 32],
+        positions: &[GridPosition],
+        queue: Q,
+    ) -> Result<(), CollapseError>
+    where
+        Q: CollapseQueue,
+    {
+        let mut rng = DefaultRng::from_seed(seed);
+        match queue.into_entrophy() {
+            Ok(queue) => self.generate_entrophy_with_queue(grid, &mut rng, positions, queue),
+            Err(queue) => self.generate_position(grid, &mut rng, positions, queue),
+        }
+    }
+
+    /// Collapse the [`CollapsibleTileGrid`] using provided non-propagating queue, such as [`PositionQueue`](crate::gen::collapse::PositionQueue) or
+    /// [`FrontierQueue`](crate::gen::collapse::FrontierQueue).
+    ///
+    /// Instead of propagating the removed options through the whole grid, only the options of direct neighbours of the
+    /// collapsed tiles are purged, so the queue decides the order of collapsing on its own.
+    ///
+    /// Arguments are the same as in [`generate_entrophy`](Self::generate_entrophy), with the additional `queue`.
+    pub fn generate_position<R, Q>(
         &mut self,
         grid: &mut CollapsibleTileGrid<Data>,
         rng: &mut R,
         positions: &[GridPosition],
-        mut queue: PositionQueue,
+        queue: Q,
     ) -> Result<(), CollapseError>
     where
         R: Rng,
+        Q: CollapseQueue,
+    {
+        let result = self
+            .resolve_position(grid, rng, positions, queue)
+            .map(|()| self.refine(grid, positions));
+        self.attach_snapshot(grid, result)
+    }
+
+    fn resolve_position<R, Q>(
+        &mut self,
+        grid: &mut CollapsibleTileGrid<Data>,
+        rng: &mut R,
+        positions: &[GridPosition],
+        mut queue: Q,
+    ) -> Result<(), CollapseError>
+    where
+        R: Rng,
+        Q: CollapseQueue,
     {
-        use crate::gen::collapse::queue::private::Sealed as _;
         use crate::gen::collapse::tile::private::Sealed as _;
         let mut iter = 0;
+        let mut timer = PhaseTimer::new(self.record_timings);
+        self.last_timings = None;
 
         if let Some(subscriber) = self.subscriber.as_mut() {
             subscriber.on_generation_start();
         }
 
         grid.remove_uncollapsed();
+        self.check_coverage(grid, positions)?;
 
         queue.populate_inner_grid(rng, &mut grid.grid, positions, &grid.option_data);
+        self.apply_constraint(grid, positions)
+            .inspect_err(|err| self.report_contradiction(grid, err))?;
+        timer.lap(|t| &mut t.initialization);
 
         // Progress with collapse.
         while let Some(collapse_position) = queue.get_next_position() {
@@ -190,20 +1004,41 @@ where
                     &grid.option_data,
                 )
             {
-                return Err(CollapseError::new(
-                    collapse_position,
-                    CollapseErrorKind::Collapse,
-                    iter,
-                ));
+                let err = CollapseError::new(collapse_position, CollapseErrorKind::Collapse, iter);
+                self.report_contradiction(grid, &err);
+                return Err(err);
             };
 
+            let multipliers = self.direction_multipliers(grid, &collapse_position);
             let mut to_collapse = grid
                 .grid
                 .get_mut_tile_at_position(&collapse_position)
                 .unwrap();
-            to_collapse.as_mut().collapse_basic(rng, &grid.option_data);
+            match self.forced_option(
+                &grid.option_data,
+                to_collapse.as_ref(),
+                &collapse_position,
+                iter,
+            )? {
+                Some(forced_idx) => to_collapse.as_mut().mark_collapsed(forced_idx),
+                None => match multipliers {
+                    Some(multipliers) => {
+                        to_collapse.as_mut().collapse_biased(
+                            rng,
+                            &grid.option_data,
+                            &|option_idx| multipliers[option_idx],
+                        );
+                    }
+                    None => to_collapse.as_mut().collapse_basic(rng, &grid.option_data),
+                },
+            }
 
             let collapsed_idx = to_collapse.as_ref().collapse_idx().unwrap();
+            timer.lap(|t| &mut t.collapse);
+            if queue.needs_update_after_collapse() {
+                queue.update_queue(&to_collapse);
+            }
+            self.check_required(grid, &collapse_position, iter)?;
             // Purge options for the neighbours. This step is not required for the generation to be sound at the end,
             // but it increases the success rate of the process greatly at the relatively small performance cost.
             CollapsibleTile::purge_options_for_neighbours(
@@ -212,6 +1047,7 @@ where
                 &collapse_position,
                 &grid.option_data,
             );
+            timer.lap(|t| &mut t.propagation);
 
             if let Some(subscriber) = self.subscriber.as_mut() {
                 let collapsed_id = grid
@@ -222,12 +1058,77 @@ where
                     .as_mut()
                     .on_collapse(&collapse_position, collapsed_id);
             }
+            timer.skip();
             iter += 1;
         }
+        self.last_timings = timer.finish();
         Ok(())
     }
 }
 
+/// State of the grid stored before the collapse, allowing [`singular::Resolver`](Resolver) to backtrack.
+struct BacktrackFrame {
+    grid: GridMap2D<CollapsibleTile>,
+    queue: EntrophyQueue,
+    position: GridPosition,
+}
+
+/// Time spent in each phase of the generation process by [`singular::Resolver`](Resolver).
+///
+/// Time spent notifying the subscriber is not attributed to any phase, so it is included only in the `total`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResolveTimings {
+    /// Removal of uncollapsed tiles, queue population and initial propagation.
+    pub initialization: Duration,
+    /// Propagation of removed options after each collapse.
+    pub propagation: Duration,
+    /// Choosing the option for each collapsed tile.
+    pub collapse: Duration,
+    /// Total time of the generation.
+    pub total: Duration,
+}
+
+/// Measures the phases of the generation, if enabled.
+struct PhaseTimer {
+    inner: Option<(Instant, Instant, ResolveTimings)>,
+}
+
+impl PhaseTimer {
+    fn new(enabled: bool) -> Self {
+        Self {
+            inner: enabled.then(|| {
+                let now = Instant::now();
+                (now, now, ResolveTimings::default())
+            }),
+        }
+    }
+
+    /// Adds the time since last lap to the phase returned by `phase`.
+    #[inline]
+    fn lap(&mut self, phase: fn(&mut ResolveTimings) -> &mut Duration) {
+        if let Some((_, last, timings)) = self.inner.as_mut() {
+            let now = Instant::now();
+            *phase(timings) += now - *last;
+            *last = now;
+        }
+    }
+
+    /// Starts the next lap without adding the time since last lap to any phase.
+    #[inline]
+    fn skip(&mut self) {
+        if let Some((_, last, _)) = self.inner.as_mut() {
+            *last = Instant::now();
+        }
+    }
+
+    fn finish(self) -> Option<ResolveTimings> {
+        self.inner.map(|(start, _, mut timings)| {
+            timings.total = start.elapsed();
+            timings
+        })
+    }
+}
+
 /// When applied to the struct allows injecting it into [`singular::Resolver`](Resolver) to react on each tile being collapsed.
 pub trait Subscriber: Any {
     /// Called when the generation process starts. No-op by default, should be overridden to clear the state of the subcscriber
@@ -273,6 +1174,44 @@ impl CollapseHistorySubscriber {
     pub fn history(&self) -> &[CollapseHistoryItem] {
         &self.history
     }
+
+    /// Replays the first `up_to` collapses from the history into a new [`GridMap2D`], building the tiles with provided
+    /// `builder`. If `up_to` exceeds the history length, whole history is replayed.
+    ///
+    /// Size of the map is inferred from the positions contained within the whole history, so maps replayed at different
+    /// steps of the same generation are of the same size. If the generated grid had rows or columns that were never
+    /// collapsed, use [`replay_into_sized`](Self::replay_into_sized) to keep its original size.
+    pub fn replay_into<Data, B>(&self, up_to: usize, builder: &B) -> GridMap2D<Data>
+    where
+        Data: IdentifiableTileData,
+        B: IdentTileBuilder<Data>,
+    {
+        let (max_x, max_y) = self.history.iter().fold((0, 0), |(x, y), item| {
+            (x.max(*item.position.x() + 1), y.max(*item.position.y() + 1))
+        });
+        self.replay_into_sized(GridSize::new_xy(max_x, max_y), up_to, builder)
+    }
+
+    /// Same as [`replay_into`](Self::replay_into), but replays the collapses into a new [`GridMap2D`] of given `size`.
+    ///
+    /// Collapses at positions not fitting within the `size` are skipped.
+    pub fn replay_into_sized<Data, B>(
+        &self,
+        size: GridSize,
+        up_to: usize,
+        builder: &B,
+    ) -> GridMap2D<Data>
+    where
+        Data: IdentifiableTileData,
+        B: IdentTileBuilder<Data>,
+    {
+        let mut map = GridMap2D::new(size);
+
+        for item in self.history.iter().take(up_to) {
+            map.insert_tile(builder.build_tile_unchecked(item.position, item.tile_type_id));
+        }
+        map
+    }
 }
 
 impl Subscriber for CollapseHistorySubscriber {
@@ -291,3 +1230,981 @@ impl Subscriber for CollapseHistorySubscriber {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use rand::{thread_rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    use crate::gen::collapse::singular::{
+        AdjacencyRules, CollapseHistorySubscriber, CollapsibleTileGrid, FrequencyHints,
+        FrequencyHintsF, RequiredAdjacency, Resolver, Subscriber,
+    };
+    use crate::gen::collapse::{
+        CollapsedGrid, CollapsedTileData, CollapsibleGrid, EntrophyQueue, FrontierQueue,
+        PositionQueue,
+    };
+    use crate::map::{GridDir, GridMap2D, GridSize};
+    use crate::tile::identifiable::builders::{
+        ConstructableViaIdentifierTile, IdentTileTraitBuilder,
+    };
+    use crate::tile::identifiable::{BasicIdentTileData, IdentifiableTileData};
+    use crate::tile::{GridPosition, GridTile, TileContainer};
+
+    fn single_tile_grid(size: GridSize) -> CollapsibleTileGrid<BasicIdentTileData> {
+        let tile = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&tile, 1);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&tile, &tile, *dir);
+        }
+        CollapsibleTileGrid::new_empty(size, &frequency, &adjacency)
+    }
+
+    #[test]
+    fn full_coverage_lists_uncovered() {
+        let size = GridSize::new_xy(3, 3);
+        let mut grid = single_tile_grid(size);
+        let mut positions = size.get_all_possible_positions();
+        let omitted = positions.remove(4);
+
+        let mut resolver = Resolver::default().with_full_coverage(true);
+        let err = resolver
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap_err();
+
+        assert_eq!(Some([omitted].as_slice()), err.uncovered_positions());
+        assert!(!err.is_probabilistic());
+    }
+
+    #[test]
+    fn full_coverage_passes_when_covered() {
+        let size = GridSize::new_xy(3, 3);
+        let mut grid = single_tile_grid(size);
+        let positions = size.get_all_possible_positions();
+
+        let mut resolver = Resolver::default().with_full_coverage(true);
+        resolver
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap();
+
+        assert!(grid.empty_positions().is_empty());
+    }
+
+    #[test]
+    fn fractional_weights_frequencies() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHintsF::default();
+        frequency.set_weight_for_tile(&first, 0.25);
+        frequency.set_weight_for_tile(&second, 0.75);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&first, &first, *dir);
+            adjacency.add_adjacency(&first, &second, *dir);
+            adjacency.add_adjacency(&second, &second, *dir);
+        }
+
+        let size = GridSize::new_xy(1, 1);
+        let positions = size.get_all_possible_positions();
+        let mut resolver = Resolver::default();
+        let samples = 2000;
+        let mut first_count = 0;
+
+        for seed in 0..samples {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            let mut rng = ChaChaRng::seed_from_u64(seed);
+            resolver
+                .generate_entrophy(&mut grid, &mut rng, &positions)
+                .unwrap();
+            let collapsed = grid.retrieve_collapsed();
+            if collapsed.tile_type_ids().any(|id| *id == 1) {
+                first_count += 1;
+            }
+        }
+
+        let ratio = first_count as f32 / samples as f32;
+        assert!((ratio - 0.25).abs() < 0.05, "ratio: {ratio}");
+    }
+
+    #[test]
+    fn direction_bias_steers_choice() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&first, 1);
+        frequency.set_weight_for_tile(&second, 1);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&first, &first, *dir);
+            adjacency.add_adjacency(&first, &second, *dir);
+            adjacency.add_adjacency(&second, &first, *dir);
+            adjacency.add_adjacency(&second, &second, *dir);
+        }
+
+        let size = GridSize::new_xy(6, 6);
+        let positions = size.get_all_possible_positions();
+        // First tile can be chosen only if none of the neighbours is collapsed yet, so two of them are never adjacent.
+        let lonely_first = |tile_type_id: u64, _: GridDir| if tile_type_id == 1 { 0. } else { 1. };
+
+        for seed in 0..10 {
+            let mut rng = ChaChaRng::seed_from_u64(seed);
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            Resolver::default()
+                .with_direction_bias(lonely_first)
+                .generate_entrophy(&mut grid, &mut rng, &positions)
+                .unwrap();
+            let collapsed = grid.retrieve_collapsed();
+            for tile in collapsed.as_ref().iter_tiles() {
+                if tile.as_ref().tile_type_id() != 1 {
+                    continue;
+                }
+                assert!(collapsed
+                    .as_ref()
+                    .get_neighbours(&tile.grid_position())
+                    .iter()
+                    .all(|neighbour| neighbour.as_ref().tile_type_id() == 2));
+            }
+
+            // Every tile except the first one has collapsed neighbour when collapsed by position.
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            Resolver::default()
+                .with_direction_bias(lonely_first)
+                .generate_position(&mut grid, &mut rng, &positions, PositionQueue::default())
+                .unwrap();
+            let collapsed = grid.retrieve_collapsed();
+            assert!(collapsed.tile_type_ids().filter(|id| **id == 1).count() <= 1);
+        }
+
+        // Bias zeroing all options is ignored.
+        let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+        Resolver::default()
+            .with_direction_bias(|_, _| 0.)
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap();
+        assert!(grid.empty_positions().is_empty());
+    }
+
+    #[test]
+    fn local_refinement_improves_score() {
+        let tiles = [1, 2]
+            .map(|id| GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(id)));
+        let mut frequency = FrequencyHints::default();
+        for tile in tiles.iter() {
+            frequency.set_weight_for_tile(tile, 2);
+        }
+        let mut free = AdjacencyRules::default();
+        let mut checkerboard = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            for first in tiles.iter() {
+                for second in tiles.iter() {
+                    free.add_adjacency(first, second, *dir);
+                }
+            }
+            checkerboard.add_adjacency(&tiles[0], &tiles[1], *dir);
+            checkerboard.add_adjacency(&tiles[1], &tiles[0], *dir);
+        }
+
+        // Number of neighbours of the same type.
+        fn clustering(map: &GridMap2D<CollapsedTileData>, position: GridPosition) -> i64 {
+            let Some(tile) = map.get_tile_at_position(&position) else {
+                return 0;
+            };
+            map.get_neighbours(&position)
+                .iter()
+                .filter(|n| n.as_ref().tile_type_id() == tile.as_ref().tile_type_id())
+                .count() as i64
+        }
+        let total = |grid: &CollapsedGrid| {
+            grid.as_ref()
+                .size()
+                .iter_all_positions()
+                .map(|position| clustering(grid.as_ref(), position))
+                .sum::<i64>()
+        };
+        let count_first = |grid: &CollapsedGrid| {
+            grid.as_ref()
+                .iter_tiles()
+                .filter(|tile| tile.as_ref().tile_type_id() == 1)
+                .count()
+        };
+
+        let size = GridSize::new_xy(8, 8);
+        let positions = size.get_all_possible_positions();
+        let generate = |adjacency: &AdjacencyRules<BasicIdentTileData>,
+                        mut resolver: Resolver<_>| {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, adjacency);
+            resolver
+                .generate_entrophy(&mut grid, &mut ChaChaRng::seed_from_u64(3), &positions)
+                .unwrap();
+            grid.retrieve_collapsed()
+        };
+
+        let plain = generate(&free, Resolver::default());
+        let refined = generate(
+            &free,
+            Resolver::default().with_local_refinement(10, clustering),
+        );
+        assert!(total(&refined) > total(&plain));
+        assert_eq!(count_first(&plain), count_first(&refined));
+
+        // No swap is valid within the checkerboard.
+        let plain = generate(&checkerboard, Resolver::default());
+        let refined = generate(
+            &checkerboard,
+            Resolver::default().with_local_refinement(10, clustering),
+        );
+        assert_eq!(
+            plain.as_ref().get_all_positions(),
+            refined.as_ref().get_all_positions()
+        );
+        for tile in plain.as_ref().iter_tiles() {
+            assert_eq!(
+                tile.as_ref().tile_type_id(),
+                refined
+                    .as_ref()
+                    .get_tile_at_position(&tile.grid_position())
+                    .unwrap()
+                    .as_ref()
+                    .tile_type_id()
+            );
+        }
+    }
+
+    #[test]
+    fn constraint_restricts_options() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&first, 1);
+        frequency.set_weight_for_tile(&second, 1);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&first, &first, *dir);
+            adjacency.add_adjacency(&first, &second, *dir);
+            adjacency.add_adjacency(&second, &first, *dir);
+            adjacency.add_adjacency(&second, &second, *dir);
+        }
+
+        let size = GridSize::new_xy(6, 4);
+        let positions = size.get_all_possible_positions();
+        fn mask(pos: &GridPosition) -> Option<&[u64]> {
+            match pos.x() {
+                0 | 1 => Some(&[1]),
+                4 | 5 => Some(&[2, 3]),
+                _ => None,
+            }
+        }
+
+        for entrophy in [true, false] {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            let mut resolver = Resolver::default().with_constraint(mask);
+            if entrophy {
+                resolver
+                    .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+                    .unwrap();
+            } else {
+                resolver
+                    .generate_position(
+                        &mut grid,
+                        &mut thread_rng(),
+                        &positions,
+                        PositionQueue::default(),
+                    )
+                    .unwrap();
+            }
+            let collapsed = grid.retrieve_collapsed();
+            for pos in &positions {
+                let tile_type_id = collapsed
+                    .as_ref()
+                    .get_tile_at_position(pos)
+                    .unwrap()
+                    .as_ref()
+                    .tile_type_id();
+                match pos.x() {
+                    0 | 1 => assert_eq!(1, tile_type_id),
+                    4 | 5 => assert_eq!(2, tile_type_id),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+        let result = Resolver::default()
+            .with_constraint(|_| Some(&[3]))
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tie_seed_decides_collapse_order() {
+        let size = GridSize::new_xy(6, 6);
+        let positions = size.get_all_possible_positions();
+
+        let collapse_order = |tie_seed| {
+            let mut grid = single_tile_grid(size);
+            let mut resolver = Resolver::default()
+                .with_tie_seed(Some(tie_seed))
+                .with_subscriber(Box::new(CollapseHistorySubscriber::default()));
+            resolver
+                .generate_entrophy(&mut grid, &mut ChaChaRng::seed_from_u64(11), &positions)
+                .unwrap();
+            let subscriber = resolver.retrieve_subscriber().unwrap();
+            let order = subscriber
+                .as_any()
+                .downcast_ref::<CollapseHistorySubscriber>()
+                .unwrap()
+                .history()
+                .iter()
+                .map(|item| item.position)
+                .collect::<Vec<_>>();
+            order
+        };
+
+        assert_eq!(collapse_order(42), collapse_order(42));
+        assert_ne!(collapse_order(42), collapse_order(43));
+    }
+
+    #[test]
+    fn seeded_generation_is_reproducible() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&first, 1);
+        frequency.set_weight_for_tile(&second, 1);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&first, &first, *dir);
+            adjacency.add_adjacency(&first, &second, *dir);
+            adjacency.add_adjacency(&second, &second, *dir);
+        }
+
+        let size = GridSize::new_xy(8, 8);
+        let positions = size.get_all_possible_positions();
+        let seed = [7; 32];
+        let mut resolver = Resolver::default();
+
+        let mut generate = |entrophy: bool| {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            if entrophy {
+                resolver
+                    .generate_seeded(&mut grid, seed, &positions, EntrophyQueue::default())
+                    .unwrap();
+            } else {
+                resolver
+                    .generate_seeded(&mut grid, seed, &positions, PositionQueue::default())
+                    .unwrap();
+            }
+            let collapsed = grid.retrieve_collapsed();
+            positions
+                .iter()
+                .map(|pos| {
+                    collapsed
+                        .as_ref()
+                        .get_tile_at_position(pos)
+                        .unwrap()
+                        .as_ref()
+                        .tile_type_id()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(generate(true), generate(true));
+        assert_eq!(generate(false), generate(false));
+    }
+
+    #[test]
+    fn seeded_generation_uses_provided_queue() {
+        let size = GridSize::new_xy(6, 6);
+        let positions = size.get_all_possible_positions();
+        let mut resolver =
+            Resolver::default().with_subscriber(Box::new(CollapseHistorySubscriber::default()));
+
+        let mut collapse_order = |tie_seed| {
+            let mut grid = single_tile_grid(size);
+            resolver
+                .generate_seeded(
+                    &mut grid,
+                    [3; 32],
+                    &positions,
+                    EntrophyQueue::new_seeded(positions.len(), tie_seed),
+                )
+                .unwrap();
+            let subscriber = resolver.retrieve_subscriber().unwrap();
+            let order = subscriber
+                .as_any()
+                .downcast_ref::<CollapseHistorySubscriber>()
+                .unwrap()
+                .history()
+                .iter()
+                .map(|item| item.position)
+                .collect::<Vec<_>>();
+            resolver = Resolver::default().with_subscriber(subscriber);
+            order
+        };
+
+        assert_eq!(collapse_order(1), collapse_order(1));
+        assert_ne!(collapse_order(1), collapse_order(2));
+    }
+
+    #[test]
+    fn collapse_override_forces_option() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&first, 1000);
+        frequency.set_weight_for_tile(&second, 1);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&first, &first, *dir);
+            adjacency.add_adjacency(&first, &second, *dir);
+            adjacency.add_adjacency(&second, &first, *dir);
+            adjacency.add_adjacency(&second, &second, *dir);
+        }
+
+        let size = GridSize::new_xy(3, 3);
+        let positions = size.get_all_possible_positions();
+        let junction = GridPosition::new_xy(1, 1);
+        let mut resolver =
+            Resolver::default().with_collapse_override(move |position, remaining| {
+                assert!(!remaining.is_empty());
+                (*position == junction).then_some(2)
+            });
+
+        for entrophy in [true, false] {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            if entrophy {
+                resolver
+                    .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+                    .unwrap();
+            } else {
+                resolver
+                    .generate_position(
+                        &mut grid,
+                        &mut thread_rng(),
+                        &positions,
+                        PositionQueue::default(),
+                    )
+                    .unwrap();
+            }
+            let collapsed = grid.retrieve_collapsed();
+            let tile = collapsed.as_ref().get_tile_at_position(&junction).unwrap();
+            assert_eq!(2, tile.as_ref().tile_type_id());
+        }
+    }
+
+    #[test]
+    fn collapse_override_rejects_unavailable_option() {
+        let size = GridSize::new_xy(2, 2);
+        let mut grid = single_tile_grid(size);
+        let mut resolver = Resolver::default().with_collapse_override(|_, _| Some(5));
+
+        let err = resolver
+            .generate_entrophy(
+                &mut grid,
+                &mut thread_rng(),
+                &size.get_all_possible_positions(),
+            )
+            .unwrap_err();
+
+        assert!(err.to_string().contains("forced option: 5"));
+    }
+
+    #[test]
+    fn timings_are_recorded() {
+        let size = GridSize::new_xy(10, 10);
+        let mut grid = single_tile_grid(size);
+        let positions = size.get_all_possible_positions();
+
+        let mut resolver = Resolver::default();
+        resolver
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap();
+        assert!(resolver.last_timings().is_none());
+
+        let mut resolver = resolver.with_timings(true);
+        resolver
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap();
+        let timings = resolver.last_timings().unwrap();
+
+        assert!(timings.total > Duration::ZERO);
+        assert!(timings.initialization + timings.propagation + timings.collapse <= timings.total);
+    }
+
+    #[test]
+    fn subscriber_time_is_excluded_from_phases() {
+        struct SlowSubscriber;
+
+        impl Subscriber for SlowSubscriber {
+            fn on_collapse(&mut self, _position: &GridPosition, _tile_type_id: u64) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+
+        let size = GridSize::new_xy(3, 3);
+        let positions = size.get_all_possible_positions();
+        let mut resolver = Resolver::default()
+            .with_timings(true)
+            .with_subscriber(Box::new(SlowSubscriber));
+
+        for entrophy in [true, false] {
+            let mut grid = single_tile_grid(size);
+            if entrophy {
+                resolver
+                    .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+                    .unwrap();
+            } else {
+                resolver
+                    .generate_position(
+                        &mut grid,
+                        &mut thread_rng(),
+                        &positions,
+                        PositionQueue::default(),
+                    )
+                    .unwrap();
+            }
+            let timings = resolver.last_timings().unwrap();
+            let subscriber_time = Duration::from_millis(5 * 9);
+
+            assert!(timings.total >= subscriber_time);
+            assert!(
+                timings.initialization + timings.propagation + timings.collapse
+                    <= timings.total - subscriber_time
+            );
+        }
+    }
+
+    #[test]
+    fn collapsed_neighbour_count() {
+        let size = GridSize::new_xy(3, 3);
+        let mut grid = single_tile_grid(size);
+        let center = GridPosition::new_xy(1, 1);
+        assert_eq!(0, grid.collapsed_neighbour_count(&center));
+
+        let mut resolver = Resolver::default();
+        resolver
+            .generate_entrophy(&mut grid, &mut thread_rng(), &[center])
+            .unwrap();
+
+        for neighbour in [(1, 0), (0, 1), (2, 1), (1, 2)] {
+            let position = GridPosition::new_xy(neighbour.0, neighbour.1);
+            assert!(grid.collapsed_neighbour_count(&position) >= 1);
+        }
+        assert_eq!(
+            0,
+            grid.collapsed_neighbour_count(&GridPosition::new_xy(0, 0))
+        );
+    }
+
+    #[test]
+    fn history_replay_follows_collapse_order() {
+        let size = GridSize::new_xy(4, 3);
+        let mut grid = single_tile_grid(size);
+
+        let mut resolver =
+            Resolver::default().with_subscriber(Box::new(CollapseHistorySubscriber::default()));
+        resolver
+            .generate_entrophy(
+                &mut grid,
+                &mut thread_rng(),
+                &size.get_all_possible_positions(),
+            )
+            .unwrap();
+
+        let subscriber = resolver.retrieve_subscriber().unwrap();
+        let subscriber = subscriber
+            .as_any()
+            .downcast_ref::<CollapseHistorySubscriber>()
+            .unwrap();
+        let builder = IdentTileTraitBuilder::<BasicIdentTileData>::default();
+
+        let partial = subscriber.replay_into(5, &builder);
+        assert_eq!(&size, partial.size());
+        assert_eq!(5, partial.get_all_positions().len());
+        for item in &subscriber.history()[..5] {
+            assert_eq!(
+                Some(item.tile_type_id),
+                partial
+                    .get_tile_at_position(&item.position)
+                    .map(|tile| tile.as_ref().tile_type_id())
+            );
+        }
+
+        let full = subscriber.replay_into(usize::MAX, &builder);
+        assert_eq!(12, full.get_all_positions().len());
+
+        // Size can be kept even if the last row and column were never collapsed.
+        let mut grid = single_tile_grid(size);
+        let positions = GridSize::new_xy(2, 2).get_all_possible_positions();
+        let mut resolver =
+            Resolver::default().with_subscriber(Box::new(CollapseHistorySubscriber::default()));
+        resolver
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap();
+        let subscriber = resolver.retrieve_subscriber().unwrap();
+        let subscriber = subscriber
+            .as_any()
+            .downcast_ref::<CollapseHistorySubscriber>()
+            .unwrap();
+        let inferred = subscriber.replay_into(usize::MAX, &builder);
+        assert_eq!(&GridSize::new_xy(2, 2), inferred.size());
+        let replayed = subscriber.replay_into_sized(size, usize::MAX, &builder);
+        assert_eq!(&size, replayed.size());
+        assert_eq!(4, replayed.get_all_positions().len());
+    }
+
+    #[test]
+    fn frontier_queue_grows_contiguously() {
+        let size = GridSize::new_xy(5, 5);
+        let mut grid = single_tile_grid(size);
+        let positions = size.get_all_possible_positions();
+        let seed = GridPosition::new_xy(2, 2);
+
+        let mut resolver =
+            Resolver::default().with_subscriber(Box::new(CollapseHistorySubscriber::default()));
+        resolver
+            .generate_position(
+                &mut grid,
+                &mut thread_rng(),
+                &positions,
+                FrontierQueue::new(&[seed]),
+            )
+            .unwrap();
+
+        let subscriber = resolver.retrieve_subscriber().unwrap();
+        let history = subscriber
+            .as_any()
+            .downcast_ref::<CollapseHistorySubscriber>()
+            .unwrap()
+            .history();
+
+        assert_eq!(positions.len(), history.len());
+        assert_eq!(seed, history[0].position);
+        for (idx, item) in history.iter().enumerate().skip(1) {
+            let adjacent = history[..idx].iter().any(|previous| {
+                GridDir::ALL_2D
+                    .iter()
+                    .any(|dir| dir.march_step(&previous.position, &size) == Some(item.position))
+            });
+            assert!(
+                adjacent,
+                "position {:?} is not adjacent to collapsed region",
+                item.position
+            );
+        }
+    }
+
+    #[test]
+    fn failure_snapshot_contains_placed_tiles() {
+        // Two tiles which can be adjacent only to themselves, placed on both ends of the row.
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        let mut adjacency = AdjacencyRules::default();
+        for tile in [&first, &second] {
+            frequency.set_weight_for_tile(tile, 1);
+            for dir in GridDir::ALL_2D {
+                adjacency.add_adjacency(tile, tile, *dir);
+            }
+        }
+
+        let size = GridSize::new_xy(4, 1);
+        let mut collapsed = CollapsedGrid::new(size);
+        collapsed.insert_data(&GridPosition::new_xy(0, 0), CollapsedTileData::new(1));
+        collapsed.insert_data(&GridPosition::new_xy(3, 0), CollapsedTileData::new(2));
+        let positions = [GridPosition::new_xy(1, 0), GridPosition::new_xy(2, 0)];
+
+        let mut grid =
+            CollapsibleTileGrid::new_from_collapsed(&collapsed, &frequency, &adjacency).unwrap();
+        let err = Resolver::default()
+            .with_failure_snapshot(true)
+            .generate_position(
+                &mut grid,
+                &mut thread_rng(),
+                &positions,
+                PositionQueue::default(),
+            )
+            .unwrap_err();
+        let snapshot = err.failure_snapshot().expect("snapshot should be captured");
+        assert_eq!(3, snapshot.as_ref().iter_tiles().count());
+        assert!(snapshot
+            .as_ref()
+            .get_tile_at_position(&GridPosition::new_xy(1, 0))
+            .is_some());
+
+        let mut grid =
+            CollapsibleTileGrid::new_from_collapsed(&collapsed, &frequency, &adjacency).unwrap();
+        let err = Resolver::default()
+            .generate_position(
+                &mut grid,
+                &mut thread_rng(),
+                &positions,
+                PositionQueue::default(),
+            )
+            .unwrap_err();
+        assert!(err.failure_snapshot().is_none());
+    }
+
+    #[test]
+    fn backtracking_recovers_from_contradictions() {
+        // Neighbouring tiles need to differ - akin to colouring the grid with three colours, which forward-only
+        // collapse can't always finish.
+        let tiles = [1, 2, 3]
+            .map(|id| GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(id)));
+        let mut frequency = FrequencyHints::default();
+        let mut adjacency = AdjacencyRules::default();
+        for tile in tiles.iter() {
+            frequency.set_weight_for_tile(tile, 2);
+            for other in tiles.iter() {
+                if tile.as_ref().tile_type_id() == other.as_ref().tile_type_id() {
+                    continue;
+                }
+                for dir in GridDir::ALL_2D {
+                    adjacency.add_adjacency(tile, other, *dir);
+                }
+            }
+        }
+
+        let size = GridSize::new_xy(8, 8);
+        let positions = size.get_all_possible_positions();
+        let mut forward_failures = 0;
+        let mut backtrack_failures = 0;
+        for seed in 0..40 {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            if Resolver::default()
+                .generate_entrophy(&mut grid, &mut ChaChaRng::seed_from_u64(seed), &positions)
+                .is_err()
+            {
+                forward_failures += 1;
+            }
+
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            if Resolver::default()
+                .with_backtracking(8)
+                .generate_entrophy(&mut grid, &mut ChaChaRng::seed_from_u64(seed), &positions)
+                .is_err()
+            {
+                backtrack_failures += 1;
+                continue;
+            }
+            let collapsed = grid.retrieve_collapsed();
+            for pos in &positions {
+                let id = collapsed
+                    .as_ref()
+                    .get_tile_at_position(pos)
+                    .unwrap()
+                    .as_ref()
+                    .tile_type_id();
+                for neighbour in collapsed.as_ref().get_neighbours(pos) {
+                    assert_ne!(id, neighbour.as_ref().tile_type_id());
+                }
+            }
+        }
+        assert!(forward_failures > 0);
+        assert_eq!(0, backtrack_failures);
+    }
+
+    #[test]
+    fn contradiction_lists_conflicts() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        let mut adjacency = AdjacencyRules::default();
+        for tile in [&first, &second] {
+            frequency.set_weight_for_tile(tile, 1);
+            for dir in GridDir::ALL_2D {
+                adjacency.add_adjacency(tile, tile, *dir);
+            }
+        }
+
+        let size = GridSize::new_xy(3, 1);
+        let mut collapsed = CollapsedGrid::new(size);
+        collapsed.insert_data(&GridPosition::new_xy(0, 0), CollapsedTileData::new(1));
+        collapsed.insert_data(&GridPosition::new_xy(2, 0), CollapsedTileData::new(2));
+
+        let mut grid =
+            CollapsibleTileGrid::new_from_collapsed(&collapsed, &frequency, &adjacency).unwrap();
+        let err = Resolver::default()
+            .generate_entrophy(&mut grid, &mut thread_rng(), &[GridPosition::new_xy(1, 0)])
+            .unwrap_err();
+
+        assert_eq!(GridPosition::new_xy(1, 0), err.failed_pos());
+        assert_eq!(&[1, 2], err.failed_options());
+        assert_eq!(
+            &[GridDir::LEFT, GridDir::RIGHT],
+            err.conflicting_directions()
+        );
+    }
+
+    #[test]
+    fn contradiction_callback_is_notified() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        let mut adjacency = AdjacencyRules::default();
+        for tile in [&first, &second] {
+            frequency.set_weight_for_tile(tile, 1);
+            for dir in GridDir::ALL_2D {
+                adjacency.add_adjacency(tile, tile, *dir);
+            }
+        }
+
+        let size = GridSize::new_xy(3, 1);
+        let mut collapsed = CollapsedGrid::new(size);
+        collapsed.insert_data(&GridPosition::new_xy(0, 0), CollapsedTileData::new(1));
+        collapsed.insert_data(&GridPosition::new_xy(2, 0), CollapsedTileData::new(2));
+
+        let reported = Rc::new(RefCell::new(Vec::new()));
+        let sink = reported.clone();
+        let mut resolver = Resolver::default().on_contradiction(move |pos, dirs| {
+            sink.borrow_mut().push((*pos, dirs.to_vec()));
+        });
+
+        let mut grid =
+            CollapsibleTileGrid::new_from_collapsed(&collapsed, &frequency, &adjacency).unwrap();
+        assert!(resolver
+            .generate_entrophy(&mut grid, &mut thread_rng(), &[GridPosition::new_xy(1, 0)])
+            .is_err());
+        let mut grid =
+            CollapsibleTileGrid::new_from_collapsed(&collapsed, &frequency, &adjacency).unwrap();
+        assert!(resolver
+            .generate_position(
+                &mut grid,
+                &mut thread_rng(),
+                &[GridPosition::new_xy(1, 0)],
+                PositionQueue::default()
+            )
+            .is_err());
+
+        let expected = (
+            GridPosition::new_xy(1, 0),
+            vec![GridDir::LEFT, GridDir::RIGHT],
+        );
+        assert_eq!(vec![expected.clone(), expected], *reported.borrow());
+    }
+
+    #[test]
+    fn contradiction_callback_sees_recovered_contradictions() {
+        let tiles = [1, 2, 3]
+            .map(|id| GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(id)));
+        let mut frequency = FrequencyHints::default();
+        let mut adjacency = AdjacencyRules::default();
+        for tile in tiles.iter() {
+            frequency.set_weight_for_tile(tile, 2);
+            for other in tiles.iter() {
+                if tile.as_ref().tile_type_id() == other.as_ref().tile_type_id() {
+                    continue;
+                }
+                for dir in GridDir::ALL_2D {
+                    adjacency.add_adjacency(tile, other, *dir);
+                }
+            }
+        }
+
+        let size = GridSize::new_xy(8, 8);
+        let positions = size.get_all_possible_positions();
+        let mut recovered = 0;
+        for seed in 0..40 {
+            let reported = Rc::new(Cell::new(0));
+            let sink = reported.clone();
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            let result = Resolver::default()
+                .with_backtracking(8)
+                .on_contradiction(move |_, _| sink.set(sink.get() + 1))
+                .generate_entrophy(&mut grid, &mut ChaChaRng::seed_from_u64(seed), &positions);
+            if result.is_ok() && reported.get() > 0 {
+                recovered += 1;
+            }
+        }
+        assert!(recovered > 0);
+    }
+
+    #[test]
+    fn impossible_options_reported() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&first, 1);
+        frequency.set_weight_for_tile(&second, 1);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&first, &first, *dir);
+        }
+        // Second tile can have neighbours in every direction except left.
+        adjacency.add_adjacency(&second, &second, GridDir::UP);
+        adjacency.add_adjacency(&second, &first, GridDir::RIGHT);
+
+        let grid = CollapsibleTileGrid::new_empty(GridSize::new_xy(3, 3), &frequency, &adjacency);
+        assert_eq!(vec![2], grid.impossible_options());
+
+        assert!(single_tile_grid(GridSize::new_xy(3, 3))
+            .impossible_options()
+            .is_empty());
+    }
+
+    #[test]
+    fn degree_tie_break_generates() {
+        let size = GridSize::new_xy(6, 6);
+        let mut grid = single_tile_grid(size);
+        Resolver::default()
+            .with_degree_tie_break(true)
+            .generate_entrophy(
+                &mut grid,
+                &mut ChaChaRng::seed_from_u64(3),
+                &size.get_all_possible_positions(),
+            )
+            .unwrap();
+        assert!(grid.retrieve_positions(false).is_empty());
+        assert_eq!(36, grid.retrieve_positions(true).len());
+    }
+
+    #[test]
+    fn required_adjacency_unsatisfiable() {
+        let size = GridSize::new_xy(4, 4);
+        let positions = size.get_all_possible_positions();
+        let mut required = RequiredAdjacency::default();
+        // Tile 2 is not present in the rules, so the requirement can't ever be met.
+        required.add_required_neighbour_raw(1, GridDir::RIGHT, 2);
+
+        let mut grid = single_tile_grid(size);
+        let err = Resolver::default()
+            .with_required_adjacency(required.clone())
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap_err();
+        assert!(err.to_string().contains("required neighbour"));
+
+        let mut grid = single_tile_grid(size);
+        assert!(Resolver::default()
+            .with_required_adjacency(required)
+            .generate_position(
+                &mut grid,
+                &mut thread_rng(),
+                &positions,
+                PositionQueue::default()
+            )
+            .is_err());
+
+        // Tile requiring itself below is satisfied everywhere except the bottom row, which is left empty.
+        let mut required = RequiredAdjacency::default();
+        required.add_required_neighbour_raw(1, GridDir::DOWN, 1);
+        let mut grid = single_tile_grid(size);
+        let positions = GridPosition::generate_rect_area(
+            &GridPosition::new_xy(0, 0),
+            &GridPosition::new_xy(3, 2),
+        );
+        Resolver::default()
+            .with_required_adjacency(required)
+            .generate_entrophy(&mut grid, &mut thread_rng(), &positions)
+            .unwrap();
+    }
+}
@@ -5,7 +5,7 @@ use rand::Rng;
 
 use crate::gen::collapse::error::CollapsibleGridError;
 use crate::gen::collapse::grid::CollapsibleGrid;
-use crate::gen::collapse::option::{PerOptionData, WaysToBeOption};
+use crate::gen::collapse::option::{PerOptionData, WaysToBeOption, Weight};
 use crate::gen::collapse::{self, tile::*, CollapsedGrid, PropagateItem};
 use crate::map::{GridMap2D, GridSize};
 use crate::tile::identifiable::builders::IdentTileBuilder;
@@ -13,7 +13,7 @@ use crate::tile::identifiable::collection::IdentTileCollection;
 use crate::tile::identifiable::IdentifiableTileData;
 use crate::tile::{GridPosition, GridTile, TileContainer, TileData};
 
-use super::{AdjacencyRules, FrequencyHints};
+use super::{AdjacencyRules, Frequencies};
 
 /// Tile with options that can be collapsed into one of them. Mostly used within the [`CollapsibleTileGrid`].
 #[derive(Clone, Debug)]
@@ -21,7 +21,7 @@ pub struct CollapsibleTile {
     collapsed_option: Option<usize>,
     num_possible_options: usize,
     ways_to_be_option: WaysToBeOption,
-    weight_sum: u32,
+    weight_sum: Weight,
     weight_log_sum: f32,
     entrophy_noise: f32,
 }
@@ -29,7 +29,7 @@ pub struct CollapsibleTile {
 impl TileData for CollapsibleTile {}
 
 impl crate::gen::collapse::tile::private::Sealed for CollapsibleTile {
-    fn remove_option(&mut self, weights: (u32, f32)) {
+    fn remove_option(&mut self, weights: (Weight, f32)) {
         self.num_possible_options -= 1;
         self.weight_sum -= weights.0;
         self.weight_log_sum -= weights.1;
@@ -39,7 +39,7 @@ impl crate::gen::collapse::tile::private::Sealed for CollapsibleTile {
         position: GridPosition,
         num_possible_options: usize,
         ways_to_be_option: WaysToBeOption,
-        weight_sum: u32,
+        weight_sum: Weight,
         weight_log_sum: f32,
         entrophy_noise: f32,
     ) -> GridTile<Self>
@@ -76,23 +76,17 @@ impl crate::gen::collapse::tile::private::Sealed for CollapsibleTile {
         rng: &mut R,
         options_data: &PerOptionData,
     ) -> Option<Vec<usize>> {
-        assert!(self.weight_sum > 0);
-        let random = rng.gen_range(0..self.weight_sum);
-        let mut current_sum = 0;
-        let mut chosen = None;
-        let mut out = Vec::new();
-        for option_idx in self.ways_to_be_option().iter_possible() {
-            current_sum += options_data.get_weights(option_idx).0;
-            if chosen.is_some() || random > current_sum {
-                out.push(option_idx);
-                continue;
-            }
-            chosen = Some(option_idx);
-        }
+        assert!(self.weight_sum.is_positive());
+        let chosen = options_data.choose_option(rng, self.weight_sum, self.ways_to_be_option());
         assert!(chosen.is_some(), "option should always be chosen!");
+        let out = self
+            .ways_to_be_option()
+            .iter_possible()
+            .filter(|option_idx| Some(*option_idx) != chosen)
+            .collect();
         self.collapsed_option = chosen;
         self.num_possible_options = 0;
-        self.weight_sum = 0;
+        self.weight_sum = Weight::ZERO;
         self.weight_log_sum = 0.;
         Some(out)
     }
@@ -100,11 +94,11 @@ impl crate::gen::collapse::tile::private::Sealed for CollapsibleTile {
     fn mark_collapsed(&mut self, collapsed_idx: usize) {
         self.collapsed_option = Some(collapsed_idx);
         self.num_possible_options = 0;
-        self.weight_sum = 0;
+        self.weight_sum = Weight::ZERO;
         self.weight_log_sum = 0.;
     }
 
-    fn weight_sum(&self) -> u32 {
+    fn weight_sum(&self) -> Weight {
         self.weight_sum
     }
 }
@@ -115,7 +109,7 @@ impl CollapsibleTileData for CollapsibleTile {
     }
 
     fn calc_entrophy(&self) -> f32 {
-        Self::calc_entrophy_ext(self.weight_sum, self.weight_log_sum) + self.entrophy_noise
+        Self::calc_entrophy_ext(self.weight_sum.as_f32(), self.weight_log_sum) + self.entrophy_noise
     }
 
     fn num_compatible_options(&self) -> usize {
@@ -127,7 +121,7 @@ impl CollapsibleTileData for CollapsibleTile {
             collapsed_option: Some(option_idx),
             num_possible_options: 0,
             ways_to_be_option: WaysToBeOption::default(),
-            weight_sum: 0,
+            weight_sum: Weight::ZERO,
             weight_log_sum: 0.,
             entrophy_noise: 0.,
         }
@@ -195,11 +189,11 @@ impl<Tile: IdentifiableTileData> CollapsibleTileGrid<Tile> {
     /// Creates a new empty grid with given [`GridSize`], preparing the rules for the generation of the tiles and the weights of the options.
     pub fn new_empty(
         size: GridSize,
-        frequencies: &FrequencyHints<Tile>,
+        frequencies: &impl Frequencies<Tile>,
         adjacencies: &AdjacencyRules<Tile>,
     ) -> Self {
         let mut option_data = PerOptionData::default();
-        option_data.populate(&frequencies.get_all_weights_cloned(), adjacencies.inner());
+        frequencies._populate_option_data(&mut option_data, adjacencies.inner());
 
         Self {
             grid: GridMap2D::new(size),
@@ -215,11 +209,11 @@ impl<Tile: IdentifiableTileData> CollapsibleTileGrid<Tile> {
     /// provided frequency hints and adjacency rules.
     pub fn new_from_collapsed(
         collapsed: &CollapsedGrid,
-        frequencies: &FrequencyHints<Tile>,
+        frequencies: &impl Frequencies<Tile>,
         adjacencies: &AdjacencyRules<Tile>,
     ) -> Result<Self, CollapsibleGridError> {
         let mut option_data = PerOptionData::default();
-        option_data.populate(&frequencies.get_all_weights_cloned(), adjacencies.inner());
+        frequencies._populate_option_data(&mut option_data, adjacencies.inner());
 
         let missing_ids = collapsed
             .tile_type_ids()
@@ -257,7 +251,7 @@ impl<Tile: IdentifiableTileData> CollapsibleTileGrid<Tile> {
     /// provided frequency hints and adjacency rules.
     pub fn change(
         self,
-        frequencies: &FrequencyHints<Tile>,
+        frequencies: &impl Frequencies<Tile>,
         adjacencies: &AdjacencyRules<Tile>,
     ) -> Result<Self, CollapsibleGridError> {
         let collapsed = self.retrieve_collapsed();
@@ -274,11 +268,7 @@ impl<Tile: IdentifiableTileData> CollapsibleTileGrid<Tile> {
         &mut self,
         collapsed: &CollapsedGrid,
     ) -> Result<(), CollapsibleGridError> {
-        if !self
-            .grid
-            .size
-            .is_contained_within(collapsed.as_ref().size())
-        {
+        if !self.grid.size.contains_size(collapsed.as_ref().size()) {
             return Err(CollapsibleGridError::new_wrong_size(
                 collapsed.as_ref().size,
                 self.grid.size,
@@ -309,6 +299,18 @@ impl<Tile: IdentifiableTileData> CollapsibleTileGrid<Tile> {
 
         Ok(())
     }
+
+    /// Returns the internal option index, used during propagation in place of the `tile_type_id`, for the tile with given
+    /// `tile_type_id`. Returns [`None`] if there is no such option.
+    pub fn option_index_for(&self, tile_type_id: u64) -> Option<usize> {
+        self.option_data.get_tile_data(&tile_type_id).copied()
+    }
+
+    /// Returns the `tile_type_id` of the tile represented by the internal option `index`. Returns [`None`] if there is
+    /// no such option.
+    pub fn tile_type_for(&self, index: usize) -> Option<u64> {
+        self.option_data.get_tile_type_id(&index)
+    }
 }
 
 impl<Tile: IdentifiableTileData> CollapsibleGrid<Tile, CollapsibleTile>
@@ -412,3 +414,115 @@ impl<Tile: IdentifiableTileData> collapse::grid::private::Sealed<CollapsibleTile
         out
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    use crate::gen::collapse::singular::{
+        AdjacencyRules, CollapsibleTileGrid, FrequencyHints, FrequencyHintsF, Resolver,
+    };
+    use crate::gen::collapse::{CollapsedGrid, CollapsibleGrid, PositionQueue};
+    use crate::map::{GridDir, GridSize};
+    use crate::tile::identifiable::builders::ConstructableViaIdentifierTile;
+    use crate::tile::identifiable::BasicIdentTileData;
+    use crate::tile::{GridPosition, GridTile};
+
+    #[test]
+    fn option_index_round_trip() {
+        let tiles = [3, 11, 42]
+            .map(|id| GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(id)));
+        let mut frequency = FrequencyHints::default();
+        let mut adjacency = AdjacencyRules::default();
+        for tile in tiles.iter() {
+            frequency.set_weight_for_tile(tile, 1);
+            for other in tiles.iter() {
+                for dir in GridDir::ALL_2D {
+                    adjacency.add_adjacency(tile, other, *dir);
+                }
+            }
+        }
+        let grid = CollapsibleTileGrid::new_empty(GridSize::new_xy(2, 2), &frequency, &adjacency);
+
+        let num_options = grid.option_data.num_options();
+        assert_eq!(3, num_options);
+        for index in 0..num_options {
+            let tile_type_id = grid.tile_type_for(index).unwrap();
+            assert_eq!(Some(index), grid.option_index_for(tile_type_id));
+        }
+        assert_eq!(None, grid.tile_type_for(num_options));
+        assert_eq!(None, grid.option_index_for(7));
+    }
+
+    #[test]
+    fn integer_weights_keep_selection_arithmetic() {
+        let first = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        let second = GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(2));
+        let mut frequency = FrequencyHints::default();
+        frequency.set_weight_for_tile(&first, 3);
+        frequency.set_weight_for_tile(&second, 5);
+        let mut adjacency = AdjacencyRules::default();
+        for dir in GridDir::ALL_2D {
+            adjacency.add_adjacency(&first, &second, *dir);
+            adjacency.add_adjacency(&second, &first, *dir);
+        }
+        let size = GridSize::new_xy(1, 1);
+        let positions = size.get_all_possible_positions();
+
+        for seed in 0..32 {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            Resolver::default()
+                .generate_position(
+                    &mut grid,
+                    &mut ChaChaRng::seed_from_u64(seed),
+                    &positions,
+                    PositionQueue::default(),
+                )
+                .unwrap();
+            let collapsed: CollapsedGrid = grid.retrieve_collapsed();
+
+            let random = ChaChaRng::seed_from_u64(seed).gen_range(0..8u32);
+            let expected = if random > 3 { 2 } else { 1 };
+            assert_eq!(
+                vec![&expected],
+                collapsed.tile_type_ids().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn fractional_weights_always_choose_option() {
+        let tiles = [1, 2, 3]
+            .map(|id| GridTile::new(GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(id)));
+        let mut frequency = FrequencyHintsF::default();
+        let mut adjacency = AdjacencyRules::default();
+        for (tile, weight) in tiles.iter().zip([0.1, 0.2, 0.7]) {
+            frequency.set_weight_for_tile(tile, weight);
+            for other in tiles.iter() {
+                for dir in GridDir::ALL_2D {
+                    adjacency.add_adjacency(tile, other, *dir);
+                }
+            }
+        }
+        let size = GridSize::new_xy(4, 4);
+        let positions = size.get_all_possible_positions();
+
+        for seed in 0..64 {
+            let mut grid = CollapsibleTileGrid::new_empty(size, &frequency, &adjacency);
+            Resolver::default()
+                .generate_position(
+                    &mut grid,
+                    &mut ChaChaRng::seed_from_u64(seed),
+                    &positions,
+                    PositionQueue::default(),
+                )
+                .unwrap();
+            let collapsed: CollapsedGrid = grid.retrieve_collapsed();
+            assert_eq!(
+                positions.len(),
+                collapsed.as_ref().get_all_positions().len()
+            );
+        }
+    }
+}
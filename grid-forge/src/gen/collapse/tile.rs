@@ -6,6 +6,7 @@ use crate::tile::TileData;
 ///
 /// Identical in most cases to [`BasicIdentTileData`](crate::tile::identifiable::BasicIdentTileData), but used consistently within the
 /// collapse algorithms - both as input for some initial constraints for the generation process, and as an collapse process output.
+#[derive(Debug)]
 pub struct CollapsedTileData {
     tile_type_id: u64,
 }
@@ -57,8 +58,8 @@ pub trait CollapsibleTileData: TileData + private::Sealed {
 
     /// Associated function to calculate entrophy.
     #[inline]
-    fn calc_entrophy_ext(weight_sum: u32, weight_log_sum: f32) -> f32 {
-        (weight_sum as f32).log2() - weight_log_sum / (weight_sum as f32)
+    fn calc_entrophy_ext(weight_sum: f32, weight_log_sum: f32) -> f32 {
+        weight_sum.log2() - weight_log_sum / weight_sum
     }
 }
 
@@ -71,7 +72,7 @@ pub(crate) mod private {
     };
 
     use crate::{
-        gen::collapse::option::{PerOptionData, WaysToBeOption},
+        gen::collapse::option::{PerOptionData, WaysToBeOption, Weight},
         map::{GridDir, GridMap2D},
         tile::{self, GridPosition, GridTile},
     };
@@ -86,7 +87,7 @@ pub(crate) mod private {
             position: GridPosition,
             num_options: usize,
             ways_to_be_option: WaysToBeOption,
-            weight_sum: u32,
+            weight_sum: Weight,
             weight_log_sum: f32,
             entrophy_noise: f32,
         ) -> GridTile<Self>;
@@ -104,7 +105,7 @@ pub(crate) mod private {
                 .iter_possible()
                 .map(|option_idx| options_data.get_weights(option_idx))
                 .fold(
-                    (0u32, 0f32),
+                    (Weight::ZERO, 0f32),
                     |(sum_weight, sum_weight_log), (weight, weight_log)| {
                         (sum_weight + weight, sum_weight_log + weight_log)
                     },
@@ -136,7 +137,7 @@ pub(crate) mod private {
                 .iter_possible()
                 .map(|option_idx| options_data.get_weights(option_idx))
                 .fold(
-                    (0u32, 0f32),
+                    (Weight::ZERO, 0f32),
                     |(sum_weight, sum_weight_log), (weight, weight_log)| {
                         (sum_weight + weight, sum_weight_log + weight_log)
                     },
@@ -164,7 +165,7 @@ pub(crate) mod private {
         fn mut_ways_to_be_option(&mut self) -> &mut WaysToBeOption;
 
         /// Removes single option from tile.
-        fn remove_option(&mut self, weights: (u32, f32));
+        fn remove_option(&mut self, weights: (Weight, f32));
 
         /// Range of uniformly distributed data for entrophy noise.
         fn entrophy_uniform() -> Uniform<f32> {
@@ -179,7 +180,7 @@ pub(crate) mod private {
 
         fn mark_collapsed(&mut self, collapsed_idx: usize);
 
-        fn weight_sum(&self) -> u32;
+        fn weight_sum(&self) -> Weight;
 
         /// Collapses tile into one of possible options, returning the vector of the removed options.
         fn collapse_gather_removed<R: Rng>(
@@ -188,42 +189,81 @@ pub(crate) mod private {
             options_data: &PerOptionData,
         ) -> Vec<usize> {
             assert!(
-                self.weight_sum() > 0,
+                self.weight_sum().is_positive(),
                 "weight sum should be positive when collapsing!"
             );
-            let random = rng.gen_range(0..self.weight_sum());
-            let mut current_sum = 0;
+            let chosen = options_data
+                .choose_option(rng, self.weight_sum(), self.ways_to_be_option())
+                .expect("options should always be chosen");
+            let out = self
+                .ways_to_be_option()
+                .iter_possible()
+                .filter(|option_idx| *option_idx != chosen)
+                .collect();
+            self.mark_collapsed(chosen);
+            out
+        }
+
+        /// Collapses tile into one of possible options with their weights multiplied by `multiplier` for each option,
+        /// returning the vector of the removed options. Falls back to
+        /// [`collapse_gather_removed`](Self::collapse_gather_removed) if no option has positive weight after
+        /// multiplying.
+        fn collapse_biased<R: Rng>(
+            &mut self,
+            rng: &mut R,
+            options_data: &PerOptionData,
+            multiplier: &dyn Fn(usize) -> f32,
+        ) -> Vec<usize> {
+            let weighted = self
+                .ways_to_be_option()
+                .iter_possible()
+                .map(|option_idx| {
+                    let weight = options_data.get_weights(option_idx).0.as_f32();
+                    (option_idx, weight * multiplier(option_idx).max(0.))
+                })
+                .collect::<Vec<_>>();
+            let total = weighted.iter().map(|(_, weight)| weight).sum::<f32>();
+            if total <= 0. {
+                return self.collapse_gather_removed(rng, options_data);
+            }
+
+            let random = rng.gen_range(0.0..total);
+            let mut current_sum = 0.;
             let mut chosen = None;
-            let mut out = Vec::new();
-            for option_idx in self.ways_to_be_option().iter_possible() {
-                current_sum += options_data.get_weights(option_idx).0;
-                if chosen.is_some() || random > current_sum {
-                    out.push(option_idx);
+            let mut last_positive = None;
+            for (option_idx, weight) in weighted.iter() {
+                if *weight <= 0. {
                     continue;
                 }
-                chosen = Some(option_idx);
+                current_sum += weight;
+                last_positive = Some(*option_idx);
+                if random < current_sum {
+                    chosen = Some(*option_idx);
+                    break;
+                }
             }
-            self.mark_collapsed(chosen.expect("options should always be chosen"));
-            out
+            // Floating point rounding can leave `random` just above the final sum.
+            let chosen = chosen
+                .or(last_positive)
+                .expect("options should always be chosen");
+            self.mark_collapsed(chosen);
+            weighted
+                .into_iter()
+                .map(|(option_idx, _)| option_idx)
+                .filter(|option_idx| *option_idx != chosen)
+                .collect()
         }
 
         /// Collapses tiles into one of possible options.
         fn collapse_basic<R: Rng>(&mut self, rng: &mut R, options_data: &PerOptionData) {
             assert!(
-                self.weight_sum() > 0,
+                self.weight_sum().is_positive(),
                 "weight sum should be positive when collapsing!"
             );
-            let random = rng.gen_range(0..self.weight_sum());
-            let mut current_sum = 0;
-            let mut chosen = None;
-            for option_idx in self.ways_to_be_option().iter_possible() {
-                current_sum += options_data.get_weights(option_idx).0;
-                if chosen.is_some() || random > current_sum {
-                    continue;
-                }
-                chosen = Some(option_idx);
-            }
-            self.mark_collapsed(chosen.expect("options should always be chosen"));
+            let chosen = options_data
+                .choose_option(rng, self.weight_sum(), self.ways_to_be_option())
+                .expect("options should always be chosen");
+            self.mark_collapsed(chosen);
         }
 
         /// Removes options from tile neighbours after its collapse.
@@ -1,4 +1,14 @@
 //! Generative algorithms for procedural generation of gridmaps.
+//!
+//! ## Randomness
+//!
+//! All algorithms are generic over the [`Rng`](rand::Rng) used, so any random number generator can be plugged in. For
+//! reproducible results, use seedable generator such as [`DefaultRng`] - the same seed and inputs will always produce
+//! the same output. Resolvers provide `generate_seeded` methods, creating the [`DefaultRng`] internally for this
+//! common case.
 
 pub mod collapse;
 pub mod walker;
+
+/// Default deterministic random number generator, used by the `*_seeded` convenience methods.
+pub type DefaultRng = rand_chacha::ChaChaRng;
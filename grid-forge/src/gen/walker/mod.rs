@@ -1,5 +1,5 @@
 use rand::{
-    distributions::{Distribution, Uniform},
+    distributions::{Distribution, Uniform, WeightedIndex},
     Rng,
 };
 use std::collections::HashSet;
@@ -20,6 +20,11 @@ where
     walked: HashSet<GridPosition>,
     rng: R,
     dir_rng: Uniform<usize>,
+    dir_weights: Option<WeightedIndex<u32>>,
+    direction_weights: [u32; 4],
+    center_bias: Option<f32>,
+    brush_radius: u32,
+    path: Option<Vec<GridPosition>>,
     step_rng: Option<Uniform<usize>>,
     size: GridSize,
     step_size: usize,
@@ -37,7 +42,15 @@ where
 
     pub fn walk(&mut self) -> bool {
         self.iters += 1;
-        let idx: usize = self.dir_rng.sample(&mut self.rng);
+        let idx: usize = if let Some(strength) = self.center_bias {
+            WeightedIndex::new(self.center_biased_weights(strength))
+                .expect("at least one direction weight should be positive")
+                .sample(&mut self.rng)
+        } else if let Some(dir_weights) = &self.dir_weights {
+            dir_weights.sample(&mut self.rng)
+        } else {
+            self.dir_rng.sample(&mut self.rng)
+        };
 
         let step_size = if let Some(step_size_rng) = self.step_rng {
             step_size_rng.sample(&mut self.rng)
@@ -59,15 +72,66 @@ where
 
         self.current_pos = current_pos;
         for walked_pos in walked.iter() {
-            self.walked.insert(*walked_pos);
+            Self::paint(&mut self.walked, &self.size, walked_pos, self.brush_radius);
+        }
+        if let Some(path) = self.path.as_mut() {
+            path.extend(walked);
         }
         true
     }
 
+    /// Marks all positions within Chebyshev `radius` of the `position` as walked.
+    fn paint(
+        walked: &mut HashSet<GridPosition>,
+        size: &GridSize,
+        position: &GridPosition,
+        radius: u32,
+    ) {
+        let (x, y) = (*position.x(), *position.y());
+        for brushed_x in x.saturating_sub(radius)..=(x + radius).min(size.x() - 1) {
+            for brushed_y in y.saturating_sub(radius)..=(y + radius).min(size.y() - 1) {
+                walked.insert(GridPosition::new_xy(brushed_x, brushed_y));
+            }
+        }
+    }
+
+    /// Direction weights with these leading toward the center multiplied according to the `strength` of the bias.
+    fn center_biased_weights(&self, strength: f32) -> [f32; 4] {
+        let (center_x, center_y) = self.size.center();
+        let max_distance = center_x.min(center_y).max(1) as f32;
+        let proximity =
+            (1. - self.size.distance_from_border(&self.current_pos) as f32 / max_distance).max(0.);
+        let to_center = (
+            center_x as i64 - *self.current_pos.x() as i64,
+            center_y as i64 - *self.current_pos.y() as i64,
+        );
+
+        let mut weights = [0.; 4];
+        for (weight, (direction, base)) in weights
+            .iter_mut()
+            .zip(GridDir::ALL_2D.iter().zip(self.direction_weights))
+        {
+            let (dx, dy, _) = direction.offset();
+            let towards_center = (dx as i64 * to_center.0) > 0 || (dy as i64 * to_center.1) > 0;
+            *weight = if towards_center {
+                base as f32 * (1. + strength * proximity)
+            } else {
+                base as f32
+            };
+        }
+        weights
+    }
+
     pub fn walked(&self) -> &HashSet<GridPosition> {
         &self.walked
     }
 
+    /// Ordered positions the walker stepped on, starting with the initial one. Positions marked only by the brush are
+    /// not included. Empty if the walker wasn't built [with path recording](GridWalker2DBuilder::with_path_recording).
+    pub fn path(&self) -> &[GridPosition] {
+        self.path.as_deref().unwrap_or_default()
+    }
+
     /// Generate [GridMap2D] out of gathered [GridPosition].
     ///
     /// # Arguments
@@ -96,6 +160,9 @@ where
     pub fn reset(&mut self) {
         self.iters = 0;
         self.walked.clear();
+        if let Some(path) = self.path.as_mut() {
+            path.clear();
+        }
     }
 }
 
@@ -108,6 +175,10 @@ where
     size: Option<GridSize>,
     min_step_size: usize,
     max_step_size: usize,
+    direction_weights: Option<[u32; 4]>,
+    center_bias: Option<f32>,
+    brush_radius: u32,
+    record_path: bool,
 }
 
 impl<R> Default for GridWalker2DBuilder<R>
@@ -121,6 +192,10 @@ where
             size: None,
             min_step_size: 1,
             max_step_size: 1,
+            direction_weights: None,
+            center_bias: None,
+            brush_radius: 0,
+            record_path: false,
         }
     }
 }
@@ -153,6 +228,40 @@ where
         self
     }
 
+    /// Set up weights for choosing the direction at every iteration, in the order of [GridDir::ALL_2D]. By default all
+    /// directions are equally probable.
+    ///
+    /// Directions with zero weight are never chosen, though at least one of the weights needs to be positive.
+    pub fn with_direction_weights(mut self, direction_weights: [u32; 4]) -> Self {
+        self.direction_weights = Some(direction_weights);
+        self
+    }
+
+    /// Bias the walker to move toward the center of the map when it gets close to the border, instead of wasting the
+    /// iterations on steps leading outside of the map.
+    ///
+    /// At every iteration the weights of the directions leading toward the center are multiplied by
+    /// `1 + strength * proximity`, where `proximity` is `1` at the border and falls linearly to `0` at the distance from
+    /// border equal to the half of the smaller map dimension. The `strength` needs to be non-negative.
+    pub fn with_center_bias(mut self, strength: f32) -> Self {
+        self.center_bias = Some(strength);
+        self
+    }
+
+    /// Set up the radius of the brush: every walked position will also mark all positions within its Chebyshev distance
+    /// of `radius` as walked, producing thicker paths. Radius `0` marks only the walked positions.
+    pub fn with_brush(mut self, radius: u32) -> Self {
+        self.brush_radius = radius;
+        self
+    }
+
+    /// If set to `true`, the walker will record the ordered positions it stepped on, available with
+    /// [GridWalker2D::path()]. Defaults to `false`.
+    pub fn with_path_recording(mut self, record_path: bool) -> Self {
+        self.record_path = record_path;
+        self
+    }
+
     /// Set up [GridSize] for walker to walk inside.
     pub fn with_size(mut self, size: GridSize) -> Self {
         self.size = Some(size);
@@ -177,20 +286,47 @@ where
             error.add_missing_field("rng");
         }
 
+        let dir_weights = match self.direction_weights {
+            Some(weights) => match WeightedIndex::new(weights) {
+                Ok(dir_weights) => Some(dir_weights),
+                Err(_) => {
+                    error.add_invalid_field(
+                        "direction_weights",
+                        "at least one weight needs to be positive",
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        if self
+            .center_bias
+            .is_some_and(|strength| !strength.is_finite() || strength < 0.)
+        {
+            error.add_invalid_field("center_bias", "strength needs to be non-negative");
+        }
+
         error.try_throw()?;
 
         let dir_rng = rand::distributions::Uniform::new(0, GridDir::ALL_2D.len());
         let step_rng = self.get_step_rng();
 
+        let size = self.size.unwrap();
         let mut walked = HashSet::new();
-        walked.insert(current_pos);
+        GridWalker2D::<R>::paint(&mut walked, &size, &current_pos, self.brush_radius);
 
         Ok(GridWalker2D {
             current_pos,
             walked,
             rng: self.rng.unwrap(),
-            size: self.size.unwrap(),
+            size,
             dir_rng,
+            dir_weights,
+            direction_weights: self.direction_weights.unwrap_or([1; 4]),
+            center_bias: self.center_bias,
+            brush_radius: self.brush_radius,
+            path: self.record_path.then(|| vec![current_pos]),
             step_rng,
             step_size: self.min_step_size,
             iters: 0,
@@ -208,3 +344,152 @@ where
         ))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::map::GridSize;
+    use crate::tile::GridPosition;
+
+    use super::GridWalker2DBuilder;
+
+    #[test]
+    fn direction_weights_exclude_zero_weighted() {
+        let start = GridPosition::new_xy(0, 5);
+        let mut walker = GridWalker2DBuilder::default()
+            .with_size(GridSize::new_xy(20, 10))
+            .with_rng(ChaChaRng::seed_from_u64(3))
+            .with_current_pos(start)
+            .with_min_step_size(2)
+            .with_max_step_size(3)
+            .with_direction_weights([0, 0, 0, 1])
+            .build()
+            .unwrap();
+
+        for _ in 0..10 {
+            walker.walk();
+        }
+
+        assert!(walker.walked().len() > 1);
+        assert!(walker.walked().iter().all(|pos| pos.y() == start.y()));
+    }
+
+    #[test]
+    fn center_bias_moves_away_from_corner() {
+        let successful = |center_bias: Option<f32>| {
+            (0..200)
+                .filter(|seed| {
+                    let mut builder = GridWalker2DBuilder::default()
+                        .with_size(GridSize::new_xy(21, 21))
+                        .with_rng(ChaChaRng::seed_from_u64(*seed))
+                        .with_current_pos(GridPosition::new_xy(0, 0))
+                        .with_min_step_size(2)
+                        .with_max_step_size(2);
+                    if let Some(strength) = center_bias {
+                        builder = builder.with_center_bias(strength);
+                    }
+                    builder.build().unwrap().walk()
+                })
+                .count()
+        };
+
+        assert!(successful(None) < 150);
+        assert!(successful(Some(100.)) > 190);
+    }
+
+    #[test]
+    fn center_bias_negative() {
+        let result = GridWalker2DBuilder::default()
+            .with_size(GridSize::new_xy(20, 10))
+            .with_rng(ChaChaRng::seed_from_u64(3))
+            .with_center_bias(-1.)
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn brush_paints_block() {
+        let mut walker = GridWalker2DBuilder::default()
+            .with_size(GridSize::new_xy(11, 11))
+            .with_rng(ChaChaRng::seed_from_u64(5))
+            .with_min_step_size(2)
+            .with_max_step_size(2)
+            .with_brush(1)
+            .build()
+            .unwrap();
+        assert_eq!(9, walker.walked().len());
+
+        assert!(walker.walk());
+        let current = walker.current_pos();
+        for x in current.x() - 1..=current.x() + 1 {
+            for y in current.y() - 1..=current.y() + 1 {
+                assert!(walker.walked().contains(&GridPosition::new_xy(x, y)));
+            }
+        }
+        assert_eq!(12, walker.walked().len());
+    }
+
+    #[test]
+    fn brush_clamped_to_grid() {
+        let walker = GridWalker2DBuilder::default()
+            .with_size(GridSize::new_xy(5, 5))
+            .with_rng(ChaChaRng::seed_from_u64(5))
+            .with_current_pos(GridPosition::new_xy(0, 0))
+            .with_brush(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(9, walker.walked().len());
+    }
+
+    #[test]
+    fn path_recording_matches_walked() {
+        let mut walker = GridWalker2DBuilder::default()
+            .with_size(GridSize::new_xy(30, 30))
+            .with_rng(ChaChaRng::seed_from_u64(11))
+            .with_min_step_size(2)
+            .with_max_step_size(4)
+            .with_path_recording(true)
+            .build()
+            .unwrap();
+
+        for _ in 0..50 {
+            walker.walk();
+        }
+
+        let path = walker.path();
+        assert_eq!(walker.current_pos(), *path.last().unwrap());
+        assert_eq!(
+            walker.walked(),
+            &path.iter().copied().collect::<HashSet<_>>()
+        );
+    }
+
+    #[test]
+    fn path_not_recorded_by_default() {
+        let mut walker = GridWalker2DBuilder::default()
+            .with_size(GridSize::new_xy(30, 30))
+            .with_rng(ChaChaRng::seed_from_u64(11))
+            .build()
+            .unwrap();
+        walker.walk();
+
+        assert!(walker.path().is_empty());
+    }
+
+    #[test]
+    fn direction_weights_all_zero() {
+        let result = GridWalker2DBuilder::default()
+            .with_size(GridSize::new_xy(20, 10))
+            .with_rng(ChaChaRng::seed_from_u64(3))
+            .with_direction_weights([0; 4])
+            .build();
+
+        assert!(result.is_err());
+    }
+}
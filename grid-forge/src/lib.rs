@@ -3,8 +3,8 @@
 //! I've found it pretty frustrating that every engine of framework have their own way of handling grid maps. This made any attempts at generic
 //! solutions for common problems not easily applicable.
 //!
-//! `grid-forge` tries to solve this problem, providing a generic abstraction for grid maps - currently only 2D rectangular grids are supported,
-//! 3D grids are planned as well, and possibly support for other shapes in the future.
+//! `grid-forge` tries to solve this problem, providing a generic abstraction for grid maps - currently 2D rectangular grids are supported,
+//! with basic support for layered 3D grids via [`GridMap3D`], and possibly support for other shapes in the future.
 //!
 //! ## Basic functionality
 //!
@@ -207,6 +207,11 @@
 //! - basic *Random Walk algorithm* - see `gen_walker` example.
 //! - collapsible tile generation (Model Synthesis/Wave function collapse) - see `gen_collapse_overlap` and `gen_collapse_singular` examples.
 //!
+//! ### Pathfinding
+//!
+//! [`pathfind`] module contains basic pathfinding algorithms working on top of the [`GridMap2D`], with the costs of
+//! moving through the tiles provided by the user.
+//!
 //! ### Godot integration
 //!
 //! `godot` module contains a collection of structs allowing for easy roundtrips between Godot's and `grid-forge` data structures, using
@@ -217,12 +222,14 @@ mod error;
 mod map;
 mod tile;
 
+pub mod pathfind;
+
 pub use error::*;
 pub use map::*;
 pub use tile::*;
 
 #[allow(clippy::non_minimal_cfg)]
-#[cfg(any(feature = "godot"))]
+#[cfg(any(feature = "godot", feature = "serde"))]
 pub(crate) mod ext;
 
 #[cfg(feature = "godot")]
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use crate::tile::identifiable::IdentifiableTileData;
+#[cfg(feature = "gen")]
+use crate::tile::GridPosition;
+
+use super::GridMap2D;
+#[cfg(feature = "gen")]
+use super::GridSize;
+
+#[cfg(feature = "gen")]
+use crate::error::AsciiError;
+#[cfg(feature = "gen")]
+use crate::gen::collapse::{CollapsedGrid, CollapsedTileData};
+
+/// Glyph written for tiles whose `tile_type_id` is not present in the mapping.
+pub const ASCII_UNMAPPED: char = '?';
+/// Glyph written for empty positions.
+pub const ASCII_EMPTY: char = ' ';
+
+/// Renders the map as a string of glyphs, one line per row from the top, using the `mapping` from `tile_type_id` to
+/// glyph. Tiles of unmapped types are rendered as [`ASCII_UNMAPPED`] and empty positions as [`ASCII_EMPTY`].
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use grid_forge::{GridMap2D, GridPosition, GridSize, write_gridmap_ascii};
+/// use grid_forge::identifiable::BasicIdentTileData;
+/// use grid_forge::identifiable::builders::ConstructableViaIdentifierTile;
+///
+/// let mut map = GridMap2D::new(GridSize::new_xy(3, 2));
+/// map.insert_data(&GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+/// map.insert_data(&GridPosition::new_xy(1, 0), BasicIdentTileData::tile_new(2));
+/// map.insert_data(&GridPosition::new_xy(2, 1), BasicIdentTileData::tile_new(1));
+///
+/// let ascii = write_gridmap_ascii(&map, &HashMap::from([(1, '#')]));
+/// assert_eq!("#? \n  #", ascii);
+/// ```
+pub fn write_gridmap_ascii<Data>(map: &GridMap2D<Data>, mapping: &HashMap<u64, char>) -> String
+where
+    Data: IdentifiableTileData,
+{
+    let mut rows = vec![String::new(); map.size().y() as usize];
+    for position in map.size().iter_all_positions() {
+        let glyph = match map.get_tile_at_position(&position) {
+            Some(tile) => *mapping
+                .get(&tile.as_ref().tile_type_id())
+                .unwrap_or(&ASCII_UNMAPPED),
+            None => ASCII_EMPTY,
+        };
+        rows[*position.y() as usize].push(glyph);
+    }
+    rows.join("\n")
+}
+
+/// Reads the map from string of glyphs, as written with [`write_gridmap_ascii`], using the `mapping` from glyph to
+/// `tile_type_id`. Positions with [`ASCII_EMPTY`] glyph are left empty.
+///
+/// Returns [`AsciiError`] if the string contains no rows, the rows have different lengths or some glyph is not
+/// mapped.
+#[cfg(feature = "gen")]
+pub fn read_gridmap_ascii(
+    input: &str,
+    mapping: &HashMap<char, u64>,
+) -> Result<CollapsedGrid, AsciiError> {
+    let rows = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let Some(width) = rows.first().map(|row| row.len()) else {
+        return Err(AsciiError::new_empty());
+    };
+
+    let mut grid = CollapsedGrid::new(GridSize::new_xy(width as u32, rows.len() as u32));
+    for (y, row) in rows.iter().enumerate() {
+        if row.len() != width {
+            return Err(AsciiError::new_ragged(y + 1, width, row.len()));
+        }
+        for (x, glyph) in row.iter().enumerate() {
+            if *glyph == ASCII_EMPTY {
+                continue;
+            }
+            let tile_type_id = mapping
+                .get(glyph)
+                .ok_or_else(|| AsciiError::new_unknown_glyph(y + 1, x + 1, *glyph))?;
+            grid.insert_data(
+                &GridPosition::new_xy(x as u32, y as u32),
+                CollapsedTileData::new(*tile_type_id),
+            );
+        }
+    }
+    Ok(grid)
+}
+
+#[cfg(all(test, feature = "gen"))]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::map::GridSize;
+    use crate::tile::identifiable::IdentifiableTileData;
+    use crate::tile::GridPosition;
+
+    use super::{read_gridmap_ascii, write_gridmap_ascii};
+
+    #[test]
+    fn ascii_roundtrip() {
+        let input = "~~#.\n~ #.\n~~~.";
+        let reverse = HashMap::from([('~', 1), ('#', 2), ('.', 3)]);
+        let grid = read_gridmap_ascii(input, &reverse).unwrap();
+
+        assert_eq!(&GridSize::new_xy(4, 3), grid.as_ref().size());
+        assert!(grid
+            .as_ref()
+            .get_tile_at_position(&GridPosition::new_xy(1, 1))
+            .is_none());
+        assert_eq!(
+            2,
+            grid.as_ref()
+                .get_tile_at_position(&GridPosition::new_xy(2, 0))
+                .unwrap()
+                .as_ref()
+                .tile_type_id()
+        );
+
+        let mapping = reverse
+            .iter()
+            .map(|(glyph, id)| (*id, *glyph))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(input, write_gridmap_ascii(grid.as_ref(), &mapping));
+
+        let err = read_gridmap_ascii("~~\n~", &reverse).unwrap_err();
+        assert_eq!(2, err.line());
+        let err = read_gridmap_ascii("~~\n~x", &reverse).unwrap_err();
+        assert!(err.to_string().contains("'x'"));
+        assert!(read_gridmap_ascii("", &reverse).is_err());
+    }
+}
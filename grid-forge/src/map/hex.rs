@@ -0,0 +1,243 @@
+use crate::tile::{GridPosition, GridTile, GridTileRef, TileData};
+
+use super::{GridMap2D, GridSize};
+
+/// Direction from tile to tile within a [`HexGridMap`].
+///
+/// Hexes are *pointy-topped*, so each of them has neighbours to the east and west, and two neighbours in each of the
+/// rows above and below.
+#[repr(u8)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexDir {
+    EAST = 0,
+    WEST = 1,
+    NORTHEAST = 2,
+    NORTHWEST = 3,
+    SOUTHEAST = 4,
+    SOUTHWEST = 5,
+}
+
+impl HexDir {
+    /// All possible directions from tile to tile within a [`HexGridMap`].
+    pub const ALL: &'static [HexDir; 6] = &[
+        HexDir::EAST,
+        HexDir::WEST,
+        HexDir::NORTHEAST,
+        HexDir::NORTHWEST,
+        HexDir::SOUTHEAST,
+        HexDir::SOUTHWEST,
+    ];
+
+    /// Take a step in specified direction from position within the contains of specified [`GridSize`].
+    ///
+    /// Positions are in *odd-r* offset coordinates - every odd row is shifted by half of the hex to the right, so
+    /// the `x` step taken while moving to another row depends on the parity of the row.
+    ///
+    /// # Returns
+    /// - resulting [`GridPosition`] after the step, or [`None`] if position is not valid within the specified size.
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::{GridPosition, GridSize, HexDir};
+    ///
+    /// let size = GridSize::new_xy(3, 3);
+    ///
+    /// assert_eq!(Some(GridPosition::new_xy(0, 1)), HexDir::SOUTHEAST.march_step(&GridPosition::new_xy(0, 0), &size));
+    /// assert_eq!(Some(GridPosition::new_xy(1, 2)), HexDir::SOUTHEAST.march_step(&GridPosition::new_xy(0, 1), &size));
+    /// assert_eq!(None, HexDir::NORTHWEST.march_step(&GridPosition::new_xy(0, 2), &size));
+    /// ```
+    pub fn march_step(&self, from: &GridPosition, size: &GridSize) -> Option<GridPosition> {
+        let (x_dif, y_dif) = self.offset(from.y().is_multiple_of(2));
+        let x = from.x().checked_add_signed(x_dif)?;
+        let y = from.y().checked_add_signed(y_dif)?;
+        if x >= size.x() || y >= size.y() {
+            return None;
+        }
+        Some(GridPosition::new_xy(x, y))
+    }
+
+    /// Get the raw `(x, y)` step vector of the direction in offset coordinates, for a row of given parity.
+    pub fn offset(&self, even_row: bool) -> (i32, i32) {
+        let shift = if even_row { 0 } else { 1 };
+        match self {
+            HexDir::EAST => (1, 0),
+            HexDir::WEST => (-1, 0),
+            HexDir::NORTHEAST => (shift, -1),
+            HexDir::NORTHWEST => (shift - 1, -1),
+            HexDir::SOUTHEAST => (shift, 1),
+            HexDir::SOUTHWEST => (shift - 1, 1),
+        }
+    }
+
+    /// Get opposite direction.
+    #[inline]
+    pub fn opposite(&self) -> Self {
+        match self {
+            HexDir::EAST => HexDir::WEST,
+            HexDir::WEST => HexDir::EAST,
+            HexDir::NORTHEAST => HexDir::SOUTHWEST,
+            HexDir::NORTHWEST => HexDir::SOUTHEAST,
+            HexDir::SOUTHEAST => HexDir::NORTHWEST,
+            HexDir::SOUTHWEST => HexDir::NORTHEAST,
+        }
+    }
+}
+
+/// Converts the offset position into the axial `(q, r)` coordinates.
+///
+/// Contrary to offset coordinates, steps in axial coordinates don't depend on the row parity, which makes them handy
+/// for calculating distances or lines.
+pub fn hex_to_axial(position: &GridPosition) -> (i32, i32) {
+    let (x, y) = (*position.x() as i32, *position.y() as i32);
+    (x - (y - (y & 1)) / 2, y)
+}
+
+/// Converts the axial `(q, r)` coordinates into the offset position. Returns [`None`] if the position would have
+/// negative coordinates.
+pub fn hex_from_axial(q: i32, r: i32) -> Option<GridPosition> {
+    let x = q + (r - (r & 1)) / 2;
+    if x < 0 || r < 0 {
+        return None;
+    }
+    Some(GridPosition::new_xy(x as u32, r as u32))
+}
+
+/// Number of steps between two positions within a [`HexGridMap`].
+pub fn hex_distance(from: &GridPosition, to: &GridPosition) -> u32 {
+    let (from_q, from_r) = hex_to_axial(from);
+    let (to_q, to_r) = hex_to_axial(to);
+    let (dq, dr) = (from_q - to_q, from_r - to_r);
+    (dq.unsigned_abs() + (dq + dr).unsigned_abs() + dr.unsigned_abs()) / 2
+}
+
+/// Two-dimensional map of pointy-topped hexes.
+///
+/// Tiles are stored in the inner [`GridMap2D`] using *odd-r* offset coordinates (see [`HexDir::march_step`]), so all
+/// its methods which don't depend on the neighbourhood of the tiles can be used via [`inner`](Self::inner).
+#[derive(Debug, Clone)]
+pub struct HexGridMap<Data: TileData> {
+    inner: GridMap2D<Data>,
+}
+
+impl<Data: TileData> HexGridMap<Data> {
+    /// Creates new, empty map of given size.
+    pub fn new(size: GridSize) -> Self {
+        Self {
+            inner: GridMap2D::new(size),
+        }
+    }
+
+    pub fn size(&self) -> &GridSize {
+        self.inner.size()
+    }
+
+    pub fn inner(&self) -> &GridMap2D<Data> {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut GridMap2D<Data> {
+        &mut self.inner
+    }
+
+    /// Get tile at specified position.
+    pub fn get_tile_at_position(&self, position: &GridPosition) -> Option<GridTileRef<'_, Data>> {
+        self.inner.get_tile_at_position(position)
+    }
+
+    /// Insert tile. If tile is present at that position already, it will be overwritten.
+    pub fn insert_tile(&mut self, tile: GridTile<Data>) -> bool {
+        self.inner.insert_tile(tile)
+    }
+
+    pub fn insert_data(&mut self, position: &GridPosition, data: Data) -> bool {
+        self.inner.insert_data(position, data)
+    }
+
+    pub fn remove_tile_at_position(&mut self, position: &GridPosition) -> bool {
+        self.inner.remove_tile_at_position(position)
+    }
+
+    /// Get up to six tiles neighbouring the specified position.
+    pub fn get_neighbours(&self, position: &GridPosition) -> Vec<GridTileRef<'_, Data>> {
+        HexDir::ALL
+            .iter()
+            .filter_map(|direction| self.get_neighbour_at(position, direction))
+            .collect::<Vec<_>>()
+    }
+
+    /// Get tile neighbouring the specified position at specified direction.
+    pub fn get_neighbour_at(
+        &self,
+        position: &GridPosition,
+        direction: &HexDir,
+    ) -> Option<GridTileRef<'_, Data>> {
+        direction
+            .march_step(position, self.inner.size())
+            .and_then(|position| self.inner.get_tile_at_position(&position))
+    }
+}
+
+impl<Data: TileData> From<GridMap2D<Data>> for HexGridMap<Data> {
+    fn from(inner: GridMap2D<Data>) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::map::GridSize;
+    use crate::tile::{GridPosition, TileContainer, TileData};
+
+    use super::{hex_distance, hex_from_axial, hex_to_axial, HexDir, HexGridMap};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct TestData;
+
+    impl TileData for TestData {}
+
+    #[test]
+    fn neighbours_follow_row_parity() {
+        let size = GridSize::new_xy(4, 4);
+        let mut map = HexGridMap::new(size);
+        for position in size.get_all_possible_positions() {
+            map.insert_data(&position, TestData);
+        }
+
+        let neighbours = |x, y| {
+            let mut positions = map
+                .get_neighbours(&GridPosition::new_xy(x, y))
+                .iter()
+                .map(|tile| tile.grid_position().xy())
+                .collect::<Vec<_>>();
+            positions.sort();
+            positions
+        };
+        assert_eq!(
+            vec![(0, 1), (1, 0), (1, 2), (2, 0), (2, 1), (2, 2)],
+            neighbours(1, 1)
+        );
+        assert_eq!(
+            vec![(0, 1), (0, 2), (0, 3), (1, 1), (1, 3), (2, 2)],
+            neighbours(1, 2)
+        );
+        assert_eq!(vec![(0, 1), (1, 0)], neighbours(0, 0));
+        assert_eq!(vec![(2, 3), (3, 2)], neighbours(3, 3));
+
+        for position in size.get_all_possible_positions() {
+            for dir in HexDir::ALL {
+                let Some(neighbour) = dir.march_step(&position, &size) else {
+                    continue;
+                };
+                assert_eq!(1, hex_distance(&position, &neighbour));
+                assert_eq!(Some(position), dir.opposite().march_step(&neighbour, &size));
+            }
+            let (q, r) = hex_to_axial(&position);
+            assert_eq!(Some(position), hex_from_axial(q, r));
+        }
+        assert_eq!(
+            3,
+            hex_distance(&GridPosition::new_xy(0, 0), &GridPosition::new_xy(2, 2))
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use grid::Grid;
+
+use crate::error::SizeError;
+use crate::tile::{GridPosition, GridTile, GridTileRef, GridTileRefMut, TileContainer, TileData};
+
+use super::{GridDir, GridMap2D, GridSize};
+
+/// Basic three-dimensional GridMap, consisting of layers of tiles stacked along the `z` axis.
+///
+/// Tiles are addressed with [`GridPosition`]s containing the `z` coordinate. Positions without it are treated as
+/// positions on the first layer. Apart from the four directions available within a layer, the neighbours can be
+/// reached also through [`GridDir::ABOVE`] and [`GridDir::BELOW`].
+///
+/// # Examples
+/// ```
+/// use grid_forge::{GridDir, GridMap3D, GridPosition, GridSize};
+/// # use grid_forge::TileData;
+/// # #[derive(Clone)]
+/// # struct Block(u8);
+/// # impl TileData for Block {}
+///
+/// let mut map = GridMap3D::new(GridSize::new_xyz(4, 4, 3));
+/// map.insert_data(&GridPosition::new_xyz(1, 1, 0), Block(1));
+/// map.insert_data(&GridPosition::new_xyz(1, 1, 1), Block(2));
+///
+/// let above = map
+///     .get_neighbour_at(&GridPosition::new_xyz(1, 1, 0), &GridDir::ABOVE)
+///     .unwrap();
+/// assert_eq!(2, above.as_ref().0);
+///
+/// let layer = map.layer(1).unwrap();
+/// assert_eq!(1, layer.get_all_positions().len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridMap3D<Data: TileData> {
+    size: GridSize,
+    layers: Vec<Grid<Option<Data>>>,
+}
+
+impl<Data: TileData> GridMap3D<Data> {
+    /// Creates new, empty map of given size. Size without `z` creates a map with a single layer.
+    pub fn new(size: GridSize) -> Self {
+        Self {
+            size,
+            layers: (0..size.layers())
+                .map(|_| Grid::new(size.x() as usize, size.y() as usize))
+                .collect(),
+        }
+    }
+
+    /// Creates new map from the provided layers, with the first one becoming the bottom layer.
+    ///
+    /// Returns [`SizeError`] if any of the layers have different size than the first one, or is three-dimensional.
+    /// If no layers are provided, the map with single empty `1x1` layer is returned.
+    pub fn from_layers(layers: Vec<GridMap2D<Data>>) -> Result<Self, SizeError> {
+        let Some(first) = layers.first() else {
+            return Ok(Self::new(GridSize::new_xy(1, 1)));
+        };
+        let expected = GridSize::new_xy(first.size().x(), first.size().y());
+        if let Some(layer) = layers.iter().find(|layer| layer.size() != &expected) {
+            return Err(SizeError::new_mismatch(expected, *layer.size()));
+        }
+        Ok(Self {
+            size: GridSize::new_xyz(expected.x(), expected.y(), layers.len() as u32),
+            layers: layers.into_iter().map(|layer| layer.tiles).collect(),
+        })
+    }
+
+    pub fn size(&self) -> &GridSize {
+        &self.size
+    }
+
+    /// Get tile at specified position.
+    pub fn get_tile_at_position(&self, position: &GridPosition) -> Option<GridTileRef<'_, Data>> {
+        if !self.size.is_position_valid(position) {
+            return None;
+        }
+        GridTileRef::maybe_new(
+            *position,
+            self.layers[position.z().unwrap_or(0) as usize]
+                .get(*position.x(), *position.y())
+                .unwrap()
+                .as_ref(),
+        )
+    }
+
+    /// Get tile at specified position mutably.
+    pub fn get_mut_tile_at_position(
+        &mut self,
+        position: &GridPosition,
+    ) -> Option<GridTileRefMut<'_, Data>> {
+        if !self.size.is_position_valid(position) {
+            return None;
+        }
+        GridTileRefMut::maybe_new(
+            *position,
+            self.layers[position.z().unwrap_or(0) as usize]
+                .get_mut(*position.x(), *position.y())
+                .unwrap()
+                .as_mut(),
+        )
+    }
+
+    /// Insert tile. Its position will be determined based on information in [GridTile::grid_position]. If tile is
+    /// present at that position already, it will be overwritten.
+    pub fn insert_tile(&mut self, tile: GridTile<Data>) -> bool {
+        let position = tile.grid_position();
+        self.insert_data(&position, tile.into_inner())
+    }
+
+    pub fn insert_data(&mut self, position: &GridPosition, data: Data) -> bool {
+        if !self.size.is_position_valid(position) {
+            return false;
+        }
+        *self.layers[position.z().unwrap_or(0) as usize]
+            .get_mut(*position.x(), *position.y())
+            .unwrap() = Some(data);
+        true
+    }
+
+    pub fn remove_tile_at_position(&mut self, position: &GridPosition) -> bool {
+        if !self.size.is_position_valid(position) {
+            return false;
+        }
+        *self.layers[position.z().unwrap_or(0) as usize]
+            .get_mut(*position.x(), *position.y())
+            .unwrap() = None;
+        true
+    }
+
+    /// Get tiles neighbouring the specified position, including the ones on the layers above and below.
+    pub fn get_neighbours(&self, position: &GridPosition) -> Vec<GridTileRef<'_, Data>> {
+        GridDir::ALL_3D
+            .iter()
+            .filter_map(|direction| self.get_neighbour_at(position, direction))
+            .collect::<Vec<_>>()
+    }
+
+    /// Get tile neighbouring the specified position at specified direction.
+    pub fn get_neighbour_at(
+        &self,
+        position: &GridPosition,
+        direction: &GridDir,
+    ) -> Option<GridTileRef<'_, Data>> {
+        direction
+            .march_step(position, &self.size)
+            .and_then(|position| self.get_tile_at_position(&position))
+    }
+
+    /// Get positions of all tiles that are occupied within the map.
+    pub fn get_all_positions(&self) -> Vec<GridPosition> {
+        self.iter_tiles().map(|tile| tile.grid_position()).collect()
+    }
+
+    pub fn iter_tiles(&self) -> impl Iterator<Item = GridTileRef<'_, Data>> {
+        self.layers.iter().enumerate().flat_map(|(z, layer)| {
+            layer.indexed_iter().filter_map(move |(pos, data)| {
+                data.as_ref().map(|d| {
+                    GridTileRef::new(
+                        GridPosition::new_xyz(pos.0 as u32, pos.1 as u32, z as u32),
+                        d,
+                    )
+                })
+            })
+        })
+    }
+}
+
+impl<Data: TileData + Clone> GridMap3D<Data> {
+    /// Returns the copy of the layer at given `z` as two-dimensional map, or [`None`] if there is no such layer.
+    pub fn layer(&self, z: u32) -> Option<GridMap2D<Data>> {
+        self.layers.get(z as usize).map(|tiles| GridMap2D {
+            size: GridSize::new_xy(self.size.x(), self.size.y()),
+            tiles: tiles.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::map::{GridDir, GridMap2D, GridSize};
+    use crate::tile::{GridPosition, TileContainer, TileData};
+
+    use super::GridMap3D;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct TestData(u32);
+
+    impl TileData for TestData {}
+
+    #[test]
+    fn neighbours_span_layers() {
+        let size = GridSize::new_xyz(3, 3, 3);
+        let mut map = GridMap3D::new(size);
+        for position in size.get_all_possible_positions() {
+            map.insert_data(&position, TestData(position.z().unwrap()));
+        }
+
+        let center = GridPosition::new_xyz(1, 1, 1);
+        assert_eq!(6, map.get_neighbours(&center).len());
+        assert_eq!(
+            &TestData(2),
+            map.get_neighbour_at(&center, &GridDir::ABOVE)
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            GridPosition::new_xyz(1, 1, 0),
+            map.get_neighbour_at(&center, &GridDir::BELOW)
+                .unwrap()
+                .grid_position()
+        );
+
+        let corner = GridPosition::new_xyz(0, 0, 2);
+        assert!(map.get_neighbour_at(&corner, &GridDir::ABOVE).is_none());
+        assert_eq!(3, map.get_neighbours(&corner).len());
+        assert!(map
+            .get_tile_at_position(&GridPosition::new_xyz(0, 0, 3))
+            .is_none());
+    }
+
+    #[test]
+    fn layers_roundtrip() {
+        let mut bottom = GridMap2D::new(GridSize::new_xy(2, 2));
+        bottom.insert_data(&GridPosition::new_xy(0, 1), TestData(1));
+        let mut top = GridMap2D::new(GridSize::new_xy(2, 2));
+        top.insert_data(&GridPosition::new_xy(1, 0), TestData(2));
+
+        let map = GridMap3D::from_layers(vec![bottom, top]).unwrap();
+        assert_eq!(&GridSize::new_xyz(2, 2, 2), map.size());
+        assert_eq!(
+            vec![
+                GridPosition::new_xyz(0, 1, 0),
+                GridPosition::new_xyz(1, 0, 1)
+            ],
+            map.get_all_positions()
+        );
+
+        let layer = map.layer(1).unwrap();
+        assert_eq!(vec![GridPosition::new_xy(1, 0)], layer.get_all_positions());
+        assert!(map.layer(2).is_none());
+
+        assert!(GridMap3D::from_layers(vec![
+            GridMap2D::<TestData>::new(GridSize::new_xy(2, 2)),
+            GridMap2D::new(GridSize::new_xy(3, 2)),
+        ])
+        .is_err());
+    }
+}
@@ -1,16 +1,45 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, Write};
 use std::ops::{Index, IndexMut};
 
 use grid::Grid;
 
+use crate::error::{CsvError, MissingTilesError, SizeError};
+use crate::tile::identifiable::builders::IdentTileBuilder;
+use crate::tile::identifiable::IdentifiableTileData;
 use crate::tile::{GridPosition, GridTile, GridTileRef, GridTileRefMut, TileContainer, TileData};
 
+mod ascii;
+#[cfg(feature = "hex")]
+mod hex;
+mod map3d;
+mod tiled;
+
+pub use ascii::*;
+#[cfg(feature = "hex")]
+pub use hex::*;
+pub use map3d::*;
+pub use tiled::*;
+
+/// Direction from tile to tile within a [`GridMap2D`] or [`GridMap3D`].
+///
+/// # Indexing
+/// Discriminants of the directions, returned by [`index`](Self::index), are the canonical indices of the directions.
+/// They start at `0` and follow the order of [`ALL_3D`](Self::ALL_3D), so for directions within a layer they are also
+/// the positions within [`ALL_2D`](Self::ALL_2D). All per-direction tables, such as [`DirectionTable`], are indexed
+/// this way.
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GridDir {
     UP = 0,
     DOWN = 1,
     LEFT = 2,
     RIGHT = 3,
+    /// Towards the layer with higher `z` coordinate. Used only within [`GridMap3D`].
+    ABOVE = 4,
+    /// Towards the layer with lower `z` coordinate. Used only within [`GridMap3D`].
+    BELOW = 5,
 }
 
 impl GridDir {
@@ -18,6 +47,56 @@ impl GridDir {
     pub const ALL_2D: &'static [GridDir; 4] =
         &[GridDir::UP, GridDir::DOWN, GridDir::LEFT, GridDir::RIGHT];
 
+    /// All possible directions from tile to tile within a [GridMap3D].
+    pub const ALL_3D: &'static [GridDir; 6] = &[
+        GridDir::UP,
+        GridDir::DOWN,
+        GridDir::LEFT,
+        GridDir::RIGHT,
+        GridDir::ABOVE,
+        GridDir::BELOW,
+    ];
+
+    /// Returns the canonical index of the direction. See [indexing](Self#indexing) section for details.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns the direction with given canonical index, or [`None`] if there is no such direction. Reverse of
+    /// [`index`](Self::index).
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::GridDir;
+    ///
+    /// assert_eq!(Some(GridDir::LEFT), GridDir::from_index(GridDir::LEFT.index()));
+    /// assert_eq!(None, GridDir::from_index(6));
+    /// ```
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL_3D.get(index).copied()
+    }
+
+    /// Returns all directions from [`ALL_2D`](Self::ALL_2D) apart from the `excluded` ones, in the same order.
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::GridDir;
+    ///
+    /// let came_from = GridDir::LEFT;
+    /// assert_eq!(
+    ///     vec![GridDir::UP, GridDir::DOWN, GridDir::RIGHT],
+    ///     GridDir::all_except(&[came_from])
+    /// );
+    /// ```
+    pub fn all_except(excluded: &[GridDir]) -> Vec<GridDir> {
+        Self::ALL_2D
+            .iter()
+            .filter(|direction| !excluded.contains(direction))
+            .copied()
+            .collect()
+    }
+
     /// Take a step in specified direction from position within the contains of specified [GridSize].
     ///
     /// # Returns
@@ -34,36 +113,26 @@ impl GridDir {
     /// assert_eq!(None, GridDir::LEFT.march_step(&position, &size));
     /// ```
     pub fn march_step(&self, from: &GridPosition, size: &GridSize) -> Option<GridPosition> {
-        let (x_dif, y_dif, z_dif) = match self {
-            GridDir::UP => {
-                if from.y() == &0 {
-                    return None;
-                }
-                (0i32, -1i32, 0i32)
-            }
-            GridDir::DOWN => {
-                if from.y() + 1 == size.y() {
-                    return None;
-                }
-                (0i32, 1i32, 0i32)
-            }
-            GridDir::LEFT => {
-                if from.x() == &0 {
-                    return None;
-                }
-                (-1i32, 0i32, 0i32)
-            }
-            GridDir::RIGHT => {
-                if from.x() + 1 == size.x() {
-                    return None;
-                }
-                (1i32, 0i32, 0i32)
-            }
+        let out_of_bounds = match self {
+            GridDir::UP => from.y() == &0,
+            GridDir::DOWN => from.y() + 1 == size.y(),
+            GridDir::LEFT => from.x() == &0,
+            GridDir::RIGHT => from.x() + 1 == size.x(),
+            GridDir::ABOVE => from.z().unwrap_or(0) + 1 >= size.layers(),
+            GridDir::BELOW => from.z().unwrap_or(0) == 0,
+        };
+        if out_of_bounds {
+            return None;
+        }
+        let (x_dif, y_dif, z_dif) = self.offset();
+        let z = if z_dif == 0 {
+            *from.z()
+        } else {
+            Some(z_dif.wrapping_add_unsigned(from.z().unwrap_or(0)) as u32)
         };
-        let (x, y, z) = (
+        let (x, y) = (
             (x_dif.wrapping_add_unsigned(*from.x())) as u32,
             (y_dif.wrapping_add_unsigned(*from.y())) as u32,
-            from.z().map(|z| z_dif.wrapping_add_unsigned(z) as u32),
         );
 
         if let Some(z) = z {
@@ -73,6 +142,26 @@ impl GridDir {
         }
     }
 
+    /// Get the raw `(x, y, z)` step vector of the direction, without any bounds checking.
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::GridDir;
+    ///
+    /// assert_eq!((0, -1, 0), GridDir::UP.offset());
+    /// assert_eq!((1, 0, 0), GridDir::RIGHT.offset());
+    /// ```
+    pub fn offset(&self) -> (i32, i32, i32) {
+        match self {
+            GridDir::UP => (0, -1, 0),
+            GridDir::DOWN => (0, 1, 0),
+            GridDir::LEFT => (-1, 0, 0),
+            GridDir::RIGHT => (1, 0, 0),
+            GridDir::ABOVE => (0, 0, 1),
+            GridDir::BELOW => (0, 0, -1),
+        }
+    }
+
     /// Get opposite direction.
     ///
     /// # Examples
@@ -88,21 +177,92 @@ impl GridDir {
             GridDir::DOWN => GridDir::UP,
             GridDir::LEFT => GridDir::RIGHT,
             GridDir::RIGHT => GridDir::LEFT,
+            GridDir::ABOVE => GridDir::BELOW,
+            GridDir::BELOW => GridDir::ABOVE,
         }
     }
 }
 
-/// Stores type in relation to each direction.
+/// Axis of the two-dimensional grid.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// Behaviour of [`GridMap2D::shift`] for tiles moved past the edge of the map.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum ShiftMode {
+    /// Tiles moved past the edge appear on the opposite side of the map.
+    Wrap,
+    /// Tiles moved past the edge are removed, leaving empty positions on the opposite side.
+    Clear,
+}
+
+/// Position at which two maps differ, alongside `tile_type_id`s of the tiles of both maps at it, as returned by
+/// [`GridMap2D::diff`].
+pub type TileTypeDiff = (GridPosition, Option<u64>, Option<u64>);
+
+/// Mutable references to the tile and its neighbours, as returned by [`GridMap2D::neighbours_mut_scatter`].
+#[derive(Debug)]
+pub struct NeighboursMut<'a, Data: TileData> {
+    /// Data of the tile at the center position.
+    pub center: &'a mut Data,
+    /// Data of the neighbouring tiles. [`None`] if the position in given direction is empty or outside of the map.
+    pub neighbours: DirectionTable<Option<&'a mut Data>>,
+}
+
+/// Stores type in relation to each direction within [GridMap2D]. Indexing it with [`GridDir::ABOVE`] or
+/// [`GridDir::BELOW`] panics - use [`get`](Self::get) if the direction can be a vertical one.
+///
+/// # Examples
+/// ```
+/// use grid_forge::{DirectionTable, GridDir};
+///
+/// let mut allowed = DirectionTable::from_fn(|direction| vec![direction.index() as u64]);
+/// allowed[GridDir::RIGHT].push(10);
+///
+/// assert_eq!(&vec![1], &allowed[GridDir::DOWN]);
+/// assert_eq!(
+///     vec![(GridDir::RIGHT, &vec![3, 10])],
+///     allowed.iter().filter(|(_, ids)| ids.len() > 1).collect::<Vec<_>>()
+/// );
+/// assert!(allowed.get(GridDir::ABOVE).is_none());
+/// ```
 #[derive(Clone, Debug)]
 pub struct DirectionTable<T> {
     table: [T; 4],
 }
 
 impl<T> DirectionTable<T> {
+    /// Creates new table from values in order of [`GridDir::ALL_2D`].
     pub const fn new_array(values: [T; 4]) -> Self {
         Self { table: values }
     }
 
+    /// Creates new table with values for each direction within [`GridDir::ALL_2D`] returned by `f`.
+    pub fn from_fn<F: Fn(GridDir) -> T>(f: F) -> Self {
+        Self {
+            table: GridDir::ALL_2D.map(f),
+        }
+    }
+
+    /// Returns the value for given `direction`, or `None` for [`GridDir::ABOVE`] and [`GridDir::BELOW`].
+    pub fn get(&self, direction: GridDir) -> Option<&T> {
+        self.table.get(direction.index())
+    }
+
+    /// Returns mutable reference to the value for given `direction`, or `None` for [`GridDir::ABOVE`] and
+    /// [`GridDir::BELOW`].
+    pub fn get_mut(&mut self, direction: GridDir) -> Option<&mut T> {
+        self.table.get_mut(direction.index())
+    }
+
+    /// Iterates over the values alongside their directions, in order of [`GridDir::ALL_2D`].
+    pub fn iter(&self) -> impl Iterator<Item = (GridDir, &T)> {
+        GridDir::ALL_2D.iter().copied().zip(self.table.iter())
+    }
+
     pub(crate) fn inner(&self) -> &[T] {
         &self.table
     }
@@ -118,7 +278,7 @@ impl<T: Default> Default for DirectionTable<T> {
 
 impl<T> IndexMut<GridDir> for DirectionTable<T> {
     fn index_mut(&mut self, index: GridDir) -> &mut Self::Output {
-        &mut self.table[index as usize]
+        &mut self.table[index.index()]
     }
 }
 
@@ -126,11 +286,11 @@ impl<T> Index<GridDir> for DirectionTable<T> {
     type Output = T;
 
     fn index(&self, index: GridDir) -> &Self::Output {
-        &self.table[index as usize]
+        &self.table[index.index()]
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct GridSize {
     x: u32,
     y: u32,
@@ -167,39 +327,87 @@ impl GridSize {
         self.y
     }
 
+    /// Returns the number of layers, if the size was created with [`new_xyz`](Self::new_xyz).
+    pub fn z(&self) -> Option<u32> {
+        self.z
+    }
+
     pub fn center(&self) -> (u32, u32) {
         self.center
     }
 
+    /// Checks if the position lies within the size. Sizes without `z` are treated as having a single layer, while
+    /// positions without `z` are treated as lying on the first one.
     pub fn is_position_valid(&self, position: &GridPosition) -> bool {
-        position.x() < &self.x && position.y() < &self.y
+        position.x() < &self.x
+            && position.y() < &self.y
+            && position.z().unwrap_or(0) < self.layers()
     }
 
-    /// Checks if each of `self` dimensions are lesser than or equal to `other`'s.
+    /// Checks if each of `self` dimensions are lesser than or equal to `other`'s, so `self` fits inside `other`.
+    /// Sizes without `z` are treated as having a single layer.
     pub fn is_contained_within(&self, other: &Self) -> bool {
-        self.x <= other.x && self.y <= other.y && self.z <= other.z
+        self.x <= other.x && self.y <= other.y && self.layers() <= other.layers()
+    }
+
+    /// Checks if `other` fits inside `self`. Inverse of [`is_contained_within`](Self::is_contained_within).
+    pub fn contains_size(&self, other: &Self) -> bool {
+        other.is_contained_within(self)
+    }
+
+    pub(crate) fn layers(&self) -> u32 {
+        self.z.unwrap_or(1)
     }
 
     pub fn get_all_possible_positions(&self) -> Vec<GridPosition> {
-        let mut out = Vec::new();
+        self.iter_all_positions().collect()
+    }
 
-        if let Some(z_size) = self.z {
-            for x in 0..self.x {
-                for y in 0..self.y {
-                    for z in 0..z_size {
-                        out.push(GridPosition::new_xyz(x, y, z));
-                    }
-                }
-            }
-        } else {
-            for x in 0..self.x {
-                for y in 0..self.y {
-                    out.push(GridPosition::new_xy(x, y));
-                }
-            }
-        }
+    /// Lazily iterates over all possible positions, in the same order as
+    /// [`get_all_possible_positions`](Self::get_all_possible_positions): `x`-major, with `z` as the innermost one.
+    pub fn iter_all_positions(&self) -> impl Iterator<Item = GridPosition> {
+        let (x_size, y_size, z_size) = (self.x, self.y, self.z);
+        (0..x_size).flat_map(move |x| {
+            (0..y_size).flat_map(move |y| {
+                (0..z_size.unwrap_or(1)).map(move |z| match z_size {
+                    Some(_) => GridPosition::new_xyz(x, y, z),
+                    None => GridPosition::new_xy(x, y),
+                })
+            })
+        })
+    }
 
-        out
+    /// Returns the number of positions within a single layer.
+    pub fn area(&self) -> u32 {
+        self.x * self.y
+    }
+
+    /// Returns the number of positions within all layers. For sizes without `z` it is equal to [`area`](Self::area).
+    pub fn volume(&self) -> u32 {
+        self.area() * self.layers()
+    }
+
+    /// Lazily iterates over the positions of the outermost ring of each layer, each one exactly once. Positions are
+    /// yielded in the same order as in [`iter_all_positions`](Self::iter_all_positions).
+    pub fn iter_border_positions(&self) -> impl Iterator<Item = GridPosition> {
+        let (x_size, y_size, z_size) = (self.x, self.y, self.z);
+        (0..x_size).flat_map(move |x| {
+            let ys = if x == 0 || x + 1 == x_size {
+                (0..y_size).collect::<Vec<_>>()
+            } else {
+                let mut ys = vec![0];
+                if y_size > 1 {
+                    ys.push(y_size - 1);
+                }
+                ys
+            };
+            ys.into_iter().flat_map(move |y| {
+                (0..z_size.unwrap_or(1)).map(move |z| match z_size {
+                    Some(_) => GridPosition::new_xyz(x, y, z),
+                    None => GridPosition::new_xy(x, y),
+                })
+            })
+        })
     }
 
     /// Get Position distance from border
@@ -255,6 +463,35 @@ impl<Data: TileData> GridMap2D<Data> {
         }
     }
 
+    /// Creates new map of given size from the flat, row-major vector of `tiles`: the tile for position `(x, y)` is
+    /// expected at index `y * size.x() + x`.
+    ///
+    /// Returns [`SizeError`] if the length of `tiles` doesn't match the number of positions in the map.
+    pub fn from_raw(size: GridSize, tiles: Vec<Option<Data>>) -> Result<Self, SizeError> {
+        let expected = size.x as usize * size.y as usize;
+        if tiles.len() != expected {
+            return Err(SizeError::new_length(size, expected, tiles.len()));
+        }
+        let mut map = Self::new(size);
+        for (idx, tile) in tiles.into_iter().enumerate() {
+            *map.tiles
+                .get_mut(idx % size.x as usize, idx / size.x as usize)
+                .unwrap() = tile;
+        }
+        Ok(map)
+    }
+
+    /// Creates new map of given size, inserting all `tiles` at their positions. Same as with
+    /// [`insert_tile`](Self::insert_tile), tiles outside of the map are skipped and later tiles overwrite the earlier
+    /// ones at the same position.
+    pub fn from_tiles(size: GridSize, tiles: impl IntoIterator<Item = GridTile<Data>>) -> Self {
+        let mut map = Self::new(size);
+        for tile in tiles {
+            map.insert_tile(tile);
+        }
+        map
+    }
+
     /// Get tile at specified position.
     pub fn get_tile_at_position(&self, position: &GridPosition) -> Option<GridTileRef<Data>> {
         if !self.size.is_position_valid(position) {
@@ -269,6 +506,7 @@ impl<Data: TileData> GridMap2D<Data> {
         )
     }
 
+    /// Get tiles at specified positions. Empty positions and positions outside of the map are skipped.
     pub fn get_tiles_at_positions(&self, positions: &[GridPosition]) -> Vec<GridTileRef<Data>> {
         positions
             .iter()
@@ -276,6 +514,27 @@ impl<Data: TileData> GridMap2D<Data> {
             .collect::<Vec<_>>()
     }
 
+    /// Get tiles at specified positions, in the same order.
+    ///
+    /// Returns [`MissingTilesError`] listing all positions that are empty or outside of the map, if there are any.
+    pub fn get_tiles_at_positions_checked(
+        &self,
+        positions: &[GridPosition],
+    ) -> Result<Vec<GridTileRef<'_, Data>>, MissingTilesError> {
+        let mut tiles = Vec::with_capacity(positions.len());
+        let mut missing = Vec::new();
+        for position in positions {
+            match self.get_tile_at_position(position) {
+                Some(tile) => tiles.push(tile),
+                None => missing.push(*position),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(MissingTilesError::new(missing));
+        }
+        Ok(tiles)
+    }
+
     /// Get tile at specified position mutably.
     pub fn get_mut_tile_at_position(
         &mut self,
@@ -293,6 +552,24 @@ impl<Data: TileData> GridMap2D<Data> {
         )
     }
 
+    /// Get tile at specified position mutably, inserting the data created by `f` first if the position is empty.
+    /// Returns [`None`] only if the position is outside of the map, in which case `f` is not called.
+    pub fn get_or_insert_with<F: FnOnce() -> Data>(
+        &mut self,
+        position: &GridPosition,
+        f: F,
+    ) -> Option<GridTileRefMut<'_, Data>> {
+        if !self.size.is_position_valid(position) {
+            return None;
+        }
+        let data = self
+            .tiles
+            .get_mut(*position.x(), *position.y())
+            .unwrap()
+            .get_or_insert_with(f);
+        Some(GridTileRefMut::new(*position, data))
+    }
+
     /// Insert tile. Its position will be determined based on information in [GridTile::grid_position]. If tile is
     /// present at that position already, it will be overwritten.
     pub fn insert_tile(&mut self, tile: GridTile<Data>) -> bool {
@@ -360,6 +637,68 @@ impl<Data: TileData> GridMap2D<Data> {
         None
     }
 
+    /// Get mutable access to the tile at specified position and its neighbours at once, eg. to swap the data between
+    /// them. Returns [`None`] if there is no tile at the position.
+    ///
+    /// All returned references point to different tiles and borrow disjoint parts of the map, so they can be used
+    /// simultaneously, contrary to the references retrieved with
+    /// [`get_mut_neighbour_at`](Self::get_mut_neighbour_at).
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::{GridDir, GridMap2D, GridPosition, GridSize};
+    /// # use grid_forge::TileData;
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct Water(u32);
+    /// # impl TileData for Water {}
+    ///
+    /// let mut map = GridMap2D::new(GridSize::new_xy(3, 3));
+    /// map.insert_data(&GridPosition::new_xy(1, 1), Water(10));
+    /// map.insert_data(&GridPosition::new_xy(1, 2), Water(0));
+    ///
+    /// let mut scatter = map.neighbours_mut_scatter(&GridPosition::new_xy(1, 1)).unwrap();
+    /// let below = scatter.neighbours[GridDir::DOWN].take().unwrap();
+    /// std::mem::swap(scatter.center, below);
+    ///
+    /// assert_eq!(&Water(10), map.get_tile_at_position(&GridPosition::new_xy(1, 2)).unwrap().as_ref());
+    /// ```
+    pub fn neighbours_mut_scatter(
+        &mut self,
+        position: &GridPosition,
+    ) -> Option<NeighboursMut<'_, Data>> {
+        if !self.size.is_position_valid(position) {
+            return None;
+        }
+        // Storage is row-major, with `x` used as the row.
+        let index = |position: &GridPosition| {
+            *position.x() as usize * self.size.y as usize + *position.y() as usize
+        };
+
+        // Slot `0` is the center, following ones are the neighbours in `GridDir::ALL_2D` order.
+        let mut requested = vec![(index(position), 0)];
+        for (slot, dir) in GridDir::ALL_2D.iter().enumerate() {
+            if let Some(neighbour) = dir.march_step(position, &self.size) {
+                requested.push((index(&neighbour), slot + 1));
+            }
+        }
+        requested.sort_unstable();
+
+        let mut slots: [Option<&mut Data>; 5] = Default::default();
+        let mut cells = self.tiles.iter_mut();
+        let mut next = 0;
+        for (index, slot) in requested {
+            let cell = cells.nth(index - next).unwrap();
+            next = index + 1;
+            slots[slot] = cell.as_mut();
+        }
+
+        let [center, up, down, left, right] = slots;
+        Some(NeighboursMut {
+            center: center?,
+            neighbours: DirectionTable::new_array([up, down, left, right]),
+        })
+    }
+
     /// Get positions of all tiles that are occupied within the GridMap
     pub fn get_all_positions(&self) -> Vec<GridPosition> {
         self.tiles
@@ -415,6 +754,21 @@ impl<Data: TileData> GridMap2D<Data> {
         })
     }
 
+    /// Iterates over all tiles alongside their neighbours, ordered the same as [`GridDir::ALL_2D`]. Neighbour is
+    /// [`None`] if the position in given direction is empty or outside of the map.
+    ///
+    /// Iteration is read-only - to compute the new state of the tiles based on their neighbours (eg. in cellular
+    /// automata), collect the changes and apply them after the iteration.
+    pub fn iter_tiles_with_neighbours(
+        &self,
+    ) -> impl Iterator<Item = (GridTileRef<'_, Data>, [Option<GridTileRef<'_, Data>>; 4])> {
+        self.iter_tiles().map(|tile| {
+            let position = tile.grid_position();
+            let neighbours = GridDir::ALL_2D.map(|dir| self.get_neighbour_at(&position, &dir));
+            (tile, neighbours)
+        })
+    }
+
     pub fn iter_mut_tiles(&mut self) -> impl Iterator<Item = GridTileRefMut<Data>> {
         self.tiles.indexed_iter_mut().filter_map(|(pos, data)| {
             data.as_mut()
@@ -422,6 +776,23 @@ impl<Data: TileData> GridMap2D<Data> {
         })
     }
 
+    /// Removes all tiles for which the predicate `f` returns `false`. Size of the map is unchanged.
+    pub fn retain<F: Fn(&GridPosition, &Data) -> bool>(&mut self, f: F) {
+        for ((x, y), tile) in self.tiles.indexed_iter_mut() {
+            let position = GridPosition::new_xy(x as u32, y as u32);
+            if tile.as_ref().is_some_and(|data| !f(&position, data)) {
+                *tile = None;
+            }
+        }
+    }
+
+    /// Removes all tiles, leaving the map empty. Size of the map is unchanged.
+    pub fn clear(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            *tile = None;
+        }
+    }
+
     /// Destroys the GridMap, returning all tiles with their position adjusted in relation to the `anchor_pos`.
     pub fn drain_remapped(mut self, anchor_pos: GridPosition) -> Vec<GridTile<Data>> {
         self.get_all_positions()
@@ -450,14 +821,342 @@ impl<Data: TileData> GridMap2D<Data> {
             .collect()
     }
 
+    /// Counts occupied positions in each row of the map. Returned vector is indexed by the `y` coordinate.
+    pub fn occupancy_per_row(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.size.y as usize];
+        for ((_, y), tile) in self.tiles.indexed_iter() {
+            if tile.is_some() {
+                counts[y] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Counts occupied positions in each column of the map. Returned vector is indexed by the `x` coordinate.
+    pub fn occupancy_per_column(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.size.x as usize];
+        for ((x, _), tile) in self.tiles.indexed_iter() {
+            if tile.is_some() {
+                counts[x] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Counts tiles for which `f` returns `true`.
+    pub fn count_where<F: Fn(&Data) -> bool>(&self, f: F) -> usize {
+        self.tiles.iter().flatten().filter(|data| f(data)).count()
+    }
+
+    /// Checks if `f` returns `true` for any tile in the map. Stops at the first matching tile.
+    pub fn any_tile<F: Fn(&Data) -> bool>(&self, f: F) -> bool {
+        self.tiles.iter().flatten().any(f)
+    }
+
+    /// Checks if `f` returns `true` for all tiles in the map. Stops at the first non-matching tile, and returns `true`
+    /// for the map without any tiles.
+    pub fn all_tiles<F: Fn(&Data) -> bool>(&self, f: F) -> bool {
+        self.tiles.iter().flatten().all(f)
+    }
+
+    /// Returns positions of the tiles belonging to the region, for which `member` returns `true`, that have at least one
+    /// neighbour in four main directions outside of the region - either empty, not belonging to it or being outside of
+    /// the map.
+    pub fn region_outline<F: Fn(&Data) -> bool>(&self, member: F) -> HashSet<GridPosition> {
+        self.iter_tiles()
+            .filter(|tile| member(tile.as_ref()))
+            .map(|tile| tile.grid_position())
+            .filter(|position| {
+                GridDir::ALL_2D.iter().any(|direction| {
+                    !self
+                        .get_neighbour_at(position, direction)
+                        .is_some_and(|neighbour| member(neighbour.as_ref()))
+                })
+            })
+            .collect()
+    }
+
+    /// Creates new map of the same size, with every tile mapped into new [`TileData`] by `f`. Empty positions are
+    /// left empty. Contrary to consuming methods, the tiles are only borrowed, so `Data` doesn't need to be [`Clone`].
+    pub fn map_tiles<NewData, F>(&self, f: F) -> GridMap2D<NewData>
+    where
+        NewData: TileData,
+        F: Fn(&GridPosition, &Data) -> NewData,
+    {
+        let mut out = GridMap2D::new(self.size);
+        for tile in self.iter_tiles() {
+            let position = tile.grid_position();
+            out.insert_data(&position, f(&position, tile.as_ref()));
+        }
+        out
+    }
+
+    /// Alias of [`map_tiles`](Self::map_tiles).
+    #[deprecated(note = "use map_tiles")]
+    #[inline]
+    pub fn project<U, F>(&self, f: F) -> GridMap2D<U>
+    where
+        U: TileData,
+        F: Fn(&GridPosition, &Data) -> U,
+    {
+        self.map_tiles(f)
+    }
+
+    /// Fallible version of [`map_tiles`](Self::map_tiles). Returns the first error returned by `f`.
+    pub fn try_map_tiles<NewData, E, F>(&self, f: F) -> Result<GridMap2D<NewData>, E>
+    where
+        NewData: TileData,
+        F: Fn(&GridPosition, &Data) -> Result<NewData, E>,
+    {
+        let mut out = GridMap2D::new(self.size);
+        for tile in self.iter_tiles() {
+            let position = tile.grid_position();
+            out.insert_data(&position, f(&position, tile.as_ref())?);
+        }
+        Ok(out)
+    }
+
+    /// Returns positions of the region connected orthogonally to the `start` position, consisting of tiles for which
+    /// `predicate` returns `true`. Empty positions are treated as walls. If there is no matching tile at `start`, the
+    /// returned region is empty.
+    pub fn flood_fill<F: Fn(&Data) -> bool>(
+        &self,
+        start: &GridPosition,
+        predicate: F,
+    ) -> HashSet<GridPosition> {
+        let mut region = HashSet::new();
+        self.flood_fill_into(start, &predicate, &mut region);
+        region
+    }
+
+    /// Partitions all tiles for which `predicate` returns `true` into the regions connected orthogonally, as with
+    /// [`flood_fill`](Self::flood_fill).
+    pub fn connected_components<F: Fn(&Data) -> bool>(
+        &self,
+        predicate: F,
+    ) -> Vec<HashSet<GridPosition>> {
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        for tile in self.iter_tiles() {
+            let position = tile.grid_position();
+            if visited.contains(&position) || !predicate(tile.as_ref()) {
+                continue;
+            }
+            let mut component = HashSet::new();
+            self.flood_fill_into(&position, &predicate, &mut component);
+            visited.extend(component.iter().copied());
+            components.push(component);
+        }
+        components
+    }
+
+    fn flood_fill_into<F: Fn(&Data) -> bool>(
+        &self,
+        start: &GridPosition,
+        predicate: &F,
+        region: &mut HashSet<GridPosition>,
+    ) {
+        if !self
+            .get_tile_at_position(start)
+            .is_some_and(|tile| predicate(tile.as_ref()))
+        {
+            return;
+        }
+        let mut stack = vec![*start];
+        region.insert(*start);
+        while let Some(position) = stack.pop() {
+            for direction in GridDir::ALL_2D {
+                let Some(neighbour) = direction.march_step(&position, &self.size) else {
+                    continue;
+                };
+                if region.contains(&neighbour)
+                    || !self
+                        .get_tile_at_position(&neighbour)
+                        .is_some_and(|tile| predicate(tile.as_ref()))
+                {
+                    continue;
+                }
+                region.insert(neighbour);
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    /// Counts the exposed faces of the region consisting of tiles for which `member` returns `true`. For every such tile,
+    /// each of the `dirs` in which the neighbour is empty, not belonging to the region or outside of the map is counted.
+    ///
+    /// With [`GridDir::ALL_2D`] it is the perimeter of the region.
+    pub fn region_surface<F: Fn(&Data) -> bool>(&self, member: F, dirs: &[GridDir]) -> usize {
+        self.iter_tiles()
+            .filter(|tile| member(tile.as_ref()))
+            .map(|tile| {
+                let position = tile.grid_position();
+                dirs.iter()
+                    .filter(|direction| {
+                        !self
+                            .get_neighbour_at(&position, direction)
+                            .is_some_and(|neighbour| member(neighbour.as_ref()))
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Lists all pairs of adjacent positions in which the first one belongs to region `a` and the second one to region
+    /// `b`, alongside the direction from the former to the latter. Adjacency is checked in four main directions with
+    /// [`GridDir::march_step`], so positions outside of the map are ignored. Regions are treated as sets of positions,
+    /// regardless of the tiles being present at them.
+    ///
+    /// Pairs are sorted by the position in `a`, and then in order of [`GridDir::ALL_2D`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use grid_forge::{GridDir, GridMap2D, GridPosition, GridSize};
+    /// # use grid_forge::TileData;
+    /// # struct Empty;
+    /// # impl TileData for Empty {}
+    ///
+    /// let map = GridMap2D::<Empty>::new(GridSize::new_xy(4, 4));
+    /// let left = HashSet::from([GridPosition::new_xy(0, 0), GridPosition::new_xy(1, 0)]);
+    /// let right = HashSet::from([GridPosition::new_xy(2, 0), GridPosition::new_xy(2, 1)]);
+    ///
+    /// assert_eq!(
+    ///     vec![(GridPosition::new_xy(1, 0), GridDir::RIGHT, GridPosition::new_xy(2, 0))],
+    ///     map.borders_between(&left, &right)
+    /// );
+    /// ```
+    pub fn borders_between(
+        &self,
+        a: &HashSet<GridPosition>,
+        b: &HashSet<GridPosition>,
+    ) -> Vec<(GridPosition, GridDir, GridPosition)> {
+        let mut from_positions = a
+            .iter()
+            .filter(|position| self.size.is_position_valid(position))
+            .collect::<Vec<_>>();
+        from_positions.sort();
+
+        from_positions
+            .into_iter()
+            .flat_map(|from| {
+                GridDir::ALL_2D.iter().filter_map(|direction| {
+                    direction
+                        .march_step(from, &self.size)
+                        .filter(|to| b.contains(to))
+                        .map(|to| (*from, *direction, to))
+                })
+            })
+            .collect()
+    }
+
+    /// Moves all tiles by `dx` columns and `dy` rows. Tiles moved past the edge of the map are handled according to the
+    /// `mode`.
+    pub fn shift(&mut self, dx: i32, dy: i32, mode: ShiftMode) {
+        let (x_len, y_len) = (self.size.x as i64, self.size.y as i64);
+        let mut shifted = Grid::new(self.size.x as usize, self.size.y as usize);
+        for ((x, y), tile) in self.tiles.indexed_iter_mut() {
+            let Some(data) = tile.take() else {
+                continue;
+            };
+            let (mut target_x, mut target_y) = (x as i64 + dx as i64, y as i64 + dy as i64);
+            match mode {
+                ShiftMode::Wrap => {
+                    target_x = target_x.rem_euclid(x_len);
+                    target_y = target_y.rem_euclid(y_len);
+                }
+                ShiftMode::Clear => {
+                    if !(0..x_len).contains(&target_x) || !(0..y_len).contains(&target_y) {
+                        continue;
+                    }
+                }
+            }
+            *shifted
+                .get_mut(target_x as usize, target_y as usize)
+                .unwrap() = Some(data);
+        }
+        self.tiles = shifted;
+    }
+
+    /// Runs `f` on the data of each tile present at one of the `positions`. Positions outside of the map or without
+    /// a tile are skipped.
+    ///
+    /// Returns the number of edited tiles.
+    pub fn apply_at<F: FnMut(GridPosition, &mut Data)>(
+        &mut self,
+        positions: &[GridPosition],
+        mut f: F,
+    ) -> usize {
+        let mut edited = 0;
+        for position in positions {
+            if !self.size.is_position_valid(position) {
+                continue;
+            }
+            if let Some(data) = self
+                .tiles
+                .get_mut(*position.x(), *position.y())
+                .unwrap()
+                .as_mut()
+            {
+                f(*position, data);
+                edited += 1;
+            }
+        }
+        edited
+    }
+
+    /// Returns the bounding box of all occupied positions as a pair of its `(top-left, bottom-right)` corners, both
+    /// inclusive. Returns [`None`] if there are no tiles in the map.
+    pub fn occupied_bounds(&self) -> Option<(GridPosition, GridPosition)> {
+        let mut bounds: Option<((usize, usize), (usize, usize))> = None;
+        for ((x, y), tile) in self.tiles.indexed_iter() {
+            if tile.is_none() {
+                continue;
+            }
+            let (min, max) = bounds.get_or_insert(((x, y), (x, y)));
+            *min = (min.0.min(x), min.1.min(y));
+            *max = (max.0.max(x), max.1.max(y));
+        }
+        bounds.map(|(min, max)| {
+            (
+                GridPosition::new_xy(min.0 as u32, min.1 as u32),
+                GridPosition::new_xy(max.0 as u32, max.1 as u32),
+            )
+        })
+    }
+
     /// Fills empty positions using constructor function.
-    pub fn fill_empty_using(&mut self, func: fn(GridPosition) -> GridTile<Data>) {
+    pub fn fill_empty_using<F>(&mut self, func: F)
+    where
+        F: Fn(GridPosition) -> GridTile<Data>,
+    {
         for position in self.get_all_empty_positions() {
             self.insert_tile(func(position));
         }
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<Data: TileData + Send> GridMap2D<Data> {
+    /// Fills empty positions using constructor function, same as [`fill_empty_using`](Self::fill_empty_using), but
+    /// the tiles are constructed in parallel before being inserted. Worth it for big maps and costly constructors.
+    pub fn fill_empty_using_par<F>(&mut self, func: F)
+    where
+        F: Fn(GridPosition) -> GridTile<Data> + Sync,
+    {
+        use rayon::prelude::*;
+
+        let tiles = self
+            .get_all_empty_positions()
+            .into_par_iter()
+            .map(&func)
+            .collect::<Vec<_>>();
+        for tile in tiles {
+            self.insert_tile(tile);
+        }
+    }
+}
+
 impl<Data: TileData + Default> GridMap2D<Data> {
     pub fn fill_empty_with_default(&mut self) {
         for pos in self.get_all_empty_positions() {
@@ -467,25 +1166,1779 @@ impl<Data: TileData + Default> GridMap2D<Data> {
 }
 
 impl<Data: TileData + Clone> GridMap2D<Data> {
+    /// Creates new map of given size, filled by repeating the `pattern` map along both axes. Positions empty in the
+    /// pattern will be empty in the created map.
+    ///
+    /// # Panics
+    /// If any of the `pattern` dimensions is `0`.
+    pub fn tiled(size: GridSize, pattern: &GridMap2D<Data>) -> Self {
+        let (pattern_x, pattern_y) = (pattern.size.x as usize, pattern.size.y as usize);
+        assert!(
+            pattern_x > 0 && pattern_y > 0,
+            "pattern map cannot be empty"
+        );
+        let mut map = Self::new(size);
+        for ((x, y), tile) in map.tiles.indexed_iter_mut() {
+            tile.clone_from(pattern.tiles.get(x % pattern_x, y % pattern_y).unwrap());
+        }
+        map
+    }
+
     pub fn fill_empty_with(&mut self, tile: Data) {
         for pos in self.get_all_empty_positions() {
             self.insert_data(&pos, tile.clone());
         }
     }
 
-    /// Get all tiles with their positions remapped according to `anchor_pos`, which is the `left-top` position.
-    pub fn get_remapped(&self, anchor_pos: GridPosition) -> Vec<GridTile<Data>> {
-        self.tiles
-            .indexed_iter()
-            .filter_map(|(pos, maybe_data)| {
-                if let Some(data) = maybe_data {
-                    let mut cloned = anchor_pos;
-                    cloned.add_xy((pos.0 as u32, pos.1 as u32));
-                    Some(GridTile::new(cloned, data.clone()))
-                } else {
-                    None
-                }
+    /// Inserts clones of `data` at every position on the line between `from` and `to` (see
+    /// [`GridPosition::line_to`]), overwriting existing tiles. Positions outside of the map are skipped.
+    ///
+    /// Returns the number of placed tiles.
+    pub fn draw_line(&mut self, from: &GridPosition, to: &GridPosition, data: Data) -> usize {
+        from.line_to(to)
+            .into_iter()
+            .filter(|position| self.insert_data(position, data.clone()))
+            .count()
+    }
+
+    /// Mirrors the tiles from one side of the center line onto the other, overwriting them. Empty positions are
+    /// mirrored as well.
+    ///
+    /// With [`Axis::X`] the left half is copied onto the right one, while with [`Axis::Y`] the top half is copied onto the
+    /// bottom one. For odd dimensions the center column or row is left untouched.
+    pub fn symmetrize(&mut self, axis: Axis) {
+        let (x_len, y_len) = (self.size.x as usize, self.size.y as usize);
+        for x in 0..x_len {
+            for y in 0..y_len {
+                let (source_x, source_y) = match axis {
+                    Axis::X if x >= x_len - x_len / 2 => (x_len - 1 - x, y),
+                    Axis::Y if y >= y_len - y_len / 2 => (x, y_len - 1 - y),
+                    _ => continue,
+                };
+                let data = self.tiles.get(source_x, source_y).unwrap().clone();
+                *self.tiles.get_mut(x, y).unwrap() = data;
+            }
+        }
+    }
+
+    /// Fills empty positions with clones of the tiles present at the same positions in the `fallback` map. Returns the
+    /// number of filled positions.
+    ///
+    /// Returns [`SizeError`] if the size of `fallback` is different than the size of this map.
+    pub fn fill_empties_from(&mut self, fallback: &GridMap2D<Data>) -> Result<usize, SizeError> {
+        if (self.size.x, self.size.y, self.size.z)
+            != (fallback.size.x, fallback.size.y, fallback.size.z)
+        {
+            return Err(SizeError::new_mismatch(self.size, fallback.size));
+        }
+        let mut filled = 0;
+        for (tile, fallback_tile) in self.tiles.iter_mut().zip(fallback.tiles.iter()) {
+            if tile.is_none() && fallback_tile.is_some() {
+                tile.clone_from(fallback_tile);
+                filled += 1;
+            }
+        }
+        Ok(filled)
+    }
+
+    /// Returns a new map cropped to the [`occupied_bounds`](Self::occupied_bounds), with positions of the tiles remapped
+    /// so the top-left corner of the bounding box becomes `(0, 0)`. If there are no tiles in the map, an empty `1x1` map
+    /// is returned.
+    pub fn trimmed(&self) -> GridMap2D<Data> {
+        let Some((min, max)) = self.occupied_bounds() else {
+            return Self::new(GridSize::new_xy(1, 1));
+        };
+        let mut trimmed = Self::new(GridSize::new_xy(
+            max.x() - min.x() + 1,
+            max.y() - min.y() + 1,
+        ));
+        for tile in self.iter_tiles() {
+            trimmed.insert_data(&(tile.grid_position() - min), tile.as_ref().clone());
+        }
+        trimmed
+    }
+
+    /// Returns a new map containing clones of the tiles at positions in `area`, sized to the bounding box of the area and
+    /// with positions remapped so its top-left corner becomes `(0, 0)`. Positions outside of the map are ignored. If
+    /// no position of the `area` is within the map, an empty `1x1` map is returned.
+    pub fn subgrid(&self, area: &[GridPosition]) -> GridMap2D<Data> {
+        let valid = area
+            .iter()
+            .filter(|position| self.size.is_position_valid(position))
+            .collect::<Vec<_>>();
+        let Some(min_x) = valid.iter().map(|position| *position.x()).min() else {
+            return Self::new(GridSize::new_xy(1, 1));
+        };
+        let min_y = valid.iter().map(|position| *position.y()).min().unwrap();
+        let max_x = valid.iter().map(|position| *position.x()).max().unwrap();
+        let max_y = valid.iter().map(|position| *position.y()).max().unwrap();
+
+        let min = GridPosition::new_xy(min_x, min_y);
+        let mut subgrid = Self::new(GridSize::new_xy(max_x - min_x + 1, max_y - min_y + 1));
+        for position in valid {
+            if let Some(tile) = self.get_tile_at_position(position) {
+                subgrid.insert_data(&(*position - min), tile.as_ref().clone());
+            }
+        }
+        subgrid
+    }
+
+    /// Inserts clones of all tiles of the `other` map, with their positions offset by `at`. Existing tiles are
+    /// overwritten, while empty positions of `other` leave the tiles of this map untouched. Tiles which would be placed
+    /// outside of this map are skipped.
+    pub fn paste(&mut self, other: &GridMap2D<Data>, at: GridPosition) {
+        for tile in other.iter_tiles() {
+            let mut position = tile.grid_position();
+            position.add_xy(at.xy());
+            self.insert_data(&position, tile.as_ref().clone());
+        }
+    }
+
+    /// Returns a new map rotated clockwise by 90 degrees. Size of the map along `x` and `y` axes is swapped.
+    pub fn rotate90(&self) -> GridMap2D<Data> {
+        let (x_len, y_len) = (self.size.x, self.size.y);
+        self.transformed(GridSize::new_xy(y_len, x_len), |x, y| (y_len - 1 - y, x))
+    }
+
+    /// Returns a new map rotated by 180 degrees.
+    pub fn rotate180(&self) -> GridMap2D<Data> {
+        let (x_len, y_len) = (self.size.x, self.size.y);
+        self.transformed(GridSize::new_xy(x_len, y_len), |x, y| {
+            (x_len - 1 - x, y_len - 1 - y)
+        })
+    }
+
+    /// Returns a new map rotated clockwise by 270 degrees. Size of the map along `x` and `y` axes is swapped.
+    pub fn rotate270(&self) -> GridMap2D<Data> {
+        let (x_len, y_len) = (self.size.x, self.size.y);
+        self.transformed(GridSize::new_xy(y_len, x_len), |x, y| (y, x_len - 1 - x))
+    }
+
+    /// Returns a new map mirrored along the `x` axis, swapping its left and right side.
+    pub fn mirror_x(&self) -> GridMap2D<Data> {
+        let (x_len, y_len) = (self.size.x, self.size.y);
+        self.transformed(GridSize::new_xy(x_len, y_len), |x, y| (x_len - 1 - x, y))
+    }
+
+    /// Returns a new map mirrored along the `y` axis, swapping its top and bottom side.
+    pub fn mirror_y(&self) -> GridMap2D<Data> {
+        let (x_len, y_len) = (self.size.x, self.size.y);
+        self.transformed(GridSize::new_xy(x_len, y_len), |x, y| (x, y_len - 1 - y))
+    }
+
+    fn transformed<F: Fn(u32, u32) -> (u32, u32)>(&self, size: GridSize, f: F) -> GridMap2D<Data> {
+        let mut out = Self::new(size);
+        for tile in self.iter_tiles() {
+            let (x, y) = f(*tile.grid_position().x(), *tile.grid_position().y());
+            out.insert_data(&GridPosition::new_xy(x, y), tile.as_ref().clone());
+        }
+        out
+    }
+
+    /// Computes the next state of the map as in cellular automata. The `rule` receives every tile together with its
+    /// eight neighbours, and returns its next state.
+    ///
+    /// Neighbours are passed row by row, starting from the top-left one: `(-1, -1)`, `(0, -1)`, `(1, -1)`, `(-1, 0)`,
+    /// `(1, 0)`, `(-1, 1)`, `(0, 1)`, `(1, 1)`. Empty positions and positions outside of the map are passed as
+    /// [`None`]. Empty positions of the map stay empty.
+    ///
+    /// The next state is written into a new map, so the rule always sees the current state of the neighbours.
+    pub fn step_automaton<F>(&self, rule: F) -> GridMap2D<Data>
+    where
+        F: Fn(&Data, &[Option<&Data>]) -> Data,
+    {
+        const OFFSETS: [(i32, i32); 8] = [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ];
+
+        let mut next = Self::new(self.size);
+        for tile in self.iter_tiles() {
+            let position = tile.grid_position();
+            let neighbours = OFFSETS.map(|(dx, dy)| {
+                let x = position.x().checked_add_signed(dx)?;
+                let y = position.y().checked_add_signed(dy)?;
+                self.tiles.get(x, y).and_then(Option::as_ref)
+            });
+            next.insert_data(&position, rule(tile.as_ref(), &neighbours));
+        }
+        next
+    }
+
+    /// Applies [`step_automaton`](Self::step_automaton) `iterations` times, returning the final state.
+    pub fn run_automaton<F>(&self, iterations: usize, rule: F) -> GridMap2D<Data>
+    where
+        F: Fn(&Data, &[Option<&Data>]) -> Data,
+    {
+        let mut current = self.clone();
+        for _ in 0..iterations {
+            current = current.step_automaton(&rule);
+        }
+        current
+    }
+
+    /// Get all tiles with their positions remapped according to `anchor_pos`, which is the `left-top` position.
+    pub fn get_remapped(&self, anchor_pos: GridPosition) -> Vec<GridTile<Data>> {
+        self.tiles
+            .indexed_iter()
+            .filter_map(|(pos, maybe_data)| {
+                if let Some(data) = maybe_data {
+                    let mut cloned = anchor_pos;
+                    cloned.add_xy((pos.0 as u32, pos.1 as u32));
+                    Some(GridTile::new(cloned, data.clone()))
+                } else {
+                    None
+                }
             })
             .collect()
     }
 }
+
+impl<Data: IdentifiableTileData> GridMap2D<Data> {
+    /// Counts tiles of each `tile_type_id` in each row of the map. Returned vector is indexed by the `y` coordinate.
+    pub fn type_counts_per_row(&self) -> Vec<HashMap<u64, usize>> {
+        let mut counts = vec![HashMap::new(); self.size.y as usize];
+        for ((_, y), tile) in self.tiles.indexed_iter() {
+            if let Some(data) = tile {
+                *counts[y].entry(data.tile_type_id()).or_default() += 1;
+            }
+        }
+        counts
+    }
+
+    /// Counts tiles of each `tile_type_id` in each column of the map. Returned vector is indexed by the `x` coordinate.
+    pub fn type_counts_per_column(&self) -> Vec<HashMap<u64, usize>> {
+        let mut counts = vec![HashMap::new(); self.size.x as usize];
+        for ((x, _), tile) in self.tiles.indexed_iter() {
+            if let Some(data) = tile {
+                *counts[x].entry(data.tile_type_id()).or_default() += 1;
+            }
+        }
+        counts
+    }
+
+    /// Lists positions at which `tile_type_id`s of the tiles in this and `other` map differ, alongside the
+    /// `tile_type_id`s of both tiles. `None` means that the position is empty in given map.
+    ///
+    /// Returns [`SizeError`] if the size of `other` is different than the size of this map.
+    pub fn diff(&self, other: &GridMap2D<Data>) -> Result<Vec<TileTypeDiff>, SizeError> {
+        if self.size != other.size {
+            return Err(SizeError::new_mismatch(self.size, other.size));
+        }
+        let tile_type_at = |map: &GridMap2D<Data>, position: &GridPosition| {
+            map.get_tile_at_position(position)
+                .map(|tile| tile.as_ref().tile_type_id())
+        };
+        Ok(self
+            .size
+            .iter_all_positions()
+            .filter_map(|position| {
+                let (own, other) = (
+                    tile_type_at(self, &position),
+                    tile_type_at(other, &position),
+                );
+                (own != other).then_some((position, own, other))
+            })
+            .collect())
+    }
+
+    /// Counts occupied tiles of each `tile_type_id` in the whole map.
+    pub fn count_by_type(&self) -> HashMap<u64, usize> {
+        let mut counts = HashMap::new();
+        for tile in self.iter_tiles() {
+            *counts.entry(tile.as_ref().tile_type_id()).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Returns the most common `tile_type_id` in the map. If multiple types are equally common, the lowest id is
+    /// returned. Returns [`None`] if the map is empty.
+    pub fn dominant_type(&self) -> Option<u64> {
+        self.count_by_type()
+            .into_iter()
+            .max_by(|(id_a, count_a), (id_b, count_b)| count_a.cmp(count_b).then(id_b.cmp(id_a)))
+            .map(|(tile_type_id, _)| tile_type_id)
+    }
+
+    /// Creates map holding, for every tile, the number of distinct `tile_type_id`s among its neighbours in four main
+    /// directions. Useful as a local complexity heatmap - the higher the value, the more chaotic the surroundings.
+    ///
+    /// Empty positions are left empty, while tiles without any neighbours hold `0`.
+    pub fn neighbour_diversity_map(&self) -> GridMap2D<u8> {
+        let mut out = GridMap2D::new(self.size);
+        for tile in self.iter_tiles() {
+            let position = tile.grid_position();
+            let distinct = self
+                .get_neighbours(&position)
+                .iter()
+                .map(|neighbour| neighbour.as_ref().tile_type_id())
+                .collect::<HashSet<_>>();
+            out.insert_data(&position, distinct.len() as u8);
+        }
+        out
+    }
+
+    /// Writes the map as CSV, with one row per `y` coordinate. Each cell contains the `tile_type_id` of the tile at given
+    /// position, or is left blank for empty positions.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        for y in 0..self.size.y as usize {
+            let row = (0..self.size.x as usize)
+                .map(|x| {
+                    self.tiles
+                        .get(x, y)
+                        .and_then(|tile| tile.as_ref())
+                        .map(|data| data.tile_type_id().to_string())
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>();
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Reads the map from CSV written by [`write_csv`](Self::write_csv), creating the tiles with provided `builder`.
+    ///
+    /// Size of the map is deduced from the number of rows and cells in them. All rows need to contain the same number
+    /// of cells, and blank cells are left empty.
+    pub fn read_csv<R, B>(reader: R, builder: &B) -> Result<Self, CsvError>
+    where
+        R: BufRead,
+        B: IdentTileBuilder<Data>,
+    {
+        let mut rows: Vec<Vec<Option<u64>>> = Vec::new();
+        for (idx, line) in reader.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.map_err(|err| CsvError::new_io(line_no, err))?;
+            let row = line
+                .split(',')
+                .enumerate()
+                .map(|(column, cell)| match cell.trim() {
+                    "" => Ok(None),
+                    trimmed => trimmed
+                        .parse::<u64>()
+                        .map(Some)
+                        .map_err(|_| CsvError::new_invalid_id(line_no, column + 1, trimmed)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first() {
+                if first.len() != row.len() {
+                    return Err(CsvError::new_ragged(line_no, first.len(), row.len()));
+                }
+            }
+            rows.push(row);
+        }
+
+        let Some(width) = rows.first().map(|row| row.len()) else {
+            return Err(CsvError::new_empty());
+        };
+
+        let mut ids = rows.iter().flatten().flatten().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        builder
+            .check_missing_ids(&ids)
+            .map_err(CsvError::new_builder)?;
+
+        let mut map = Self::new(GridSize::new_xy(width as u32, rows.len() as u32));
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, tile_type_id) in row.into_iter().enumerate() {
+                if let Some(tile_type_id) = tile_type_id {
+                    map.insert_tile(builder.build_tile_unchecked(
+                        GridPosition::new_xy(x as u32, y as u32),
+                        tile_type_id,
+                    ));
+                }
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl<Data: TileData> FromIterator<GridTile<Data>> for GridMap2D<Data> {
+    /// Collects the tiles into the smallest map starting at `(0, 0)` containing all of them. If there are no tiles, an
+    /// empty `1x1` map is returned. Use [`GridMap2D::from_tiles`] to collect into a map of specific size.
+    fn from_iter<T: IntoIterator<Item = GridTile<Data>>>(iter: T) -> Self {
+        let tiles = iter.into_iter().collect::<Vec<_>>();
+        let (max_x, max_y) = tiles
+            .iter()
+            .map(|tile| tile.grid_position().xy())
+            .fold((0, 0), |(max_x, max_y), (x, y)| {
+                (max_x.max(x), max_y.max(y))
+            });
+        Self::from_tiles(GridSize::new_xy(max_x + 1, max_y + 1), tiles)
+    }
+}
+
+impl<Data: TileData> IntoIterator for GridMap2D<Data> {
+    type Item = GridTile<Data>;
+    type IntoIter = std::vec::IntoIter<GridTile<Data>>;
+
+    /// Destroys the GridMap, iterating over all its tiles. Equivalent to [`GridMap2D::drain`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.drain().into_iter()
+    }
+}
+
+#[cfg(feature = "gen")]
+impl<Data: TileData> GridMap2D<Data> {
+    /// Samples the position of one of the occupied tiles, with probability proportional to the weight returned by
+    /// `weight` for given tile.
+    ///
+    /// Tiles with zero (or negative) or non-finite weight are never chosen. Returns `None` if there are no tiles with
+    /// positive, finite weight.
+    pub fn weighted_random_position<R, F>(&self, rng: &mut R, weight: F) -> Option<GridPosition>
+    where
+        R: rand::Rng,
+        F: Fn(&GridPosition, &Data) -> f32,
+    {
+        let weighted = self
+            .iter_tiles()
+            .filter_map(|tile| {
+                let position = tile.grid_position();
+                let tile_weight = weight(&position, tile.as_ref());
+                (tile_weight.is_finite() && tile_weight > 0.0)
+                    .then_some((position, tile_weight as f64))
+            })
+            .collect::<Vec<_>>();
+
+        if weighted.is_empty() {
+            return None;
+        }
+
+        // Summed as `f64`, so the total of finite `f32` weights cannot overflow.
+        let total = weighted.iter().map(|(_, w)| w).sum::<f64>();
+        let random = rng.gen_range(0.0..total);
+        let mut current_sum = 0.0;
+        for (position, tile_weight) in weighted.iter() {
+            current_sum += tile_weight;
+            if random < current_sum {
+                return Some(*position);
+            }
+        }
+        // Possible only due to floating point rounding.
+        weighted.last().map(|(position, _)| *position)
+    }
+}
+
+#[cfg(feature = "gen")]
+impl<Data: TileData + Clone> GridMap2D<Data> {
+    /// Fills each of the `positions` with the copy of data randomly chosen from `weights`, with probability proportional
+    /// to its weight. Tiles already present at the positions are overwritten, while positions outside of the map are
+    /// skipped. For the same state of the `rng` the outcome is always the same.
+    ///
+    /// Returns [`WeightedError`](rand::distributions::WeightedError) without modifying the map if there is no data with
+    /// positive weight.
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::{GridMap2D, GridPosition, GridSize};
+    /// # use grid_forge::TileData;
+    /// # #[derive(Clone, Debug, PartialEq)]
+    /// # enum Decoration { Flower, Rock }
+    /// # impl TileData for Decoration {}
+    ///
+    /// let mut map = GridMap2D::new(GridSize::new_xy(4, 4));
+    /// let positions = map.size().get_all_possible_positions();
+    /// map.scatter(&mut rand::thread_rng(), &positions, &[(Decoration::Flower, 3), (Decoration::Rock, 1)]).unwrap();
+    ///
+    /// assert!(map.get_all_empty_positions().is_empty());
+    /// ```
+    pub fn scatter<R: rand::Rng>(
+        &mut self,
+        rng: &mut R,
+        positions: &[GridPosition],
+        weights: &[(Data, u32)],
+    ) -> Result<(), rand::distributions::WeightedError> {
+        use rand::distributions::{Distribution, WeightedIndex};
+
+        let index = WeightedIndex::new(weights.iter().map(|(_, weight)| *weight))?;
+        for position in positions {
+            if !self.size.is_position_valid(position) {
+                continue;
+            }
+            self.insert_data(position, weights[index.sample(rng)].0.clone());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::collections::HashSet;
+
+    use crate::tile::identifiable::builders::{
+        ConstructableViaIdentifierTile, IdentTileTraitBuilder,
+    };
+    use crate::tile::identifiable::BasicIdentTileData;
+    use crate::tile::identifiable::IdentifiableTileData;
+    use crate::tile::{GridPosition, GridTile, TileContainer, TileData};
+
+    use super::{Axis, DirectionTable, GridDir, GridMap2D, GridSize, NeighboursMut, ShiftMode};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct TestData(u32);
+
+    impl TileData for TestData {}
+
+    #[test]
+    fn direction_table_from_fn() {
+        let table = DirectionTable::from_fn(|direction| direction.opposite());
+        for (direction, opposite) in table.iter() {
+            assert_eq!(direction.opposite(), *opposite);
+            assert_eq!(opposite, &table[direction]);
+        }
+        assert_eq!(
+            GridDir::ALL_2D.to_vec(),
+            table
+                .iter()
+                .map(|(direction, _)| direction)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn direction_table_get_skips_vertical() {
+        let mut table = DirectionTable::from_fn(|direction| direction.index());
+        for direction in GridDir::ALL_2D {
+            assert_eq!(Some(&direction.index()), table.get(*direction));
+        }
+        *table.get_mut(GridDir::LEFT).unwrap() = 10;
+        assert_eq!(10, table[GridDir::LEFT]);
+
+        for direction in [GridDir::ABOVE, GridDir::BELOW] {
+            assert!(table.get(direction).is_none());
+            assert!(table.get_mut(direction).is_none());
+        }
+    }
+
+    #[test]
+    fn dir_indices_are_canonical() {
+        for (index, direction) in GridDir::ALL_3D.iter().enumerate() {
+            assert_eq!(index, direction.index());
+            assert_eq!(Some(*direction), GridDir::from_index(index));
+        }
+        for (index, direction) in GridDir::ALL_2D.iter().enumerate() {
+            assert_eq!(index, direction.index());
+        }
+        assert_eq!(None, GridDir::from_index(GridDir::ALL_3D.len()));
+
+        assert_eq!(GridDir::ALL_2D.to_vec(), GridDir::all_except(&[]));
+        assert_eq!(
+            vec![GridDir::LEFT],
+            GridDir::all_except(&[GridDir::UP, GridDir::DOWN, GridDir::RIGHT, GridDir::ABOVE])
+        );
+    }
+
+    #[test]
+    fn dir_offsets() {
+        assert_eq!((0, -1, 0), GridDir::UP.offset());
+        assert_eq!((0, 1, 0), GridDir::DOWN.offset());
+        assert_eq!((-1, 0, 0), GridDir::LEFT.offset());
+        assert_eq!((1, 0, 0), GridDir::RIGHT.offset());
+
+        for dir in GridDir::ALL_2D {
+            let (x, y, z) = dir.offset();
+            let (ox, oy, oz) = dir.opposite().offset();
+            assert_eq!((0, 0, 0), (x + ox, y + oy, z + oz));
+        }
+    }
+
+    #[test]
+    fn subgrid_is_rebased_and_pasted_back() {
+        let size = GridSize::new_xy(5, 4);
+        let mut map = GridMap2D::<TestData>::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, TestData(pos.x() * 10 + pos.y()));
+        }
+        map.remove_tile_at_position(&GridPosition::new_xy(2, 2));
+
+        let area = GridPosition::generate_rect_area(
+            &GridPosition::new_xy(1, 1),
+            &GridPosition::new_xy(3, 2),
+        );
+        let subgrid = map.subgrid(&area);
+        assert_eq!(&GridSize::new_xy(3, 2), subgrid.size());
+        assert_eq!(
+            &TestData(11),
+            subgrid
+                .get_tile_at_position(&GridPosition::new_xy(0, 0))
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            &TestData(32),
+            subgrid
+                .get_tile_at_position(&GridPosition::new_xy(2, 1))
+                .unwrap()
+                .as_ref()
+        );
+        assert!(subgrid
+            .get_tile_at_position(&GridPosition::new_xy(1, 1))
+            .is_none());
+        assert_eq!(5, subgrid.get_all_positions().len());
+
+        let outside = map.subgrid(&[GridPosition::new_xy(4, 3), GridPosition::new_xy(9, 9)]);
+        assert_eq!(&GridSize::new_xy(1, 1), outside.size());
+
+        // Pasting past the border clips, empty positions in the pasted map keep the existing tiles.
+        let mut target = GridMap2D::<TestData>::new(size);
+        target.insert_data(&GridPosition::new_xy(4, 3), TestData(99));
+        target.paste(&subgrid, GridPosition::new_xy(3, 2));
+        assert_eq!(
+            &TestData(11),
+            target
+                .get_tile_at_position(&GridPosition::new_xy(3, 2))
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            &TestData(99),
+            target
+                .get_tile_at_position(&GridPosition::new_xy(4, 3))
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(4, target.get_all_positions().len());
+
+        // Overlapping paste overwrites.
+        target.paste(&outside, GridPosition::new_xy(3, 2));
+        assert_eq!(
+            &TestData(43),
+            target
+                .get_tile_at_position(&GridPosition::new_xy(3, 2))
+                .unwrap()
+                .as_ref()
+        );
+    }
+
+    #[test]
+    fn rotations_and_mirrors_are_consistent() {
+        let size = GridSize::new_xy(3, 2);
+        let mut map = GridMap2D::<TestData>::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, TestData(pos.x() * 10 + pos.y()));
+        }
+        map.remove_tile_at_position(&GridPosition::new_xy(1, 0));
+        let tiles = |map: &GridMap2D<TestData>| {
+            map.iter_tiles()
+                .map(|tile| (tile.grid_position().xy(), *tile.as_ref()))
+                .collect::<Vec<_>>()
+        };
+
+        let rotated = map.rotate90();
+        assert_eq!(&GridSize::new_xy(2, 3), rotated.size());
+        // Top-left corner lands in the top-right one.
+        assert_eq!(
+            &TestData(0),
+            rotated
+                .get_tile_at_position(&GridPosition::new_xy(1, 0))
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            &TestData(21),
+            rotated
+                .get_tile_at_position(&GridPosition::new_xy(0, 2))
+                .unwrap()
+                .as_ref()
+        );
+        assert!(rotated
+            .get_tile_at_position(&GridPosition::new_xy(1, 1))
+            .is_none());
+
+        assert_eq!(
+            tiles(&map),
+            tiles(&rotated.rotate90().rotate90().rotate90())
+        );
+        assert_eq!(tiles(&map), tiles(&map.rotate270().rotate90()));
+        assert_eq!(tiles(&map.rotate180()), tiles(&rotated.rotate90()));
+        assert_eq!(tiles(&map.rotate180()), tiles(&map.mirror_x().mirror_y()));
+        assert_eq!(tiles(&map), tiles(&map.mirror_x().mirror_x()));
+        assert_eq!(tiles(&map), tiles(&map.mirror_y().mirror_y()));
+        assert_eq!(
+            &TestData(20),
+            map.mirror_x()
+                .get_tile_at_position(&GridPosition::new_xy(0, 0))
+                .unwrap()
+                .as_ref()
+        );
+    }
+
+    #[test]
+    fn symmetrize_mirrors_halves() {
+        let size = GridSize::new_xy(5, 3);
+        let mut map = GridMap2D::<TestData>::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, TestData(pos.x() * 10 + pos.y()));
+        }
+        map.remove_tile_at_position(&GridPosition::new_xy(1, 1));
+
+        map.symmetrize(Axis::X);
+
+        for y in 0..3 {
+            for x in 0..2 {
+                let left = map
+                    .get_tile_at_position(&GridPosition::new_xy(x, y))
+                    .map(|t| *t.as_ref());
+                let right = map
+                    .get_tile_at_position(&GridPosition::new_xy(4 - x, y))
+                    .map(|t| *t.as_ref());
+                assert_eq!(left, right);
+            }
+            let center = map
+                .get_tile_at_position(&GridPosition::new_xy(2, y))
+                .unwrap();
+            assert_eq!(TestData(20 + y), *center.as_ref());
+        }
+        assert!(map
+            .get_tile_at_position(&GridPosition::new_xy(3, 1))
+            .is_none());
+    }
+
+    #[test]
+    fn tiled_repeats_pattern() {
+        let mut pattern = GridMap2D::new(GridSize::new_xy(2, 2));
+        for pos in pattern.size().get_all_possible_positions() {
+            pattern.insert_data(&pos, TestData(pos.x() * 10 + pos.y()));
+        }
+
+        let map = GridMap2D::tiled(GridSize::new_xy(5, 5), &pattern);
+
+        assert_eq!((5, 5), (map.size().x(), map.size().y()));
+        for (pos, expected) in [
+            ((0, 0), TestData(0)),
+            ((3, 2), TestData(10)),
+            ((4, 3), TestData(1)),
+            ((1, 4), TestData(10)),
+            ((3, 3), TestData(11)),
+        ] {
+            let tile = map
+                .get_tile_at_position(&GridPosition::new_xy(pos.0, pos.1))
+                .unwrap();
+            assert_eq!(expected, *tile.as_ref());
+        }
+    }
+
+    #[test]
+    fn occupancy_counts() {
+        let mut map = GridMap2D::new(GridSize::new_xy(4, 3));
+        for x in 0..4 {
+            map.insert_data(
+                &GridPosition::new_xy(x, 1),
+                BasicIdentTileData::tile_new(x as u64 % 2),
+            );
+        }
+        map.insert_data(&GridPosition::new_xy(2, 0), BasicIdentTileData::tile_new(1));
+
+        assert_eq!(vec![1, 4, 0], map.occupancy_per_row());
+        assert_eq!(vec![1, 1, 2, 1], map.occupancy_per_column());
+
+        let per_row = map.type_counts_per_row();
+        assert_eq!(Some(&2), per_row[1].get(&0));
+        assert_eq!(Some(&2), per_row[1].get(&1));
+        assert!(per_row[2].is_empty());
+
+        let per_column = map.type_counts_per_column();
+        assert_eq!(Some(&1), per_column[2].get(&0));
+        assert_eq!(Some(&1), per_column[2].get(&1));
+    }
+
+    #[test]
+    fn diff_lists_changed_positions() {
+        let size = GridSize::new_xy(3, 2);
+        let mut map = GridMap2D::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, BasicIdentTileData::tile_new(1));
+        }
+        let mut other = map.clone();
+        assert!(map.diff(&other).unwrap().is_empty());
+
+        other.insert_data(&GridPosition::new_xy(2, 0), BasicIdentTileData::tile_new(3));
+        other.remove_tile_at_position(&GridPosition::new_xy(0, 1));
+        assert_eq!(
+            vec![
+                (GridPosition::new_xy(0, 1), Some(1), None),
+                (GridPosition::new_xy(2, 0), Some(1), Some(3)),
+            ],
+            map.diff(&other).unwrap()
+        );
+
+        assert!(map.diff(&GridMap2D::new(GridSize::new_xy(2, 3))).is_err());
+    }
+
+    #[test]
+    fn neighbours_iteration_marks_borders() {
+        let size = GridSize::new_xy(3, 2);
+        let mut map = GridMap2D::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, TestData(pos.x() * 10 + pos.y()));
+        }
+        map.remove_tile_at_position(&GridPosition::new_xy(1, 1));
+
+        let all = map.iter_tiles_with_neighbours().collect::<Vec<_>>();
+        assert_eq!(5, all.len());
+        let neighbours_of = |x, y| {
+            all.iter()
+                .find(|(tile, _)| tile.grid_position() == GridPosition::new_xy(x, y))
+                .map(|(_, neighbours)| {
+                    neighbours
+                        .iter()
+                        .map(|n| n.as_ref().map(|tile| *tile.as_ref()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap()
+        };
+        // UP, DOWN, LEFT, RIGHT
+        assert_eq!(
+            vec![None, Some(TestData(1)), None, Some(TestData(10))],
+            neighbours_of(0, 0)
+        );
+        assert_eq!(
+            vec![None, None, Some(TestData(0)), Some(TestData(20))],
+            neighbours_of(1, 0)
+        );
+        assert_eq!(
+            vec![Some(TestData(20)), None, None, None],
+            neighbours_of(2, 1)
+        );
+    }
+
+    #[test]
+    fn automaton_smooths_noise() {
+        let size = GridSize::new_xy(5, 5);
+        let mut map = GridMap2D::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, TestData(1));
+        }
+        map.insert_data(&GridPosition::new_xy(2, 2), TestData(0));
+        map.remove_tile_at_position(&GridPosition::new_xy(4, 4));
+
+        // Tile becomes a wall if at least five of its neighbours are walls.
+        let rule = |_: &TestData, neighbours: &[Option<&TestData>]| {
+            let walls = neighbours
+                .iter()
+                .filter(|n| matches!(n, Some(TestData(1))))
+                .count();
+            TestData((walls >= 5) as u32)
+        };
+        let next = map.step_automaton(rule);
+        assert_eq!(
+            Some(TestData(1)),
+            next.get_tile_at_position(&GridPosition::new_xy(2, 2))
+                .map(|t| *t.as_ref())
+        );
+        // Corner has only three neighbours.
+        assert_eq!(
+            Some(TestData(0)),
+            next.get_tile_at_position(&GridPosition::new_xy(0, 0))
+                .map(|t| *t.as_ref())
+        );
+        assert!(next
+            .get_tile_at_position(&GridPosition::new_xy(4, 4))
+            .is_none());
+        // Original map is untouched.
+        assert_eq!(
+            Some(TestData(0)),
+            map.get_tile_at_position(&GridPosition::new_xy(2, 2))
+                .map(|t| *t.as_ref())
+        );
+
+        let twice = map.step_automaton(rule).step_automaton(rule);
+        let run = map.run_automaton(2, rule);
+        assert_eq!(24, run.get_all_positions().len());
+        for tile in twice.iter_tiles() {
+            assert_eq!(
+                Some(*tile.as_ref()),
+                run.get_tile_at_position(&tile.grid_position())
+                    .map(|t| *t.as_ref())
+            );
+        }
+    }
+
+    #[test]
+    fn collects_from_and_into_tiles() {
+        let tiles = || {
+            [(0, 0, 1), (3, 1, 2), (5, 5, 3)]
+                .map(|(x, y, v)| GridTile::new(GridPosition::new_xy(x, y), TestData(v)))
+        };
+
+        let sized = GridMap2D::from_tiles(GridSize::new_xy(4, 4), tiles());
+        assert_eq!(2, sized.get_all_positions().len());
+
+        let collected = tiles().into_iter().collect::<GridMap2D<_>>();
+        assert_eq!(&GridSize::new_xy(6, 6), collected.size());
+
+        let roundtrip = collected
+            .into_iter()
+            .map(|tile| (tile.grid_position().xy(), tile.into_inner().0))
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            HashSet::from([((0, 0), 1), ((3, 1), 2), ((5, 5), 3)]),
+            roundtrip
+        );
+
+        let empty = std::iter::empty::<GridTile<TestData>>().collect::<GridMap2D<_>>();
+        assert_eq!(&GridSize::new_xy(1, 1), empty.size());
+    }
+
+    #[test]
+    fn border_positions_are_unique() {
+        let size = GridSize::new_xy(4, 3);
+        assert_eq!(12, size.area());
+        assert_eq!(12, size.volume());
+        assert_eq!(36, GridSize::new_xyz(4, 3, 3).volume());
+
+        let border = size.iter_border_positions().collect::<Vec<_>>();
+        assert_eq!(10, border.len());
+        assert_eq!(border.len(), border.iter().collect::<HashSet<_>>().len());
+        for corner in [(0, 0), (3, 0), (0, 2), (3, 2)] {
+            assert_eq!(1, border.iter().filter(|pos| pos.xy() == corner).count());
+        }
+        let expected = size
+            .iter_all_positions()
+            .filter(|pos| size.distance_from_border(pos) == 0)
+            .collect::<Vec<_>>();
+        assert_eq!(expected, border);
+
+        assert_eq!(3, GridSize::new_xy(1, 3).iter_border_positions().count());
+        assert_eq!(3, GridSize::new_xy(3, 1).iter_border_positions().count());
+        assert_eq!(
+            16,
+            GridSize::new_xyz(3, 3, 2).iter_border_positions().count()
+        );
+    }
+
+    #[test]
+    fn scatter_borrows_disjointly() {
+        let size = GridSize::new_xy(3, 4);
+        let mut map = GridMap2D::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, TestData(pos.x() * 10 + pos.y()));
+        }
+        map.remove_tile_at_position(&GridPosition::new_xy(1, 0));
+
+        let scatter = map
+            .neighbours_mut_scatter(&GridPosition::new_xy(0, 0))
+            .unwrap();
+        assert_eq!(&TestData(0), scatter.center);
+        assert!(scatter.neighbours[GridDir::UP].is_none());
+        assert!(scatter.neighbours[GridDir::LEFT].is_none());
+        // Emptied position.
+        assert!(scatter.neighbours[GridDir::RIGHT].is_none());
+        assert_eq!(
+            Some(&TestData(1)),
+            scatter.neighbours[GridDir::DOWN].as_deref()
+        );
+
+        let NeighboursMut {
+            center,
+            mut neighbours,
+        } = map
+            .neighbours_mut_scatter(&GridPosition::new_xy(1, 2))
+            .unwrap();
+        for dir in GridDir::ALL_2D {
+            let neighbour = neighbours[*dir].take().unwrap();
+            center.0 += neighbour.0;
+            neighbour.0 = 0;
+        }
+        // 12 + 11 + 13 + 2 + 22
+        assert_eq!(
+            &TestData(60),
+            map.get_tile_at_position(&GridPosition::new_xy(1, 2))
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            &TestData(0),
+            map.get_tile_at_position(&GridPosition::new_xy(2, 2))
+                .unwrap()
+                .as_ref()
+        );
+
+        assert!(map
+            .neighbours_mut_scatter(&GridPosition::new_xy(1, 0))
+            .is_none());
+        assert!(map
+            .neighbours_mut_scatter(&GridPosition::new_xy(3, 0))
+            .is_none());
+    }
+
+    #[test]
+    fn get_or_insert_with_calls_once() {
+        let mut map = GridMap2D::new(GridSize::new_xy(2, 2));
+        let position = GridPosition::new_xy(1, 0);
+        let calls = Cell::new(0);
+        let make = || {
+            calls.set(calls.get() + 1);
+            TestData(5)
+        };
+
+        map.get_or_insert_with(&position, make).unwrap().as_mut().0 += 1;
+        map.get_or_insert_with(&position, make).unwrap().as_mut().0 += 1;
+        assert_eq!(1, calls.get());
+        assert_eq!(
+            &TestData(7),
+            map.get_tile_at_position(&position).unwrap().as_ref()
+        );
+
+        assert!(map
+            .get_or_insert_with(&GridPosition::new_xy(2, 0), make)
+            .is_none());
+        assert_eq!(1, calls.get());
+        assert_eq!(1, map.get_all_positions().len());
+    }
+
+    #[test]
+    fn retain_and_clear_keep_size() {
+        let size = GridSize::new_xy(4, 3);
+        let mut map = GridMap2D::new(size);
+        for pos in size.get_all_possible_positions() {
+            map.insert_data(&pos, TestData(pos.x() + pos.y()));
+        }
+        map.remove_tile_at_position(&GridPosition::new_xy(0, 0));
+
+        map.retain(|pos, data| *pos.x() < 2 && data.0 % 2 == 1);
+        assert_eq!(&size, map.size());
+        assert_eq!(
+            vec![
+                GridPosition::new_xy(0, 1),
+                GridPosition::new_xy(1, 0),
+                GridPosition::new_xy(1, 2)
+            ],
+            map.get_all_positions()
+        );
+
+        map.clear();
+        assert_eq!(&size, map.size());
+        assert!(map.get_all_positions().is_empty());
+        assert_eq!(12, map.get_all_empty_positions().len());
+    }
+
+    #[test]
+    fn counts_by_type() {
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 3));
+        assert_eq!(None, map.dominant_type());
+
+        for (x, y, id) in [(0, 0, 2), (1, 0, 5), (2, 2, 5), (0, 1, 2), (1, 1, 7)] {
+            map.insert_data(
+                &GridPosition::new_xy(x, y),
+                BasicIdentTileData::tile_new(id),
+            );
+        }
+        assert_eq!(
+            std::collections::HashMap::from([(2, 2), (5, 2), (7, 1)]),
+            map.count_by_type()
+        );
+        assert_eq!(Some(2), map.dominant_type());
+
+        map.insert_data(&GridPosition::new_xy(2, 1), BasicIdentTileData::tile_new(5));
+        assert_eq!(Some(5), map.dominant_type());
+    }
+
+    #[test]
+    fn fill_empties_from_fallback() {
+        let size = GridSize::new_xy(3, 3);
+        let mut map = GridMap2D::new(size);
+        map.insert_data(&GridPosition::new_xy(0, 0), TestData(1));
+        map.insert_data(&GridPosition::new_xy(2, 1), TestData(1));
+
+        let mut fallback = GridMap2D::new(size);
+        fallback.fill_empty_with(TestData(2));
+        fallback.remove_tile_at_position(&GridPosition::new_xy(1, 1));
+
+        assert_eq!(6, map.fill_empties_from(&fallback).unwrap());
+        for pos in size.get_all_possible_positions() {
+            let tile = map.get_tile_at_position(&pos).map(|t| *t.as_ref());
+            let expected = match (pos.x(), pos.y()) {
+                (0, 0) | (2, 1) => Some(TestData(1)),
+                (1, 1) => None,
+                _ => Some(TestData(2)),
+            };
+            assert_eq!(expected, tile);
+        }
+
+        assert!(map
+            .fill_empties_from(&GridMap2D::new(GridSize::new_xy(2, 3)))
+            .is_err());
+    }
+
+    #[test]
+    fn trimmed_to_occupied_bounds() {
+        let mut map = GridMap2D::new(GridSize::new_xy(8, 8));
+        assert!(map.occupied_bounds().is_none());
+        let trimmed = map.trimmed();
+        assert_eq!((1, 1), (trimmed.size().x(), trimmed.size().y()));
+        assert!(trimmed.get_all_positions().is_empty());
+
+        for (x, y) in [(2, 3), (2, 4), (2, 5), (3, 5), (4, 5)] {
+            map.insert_data(&GridPosition::new_xy(x, y), TestData(x * 10 + y));
+        }
+        assert_eq!(
+            Some((GridPosition::new_xy(2, 3), GridPosition::new_xy(4, 5))),
+            map.occupied_bounds()
+        );
+
+        let trimmed = map.trimmed();
+        assert_eq!((3, 3), (trimmed.size().x(), trimmed.size().y()));
+        assert_eq!(5, trimmed.get_all_positions().len());
+        assert_eq!(
+            TestData(23),
+            *trimmed
+                .get_tile_at_position(&GridPosition::new_xy(0, 0))
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            TestData(45),
+            *trimmed
+                .get_tile_at_position(&GridPosition::new_xy(2, 2))
+                .unwrap()
+                .as_ref()
+        );
+        assert!(trimmed
+            .get_tile_at_position(&GridPosition::new_xy(2, 0))
+            .is_none());
+    }
+
+    #[test]
+    fn tile_predicates() {
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 3));
+        assert!(map.all_tiles(|_| false));
+        assert!(!map.any_tile(|_| true));
+
+        for x in 0..3 {
+            map.insert_data(&GridPosition::new_xy(x, 0), TestData(x));
+            map.insert_data(&GridPosition::new_xy(x, 2), TestData(x + 3));
+        }
+        assert_eq!(3, map.count_where(|data| data.0 % 2 == 0));
+
+        let checked = Cell::new(0);
+        assert!(map.any_tile(|data| {
+            checked.set(checked.get() + 1);
+            data.0 == 0
+        }));
+        assert_eq!(1, checked.get());
+
+        checked.set(0);
+        assert!(!map.all_tiles(|data| {
+            checked.set(checked.get() + 1);
+            data.0 != 0
+        }));
+        assert_eq!(1, checked.get());
+    }
+
+    #[test]
+    fn draw_line_clamped() {
+        let mut map = GridMap2D::new(GridSize::new_xy(4, 4));
+
+        let placed = map.draw_line(
+            &GridPosition::new_xy(1, 0),
+            &GridPosition::new_xy(6, 5),
+            TestData(1),
+        );
+        assert_eq!(3, placed);
+        assert_eq!(
+            vec![
+                GridPosition::new_xy(1, 0),
+                GridPosition::new_xy(2, 1),
+                GridPosition::new_xy(3, 2)
+            ],
+            map.get_all_positions()
+        );
+    }
+
+    #[cfg(feature = "gen")]
+    #[test]
+    fn weighted_random_position() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaChaRng;
+
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 3));
+        for position in map.size().get_all_possible_positions() {
+            map.insert_data(&position, TestData(1));
+        }
+        map.insert_data(&GridPosition::new_xy(2, 1), TestData(100));
+        map.insert_data(&GridPosition::new_xy(0, 0), TestData(0));
+
+        let mut rng = ChaChaRng::seed_from_u64(0);
+        let mut dominant = 0;
+        for _ in 0..1000 {
+            let position = map
+                .weighted_random_position(&mut rng, |_, data| data.0 as f32)
+                .unwrap();
+            assert_ne!(GridPosition::new_xy(0, 0), position);
+            if position == GridPosition::new_xy(2, 1) {
+                dominant += 1;
+            }
+        }
+        assert!(dominant > 850, "dominant tile chosen {dominant} times");
+
+        assert_eq!(None, map.weighted_random_position(&mut rng, |_, _| 0.0));
+        assert_eq!(
+            None,
+            map.weighted_random_position(&mut rng, |_, _| f32::NAN)
+        );
+    }
+
+    #[cfg(feature = "gen")]
+    #[test]
+    fn weighted_random_position_extreme_weights() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaChaRng;
+
+        let mut map = GridMap2D::new(GridSize::new_xy(2, 2));
+        for position in map.size().get_all_possible_positions() {
+            map.insert_data(&position, TestData(position.x() + 2 * position.y()));
+        }
+
+        let mut rng = ChaChaRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let position = map
+                .weighted_random_position(&mut rng, |_, data| match data.0 {
+                    0 => f32::INFINITY,
+                    _ => 1.0,
+                })
+                .unwrap();
+            assert_ne!(GridPosition::new_xy(0, 0), position);
+
+            let position = map
+                .weighted_random_position(&mut rng, |_, data| match data.0 {
+                    0 | 1 => f32::MAX,
+                    _ => 0.0,
+                })
+                .unwrap();
+            assert_eq!(0, *position.y());
+        }
+    }
+
+    #[cfg(feature = "gen")]
+    #[test]
+    fn scatter_is_weighted_and_deterministic() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaChaRng;
+
+        let size = GridSize::new_xy(20, 20);
+        let mut positions = size.get_all_possible_positions();
+        positions.push(GridPosition::new_xy(20, 0));
+        let weights = [(TestData(1), 9), (TestData(2), 1), (TestData(3), 0)];
+
+        let scattered = |seed| {
+            let mut map = GridMap2D::new(size);
+            map.scatter(&mut ChaChaRng::seed_from_u64(seed), &positions, &weights)
+                .unwrap();
+            map
+        };
+        let map = scattered(3);
+        assert!(map.get_all_empty_positions().is_empty());
+        assert_eq!(0, map.count_where(|data| data.0 == 3));
+        let dominant = map.count_where(|data| data.0 == 1);
+        assert!(dominant > 320, "dominant data placed {dominant} times");
+
+        let again = scattered(3);
+        assert!(map.iter_tiles().all(|tile| again
+            .get_tile_at_position(&tile.grid_position())
+            .unwrap()
+            .as_ref()
+            == tile.as_ref()));
+    }
+
+    #[cfg(feature = "gen")]
+    #[test]
+    fn scatter_without_positive_weight() {
+        let size = GridSize::new_xy(2, 2);
+        let positions = size.get_all_possible_positions();
+        let mut map = GridMap2D::new(size);
+
+        assert_eq!(
+            Err(rand::distributions::WeightedError::AllWeightsZero),
+            map.scatter(&mut rand::thread_rng(), &positions, &[(TestData(1), 0)])
+        );
+        assert_eq!(
+            Err(rand::distributions::WeightedError::NoItem),
+            map.scatter(&mut rand::thread_rng(), &positions, &[])
+        );
+        assert!(map.get_all_positions().is_empty());
+    }
+
+    #[test]
+    fn csv_roundtrip() {
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 2));
+        map.insert_data(&GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(1));
+        map.insert_data(
+            &GridPosition::new_xy(2, 0),
+            BasicIdentTileData::tile_new(20),
+        );
+        map.insert_data(&GridPosition::new_xy(1, 1), BasicIdentTileData::tile_new(3));
+
+        let mut buffer = Vec::new();
+        map.write_csv(&mut buffer).unwrap();
+        assert_eq!("1,,20\n,3,\n", String::from_utf8(buffer.clone()).unwrap());
+
+        let builder = IdentTileTraitBuilder::<BasicIdentTileData>::default();
+        let read = GridMap2D::read_csv(buffer.as_slice(), &builder).unwrap();
+        assert_eq!((3, 2), (read.size().x(), read.size().y()));
+        for position in map.size().get_all_possible_positions() {
+            assert_eq!(
+                map.get_tile_at_position(&position)
+                    .map(|tile| tile.as_ref().tile_type_id()),
+                read.get_tile_at_position(&position)
+                    .map(|tile| tile.as_ref().tile_type_id())
+            );
+        }
+
+        let err = GridMap2D::read_csv("1,2\n3,4\n5\n".as_bytes(), &builder).unwrap_err();
+        assert_eq!(3, err.line());
+        let err = GridMap2D::read_csv("1,2\n3,x\n".as_bytes(), &builder).unwrap_err();
+        assert_eq!(2, err.line());
+    }
+
+    #[test]
+    fn region_outline_rectangle() {
+        let mut map = GridMap2D::new(GridSize::new_xy(6, 6));
+        for position in map.size().get_all_possible_positions() {
+            map.insert_data(&position, TestData(0));
+        }
+        for position in GridPosition::generate_rect_area(
+            &GridPosition::new_xy(1, 1),
+            &GridPosition::new_xy(4, 3),
+        ) {
+            map.insert_data(&position, TestData(1));
+        }
+
+        let outline = map.region_outline(|data| data.0 == 1);
+        assert_eq!(10, outline.len());
+        for position in [GridPosition::new_xy(2, 2), GridPosition::new_xy(3, 2)] {
+            assert!(!outline.contains(&position));
+        }
+        for position in [
+            GridPosition::new_xy(1, 1),
+            GridPosition::new_xy(4, 1),
+            GridPosition::new_xy(1, 2),
+            GridPosition::new_xy(4, 3),
+            GridPosition::new_xy(2, 3),
+        ] {
+            assert!(outline.contains(&position));
+        }
+        assert!(outline.iter().all(|position| map
+            .get_tile_at_position(position)
+            .unwrap()
+            .as_ref()
+            .0
+            == 1));
+    }
+
+    #[test]
+    fn map_tiles_identifiable_to_ids() {
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 2));
+        map.insert_data(&GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(4));
+        map.insert_data(&GridPosition::new_xy(2, 1), BasicIdentTileData::tile_new(7));
+
+        let mapped = map.map_tiles(|_, data| TestData(data.tile_type_id() as u32));
+        assert_eq!((3, 2), (mapped.size().x(), mapped.size().y()));
+        assert_eq!(map.get_all_positions(), mapped.get_all_positions());
+        for tile in map.iter_tiles() {
+            assert_eq!(
+                TestData(tile.as_ref().tile_type_id() as u32),
+                *mapped
+                    .get_tile_at_position(&tile.grid_position())
+                    .unwrap()
+                    .as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn map_tiles_keeps_empty_positions() {
+        let mut map = numbered_map();
+        map.remove_tile_at_position(&GridPosition::new_xy(1, 0));
+
+        let mapped = map.map_tiles(|position, data| TestData(data.0 * 10 + position.y()));
+
+        assert_eq!(map.get_all_positions(), mapped.get_all_positions());
+        assert_eq!(Some(0), data_at(&mapped, 0, 0));
+        assert_eq!(None, data_at(&mapped, 1, 0));
+        assert_eq!(Some(51), data_at(&mapped, 2, 1));
+    }
+
+    #[test]
+    fn try_map_tiles_propagates_error() {
+        let map = numbered_map();
+
+        let ok = map.try_map_tiles(|_, data| Ok::<_, u32>(TestData(data.0 + 1)));
+        assert_eq!(Some(6), data_at(&ok.unwrap(), 2, 1));
+
+        let err = map.try_map_tiles(|_, data| {
+            if data.0 >= 4 {
+                Err(data.0)
+            } else {
+                Ok(TestData(data.0))
+            }
+        });
+        assert!(err.is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn fill_empty_using_par_matches_serial() {
+        let offset = 7;
+        let mut serial = GridMap2D::new(GridSize::new_xy(20, 15));
+        serial.insert_data(&GridPosition::new_xy(3, 4), TestData(0));
+        let mut parallel = serial.clone();
+
+        let constructor = |position: GridPosition| {
+            crate::tile::GridTile::new(position, TestData(position.x() * offset + position.y()))
+        };
+        serial.fill_empty_using(constructor);
+        parallel.fill_empty_using_par(constructor);
+
+        for position in serial.size().get_all_possible_positions() {
+            assert_eq!(
+                data_at(&serial, *position.x(), *position.y()),
+                data_at(&parallel, *position.x(), *position.y())
+            );
+        }
+        assert_eq!(Some(0), data_at(&parallel, 3, 4));
+    }
+
+    #[test]
+    fn flood_fill_stops_at_walls() {
+        // 0 0 1 1
+        // 0 . 1 0
+        // 1 1 1 0
+        let mut map = GridMap2D::new(GridSize::new_xy(4, 3));
+        for (x, y, value) in [
+            (0, 0, 0),
+            (1, 0, 0),
+            (2, 0, 1),
+            (3, 0, 1),
+            (0, 1, 0),
+            (2, 1, 1),
+            (3, 1, 0),
+            (0, 2, 1),
+            (1, 2, 1),
+            (2, 2, 1),
+            (3, 2, 0),
+        ] {
+            map.insert_data(&GridPosition::new_xy(x, y), TestData(value));
+        }
+
+        let zeros = map.flood_fill(&GridPosition::new_xy(0, 0), |data| data.0 == 0);
+        assert_eq!(
+            HashSet::from([
+                GridPosition::new_xy(0, 0),
+                GridPosition::new_xy(1, 0),
+                GridPosition::new_xy(0, 1)
+            ]),
+            zeros
+        );
+        assert!(map
+            .flood_fill(&GridPosition::new_xy(1, 1), |_| true)
+            .is_empty());
+        assert_eq!(
+            11,
+            map.flood_fill(&GridPosition::new_xy(3, 2), |_| true).len()
+        );
+
+        let mut components = map.connected_components(|data| data.0 == 0);
+        components.sort_by_key(|component| component.len());
+        assert_eq!(
+            vec![2, 3],
+            components.iter().map(|c| c.len()).collect::<Vec<_>>()
+        );
+        assert_eq!(1, map.connected_components(|data| data.0 == 1).len());
+    }
+
+    #[test]
+    fn borders_between_regions() {
+        let map = GridMap2D::<TestData>::new(GridSize::new_xy(4, 3));
+        let left = GridPosition::generate_rect_area(
+            &GridPosition::new_xy(0, 0),
+            &GridPosition::new_xy(1, 2),
+        )
+        .into_iter()
+        .collect::<HashSet<_>>();
+        let mut right = GridPosition::generate_rect_area(
+            &GridPosition::new_xy(2, 1),
+            &GridPosition::new_xy(3, 2),
+        )
+        .into_iter()
+        .collect::<HashSet<_>>();
+        // Position outside of the map is never adjacent.
+        right.insert(GridPosition::new_xy(4, 0));
+
+        let borders = map.borders_between(&left, &right);
+        assert_eq!(
+            vec![
+                (
+                    GridPosition::new_xy(1, 1),
+                    GridDir::RIGHT,
+                    GridPosition::new_xy(2, 1)
+                ),
+                (
+                    GridPosition::new_xy(1, 2),
+                    GridDir::RIGHT,
+                    GridPosition::new_xy(2, 2)
+                ),
+            ],
+            borders
+        );
+
+        let reverse = map.borders_between(&right, &left);
+        assert_eq!(borders.len(), reverse.len());
+        for (from, direction, to) in reverse {
+            assert_eq!(GridDir::LEFT, direction);
+            assert!(borders.contains(&(to, direction.opposite(), from)));
+        }
+
+        let top = HashSet::from([GridPosition::new_xy(2, 0), GridPosition::new_xy(3, 0)]);
+        assert_eq!(2, map.borders_between(&top, &right).len());
+        assert_eq!(2, map.borders_between(&top, &top).len());
+        assert!(map.borders_between(&left, &HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn region_surface_block() {
+        let mut map = GridMap2D::new(GridSize::new_xy(4, 4));
+        for position in map.size().get_all_possible_positions() {
+            map.insert_data(&position, TestData(0));
+        }
+        for position in GridPosition::generate_rect_area(
+            &GridPosition::new_xy(1, 1),
+            &GridPosition::new_xy(2, 2),
+        ) {
+            map.insert_data(&position, TestData(1));
+        }
+
+        assert_eq!(8, map.region_surface(|data| data.0 == 1, GridDir::ALL_2D));
+        assert_eq!(
+            4,
+            map.region_surface(|data| data.0 == 1, &[GridDir::UP, GridDir::DOWN])
+        );
+
+        // Outer ring is exposed both to the map border and to the empty corner, as well as to the inner block.
+        map.remove_tile_at_position(&GridPosition::new_xy(0, 0));
+        assert_eq!(
+            16 + 8,
+            map.region_surface(|data| data.0 == 0, GridDir::ALL_2D)
+        );
+    }
+
+    fn numbered_map() -> GridMap2D<TestData> {
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 2));
+        for position in map.size().get_all_possible_positions() {
+            map.insert_data(&position, TestData(position.x() + position.y() * 3));
+        }
+        map
+    }
+
+    fn data_at(map: &GridMap2D<TestData>, x: u32, y: u32) -> Option<u32> {
+        map.get_tile_at_position(&GridPosition::new_xy(x, y))
+            .map(|tile| tile.as_ref().0)
+    }
+
+    #[test]
+    fn apply_at_edits_only_given_positions() {
+        let mut map = numbered_map();
+        map.remove_tile_at_position(&GridPosition::new_xy(1, 1));
+        let positions = [
+            GridPosition::new_xy(0, 0),
+            GridPosition::new_xy(2, 0),
+            GridPosition::new_xy(1, 1),
+            GridPosition::new_xy(10, 10),
+        ];
+
+        let edited = map.apply_at(&positions, |_, data| data.0 += 100);
+
+        assert_eq!(2, edited);
+        assert_eq!(Some(100), data_at(&map, 0, 0));
+        assert_eq!(Some(1), data_at(&map, 1, 0));
+        assert_eq!(Some(102), data_at(&map, 2, 0));
+        assert_eq!(Some(3), data_at(&map, 0, 1));
+        assert_eq!(None, data_at(&map, 1, 1));
+        assert_eq!(Some(5), data_at(&map, 2, 1));
+    }
+
+    #[test]
+    fn from_raw_row_major() {
+        let tiles = (0..6).map(|n| (n != 4).then_some(TestData(n))).collect();
+        let map = GridMap2D::from_raw(GridSize::new_xy(3, 2), tiles).unwrap();
+
+        assert_eq!(Some(0), data_at(&map, 0, 0));
+        assert_eq!(Some(2), data_at(&map, 2, 0));
+        assert_eq!(Some(3), data_at(&map, 0, 1));
+        assert_eq!(None, data_at(&map, 1, 1));
+        assert_eq!(Some(5), data_at(&map, 2, 1));
+    }
+
+    #[test]
+    fn from_raw_length_mismatch() {
+        let tiles = (0..5).map(|n| Some(TestData(n))).collect();
+
+        assert!(GridMap2D::from_raw(GridSize::new_xy(3, 2), tiles).is_err());
+    }
+
+    #[test]
+    fn grid_size_equality() {
+        let mut sizes = HashSet::new();
+        sizes.insert(GridSize::new_xy(4, 3));
+
+        assert_eq!(GridSize::new_xy(4, 3), GridSize::new_xy(4, 3));
+        assert!(sizes.contains(&GridSize::new_xy(4, 3)));
+        assert_ne!(GridSize::new_xy(4, 3), GridSize::new_xy(3, 4));
+        assert_ne!(GridSize::new_xy(4, 3), GridSize::new_xyz(4, 3, 1));
+    }
+
+    #[test]
+    fn iter_all_positions_order() {
+        let size = GridSize::new_xyz(2, 2, 2);
+        let positions = size.iter_all_positions().collect::<Vec<_>>();
+
+        assert_eq!(8, positions.len());
+        assert_eq!(GridPosition::new_xyz(0, 0, 0), positions[0]);
+        assert_eq!(GridPosition::new_xyz(0, 0, 1), positions[1]);
+        assert_eq!(GridPosition::new_xyz(0, 1, 0), positions[2]);
+        assert_eq!(GridPosition::new_xyz(1, 1, 1), positions[7]);
+
+        let size = GridSize::new_xy(3, 2);
+        assert_eq!(
+            vec![
+                GridPosition::new_xy(0, 0),
+                GridPosition::new_xy(0, 1),
+                GridPosition::new_xy(1, 0),
+                GridPosition::new_xy(1, 1),
+                GridPosition::new_xy(2, 0),
+                GridPosition::new_xy(2, 1),
+            ],
+            size.iter_all_positions().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn grid_size_containment() {
+        let small = GridSize::new_xy(3, 4);
+        let big = GridSize::new_xy(5, 5);
+        assert!(small.is_contained_within(&big));
+        assert!(big.contains_size(&small));
+        assert!(!big.is_contained_within(&small));
+        assert!(!GridSize::new_xy(6, 2).is_contained_within(&big));
+
+        let layered = GridSize::new_xyz(5, 5, 3);
+        assert!(small.is_contained_within(&layered));
+        assert!(!layered.is_contained_within(&big));
+        assert!(GridSize::new_xyz(5, 5, 1).is_contained_within(&big));
+        assert!(!GridSize::new_xyz(5, 5, 4).is_contained_within(&layered));
+    }
+
+    #[test]
+    fn position_validity_checks_z() {
+        let flat = GridSize::new_xy(3, 3);
+        let layered = GridSize::new_xyz(3, 3, 2);
+
+        assert!(flat.is_position_valid(&GridPosition::new_xy(2, 2)));
+        assert!(flat.is_position_valid(&GridPosition::new_xyz(2, 2, 0)));
+        assert!(!flat.is_position_valid(&GridPosition::new_xyz(2, 2, 1)));
+        assert!(!flat.is_position_valid(&GridPosition::new_xy(3, 0)));
+        assert!(layered.is_position_valid(&GridPosition::new_xyz(0, 0, 1)));
+        assert!(!layered.is_position_valid(&GridPosition::new_xyz(0, 0, 2)));
+    }
+
+    #[test]
+    fn grid_size_z() {
+        assert_eq!(None, GridSize::new_xy(4, 3).z());
+        assert_eq!(Some(2), GridSize::new_xyz(4, 3, 2).z());
+    }
+
+    #[test]
+    fn get_tiles_at_positions_checked() {
+        let mut map = numbered_map();
+        map.remove_tile_at_position(&GridPosition::new_xy(1, 0));
+        let present = [GridPosition::new_xy(2, 1), GridPosition::new_xy(0, 0)];
+
+        let tiles = map.get_tiles_at_positions_checked(&present).unwrap();
+        assert_eq!(
+            vec![5, 0],
+            tiles.iter().map(|tile| tile.as_ref().0).collect::<Vec<_>>()
+        );
+
+        let requested = [
+            GridPosition::new_xy(0, 0),
+            GridPosition::new_xy(1, 0),
+            GridPosition::new_xy(5, 5),
+        ];
+        assert_eq!(1, map.get_tiles_at_positions(&requested).len());
+        let Err(err) = map.get_tiles_at_positions_checked(&requested) else {
+            panic!("missing tiles should be reported");
+        };
+        assert_eq!(&requested[1..], err.missing_positions());
+    }
+
+    #[test]
+    fn shift_wrap() {
+        let mut map = numbered_map();
+        map.shift(1, -1, ShiftMode::Wrap);
+
+        assert_eq!(6, map.get_all_positions().len());
+        // (0, 1) moved to (1, 0), while (2, 0) wrapped around to (0, 1).
+        assert_eq!(Some(3), data_at(&map, 1, 0));
+        assert_eq!(Some(2), data_at(&map, 0, 1));
+        assert_eq!(Some(5), data_at(&map, 0, 0));
+
+        map.shift(-1, 1, ShiftMode::Wrap);
+        for position in map.size().get_all_possible_positions() {
+            assert_eq!(
+                Some(position.x() + position.y() * 3),
+                data_at(&map, *position.x(), *position.y())
+            );
+        }
+    }
+
+    #[test]
+    fn shift_clear() {
+        let mut map = numbered_map();
+        map.shift(2, 0, ShiftMode::Clear);
+
+        assert_eq!(
+            vec![GridPosition::new_xy(2, 0), GridPosition::new_xy(2, 1)],
+            map.get_all_positions()
+        );
+        assert_eq!(Some(0), data_at(&map, 2, 0));
+        assert_eq!(Some(3), data_at(&map, 2, 1));
+
+        map.shift(0, -5, ShiftMode::Clear);
+        assert!(map.get_all_positions().is_empty());
+    }
+
+    #[test]
+    fn neighbour_diversity() {
+        let mut map = GridMap2D::new(GridSize::new_xy(6, 3));
+        for position in map.size().get_all_possible_positions() {
+            let tile_type_id = if *position.x() < 3 { 1 } else { 2 };
+            map.insert_data(&position, BasicIdentTileData::tile_new(tile_type_id));
+        }
+        map.insert_data(&GridPosition::new_xy(4, 1), BasicIdentTileData::tile_new(3));
+
+        let diversity = map.neighbour_diversity_map();
+        let at = |x, y| {
+            *diversity
+                .get_tile_at_position(&GridPosition::new_xy(x, y))
+                .unwrap()
+                .as_ref()
+        };
+        // Uniform area.
+        assert_eq!(1, at(0, 1));
+        assert_eq!(1, at(1, 1));
+        // Type boundary.
+        assert_eq!(2, at(2, 1));
+        assert_eq!(2, at(3, 0));
+        // Boundary next to the unique tile.
+        assert_eq!(3, at(3, 1));
+        assert_eq!(1, at(4, 1));
+    }
+}
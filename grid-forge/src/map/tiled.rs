@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use crate::error::TiledError;
+use crate::tile::identifiable::builders::ConstructableViaIdentifierTile;
+use crate::tile::identifiable::{BasicIdentTileData, IdentifiableTileData};
+use crate::tile::GridPosition;
+
+use super::{GridMap2D, GridSize};
+
+/// Bits of the gid used by the Tiled editor to mark flipped and rotated tiles.
+const TILED_FLIP_FLAGS: u32 = 0xF000_0000;
+
+/// Loads the map from the CSV-encoded data of the Tiled editor tile layer, as found within the `<data encoding="csv">`
+/// element of the `.tmx` file.
+///
+/// Gids are expected in row-major order, starting from the top-left tile. Each of them is translated into the
+/// `tile_type_id` with the `gid_to_type` mapping, with gid `0` leaving the position empty. Flags marking flipped and
+/// rotated tiles are ignored, so all orientations of the tile share its `tile_type_id`.
+///
+/// Returns [`TiledError`] if the number of gids doesn't match the `size`, or some of them is invalid or not mapped.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use grid_forge::{load_gridmap_from_tiled_csv, GridPosition, GridSize};
+/// use grid_forge::identifiable::IdentifiableTileData;
+///
+/// let csv = "
+/// 1,1,2,
+/// 0,3,2
+/// ";
+/// let map = load_gridmap_from_tiled_csv(csv, GridSize::new_xy(3, 2), &HashMap::from([(1, 10), (2, 20), (3, 30)])).unwrap();
+///
+/// assert_eq!(20, map.get_tile_at_position(&GridPosition::new_xy(2, 0)).unwrap().as_ref().tile_type_id());
+/// assert!(map.get_tile_at_position(&GridPosition::new_xy(0, 1)).is_none());
+/// ```
+pub fn load_gridmap_from_tiled_csv(
+    csv: &str,
+    size: GridSize,
+    gid_to_type: &HashMap<u32, u64>,
+) -> Result<GridMap2D<BasicIdentTileData>, TiledError> {
+    let cells = csv
+        .split(',')
+        .map(str::trim)
+        .filter(|cell| !cell.is_empty())
+        .collect::<Vec<_>>();
+    if cells.len() != size.area() as usize {
+        return Err(TiledError::new_length(size, cells.len()));
+    }
+
+    let mut map = GridMap2D::new(size);
+    for (idx, cell) in cells.into_iter().enumerate() {
+        let position = GridPosition::new_xy(idx as u32 % size.x(), idx as u32 / size.x());
+        let gid = cell
+            .parse::<u32>()
+            .map_err(|_| TiledError::new_invalid_gid(position, cell))?
+            & !TILED_FLIP_FLAGS;
+        if gid == 0 {
+            continue;
+        }
+        let tile_type_id = gid_to_type
+            .get(&gid)
+            .ok_or_else(|| TiledError::new_unknown_gid(position, gid))?;
+        map.insert_data(&position, BasicIdentTileData::tile_new(*tile_type_id));
+    }
+    Ok(map)
+}
+
+/// Writes the map as the CSV-encoded data of the Tiled editor tile layer, ready to be placed within the
+/// `<data encoding="csv">` element of the `.tmx` file and read back with [`load_gridmap_from_tiled_csv`].
+///
+/// Each `tile_type_id` is translated into the gid with the `type_to_gid` mapping, while empty positions are written
+/// as gid `0`. Gids are written in row-major order, starting from the top-left tile, with each row in separate line.
+/// Following the files saved by Tiled itself, every row apart from the last one ends with a trailing comma.
+///
+/// Returns [`TiledError`] if any `tile_type_id` present in the map is not mapped.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use grid_forge::{write_gridmap_to_tiled_csv, GridMap2D, GridPosition, GridSize};
+/// use grid_forge::identifiable::BasicIdentTileData;
+/// use grid_forge::identifiable::builders::ConstructableViaIdentifierTile;
+///
+/// let mut map = GridMap2D::new(GridSize::new_xy(3, 2));
+/// map.insert_data(&GridPosition::new_xy(0, 0), BasicIdentTileData::tile_new(10));
+/// map.insert_data(&GridPosition::new_xy(2, 1), BasicIdentTileData::tile_new(20));
+///
+/// let csv = write_gridmap_to_tiled_csv(&map, &HashMap::from([(10, 1), (20, 2)])).unwrap();
+/// assert_eq!("1,0,0,\n0,0,2", csv);
+/// ```
+pub fn write_gridmap_to_tiled_csv<Data>(
+    map: &GridMap2D<Data>,
+    type_to_gid: &HashMap<u64, u32>,
+) -> Result<String, TiledError>
+where
+    Data: IdentifiableTileData,
+{
+    let mut rows = vec![Vec::with_capacity(map.size().x() as usize); map.size().y() as usize];
+    for position in map.size().iter_all_positions() {
+        let gid = match map.get_tile_at_position(&position) {
+            Some(tile) => {
+                let tile_type_id = tile.as_ref().tile_type_id();
+                *type_to_gid
+                    .get(&tile_type_id)
+                    .ok_or_else(|| TiledError::new_unmapped_type(position, tile_type_id))?
+            }
+            None => 0,
+        };
+        rows[*position.y() as usize].push(gid.to_string());
+    }
+    Ok(rows
+        .iter()
+        .map(|row| row.join(","))
+        .collect::<Vec<_>>()
+        .join(",\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::map::GridSize;
+    use crate::tile::identifiable::IdentifiableTileData;
+    use crate::tile::GridPosition;
+
+    use super::{load_gridmap_from_tiled_csv, write_gridmap_to_tiled_csv};
+
+    #[test]
+    fn tiled_csv_is_loaded() {
+        let mapping = HashMap::from([(1, 100), (7, 700)]);
+        // Last gid is flipped horizontally.
+        let csv = "1,0,7,\n0,0,1,\n7,1,2147483655\n";
+        let map = load_gridmap_from_tiled_csv(csv, GridSize::new_xy(3, 3), &mapping).unwrap();
+
+        let type_at = |x, y| {
+            map.get_tile_at_position(&GridPosition::new_xy(x, y))
+                .map(|tile| tile.as_ref().tile_type_id())
+        };
+        assert_eq!(Some(700), type_at(2, 0));
+        assert_eq!(Some(100), type_at(2, 1));
+        assert_eq!(Some(700), type_at(2, 2));
+        assert_eq!(6, map.get_all_positions().len());
+
+        let err =
+            load_gridmap_from_tiled_csv("1,3,\n1,1", GridSize::new_xy(2, 2), &mapping).unwrap_err();
+        assert!(err.to_string().contains("gid 3"));
+        assert!(load_gridmap_from_tiled_csv("1,1,1", GridSize::new_xy(2, 2), &mapping).is_err());
+        assert!(load_gridmap_from_tiled_csv("1,x,1,1", GridSize::new_xy(2, 2), &mapping).is_err());
+    }
+
+    #[test]
+    fn tiled_csv_roundtrip() {
+        let gid_to_type = HashMap::from([(1, 100), (2, 200), (5, 500)]);
+        let type_to_gid = gid_to_type
+            .iter()
+            .map(|(gid, id)| (*id, *gid))
+            .collect::<HashMap<_, _>>();
+        let csv = "1,2,0,5,\n0,0,1,1,\n5,2,2,0";
+        let size = GridSize::new_xy(4, 3);
+
+        let map = load_gridmap_from_tiled_csv(csv, size, &gid_to_type).unwrap();
+        let written = write_gridmap_to_tiled_csv(&map, &type_to_gid).unwrap();
+        assert_eq!(csv, written);
+
+        let reloaded = load_gridmap_from_tiled_csv(&written, size, &gid_to_type).unwrap();
+        assert_eq!(map.get_all_positions(), reloaded.get_all_positions());
+        for position in map.get_all_positions() {
+            assert_eq!(
+                map.get_tile_at_position(&position)
+                    .unwrap()
+                    .as_ref()
+                    .tile_type_id(),
+                reloaded
+                    .get_tile_at_position(&position)
+                    .unwrap()
+                    .as_ref()
+                    .tile_type_id()
+            );
+        }
+
+        let err =
+            write_gridmap_to_tiled_csv(&map, &HashMap::from([(100, 1), (200, 2)])).unwrap_err();
+        assert!(err.to_string().contains("`tile_type_id` 500"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Pathfinding algorithms working on top of the [`GridMap2D`].
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::map::{GridDir, GridMap2D};
+use crate::tile::{GridPosition, TileData};
+
+/// Finds the cheapest path between `start` and `goal` using the A* algorithm, moving orthogonally with the Manhattan
+/// distance as the heuristic.
+///
+/// `cost` returns the cost of entering the tile, or [`None`] if it is impassable. Empty positions are impassable as
+/// well. The cost of the `start` tile is not taken into account.
+///
+/// Returns the path containing both `start` and `goal`, or [`None`] if the `goal` can't be reached.
+///
+/// # Examples
+/// ```
+/// use grid_forge::{GridMap2D, GridPosition, GridSize, TileData};
+/// use grid_forge::pathfind::astar;
+///
+/// struct Wall(bool);
+/// impl TileData for Wall {}
+///
+/// let mut map = GridMap2D::new(GridSize::new_xy(3, 3));
+/// for position in map.size().get_all_possible_positions() {
+///     map.insert_data(&position, Wall(*position.x() == 1 && *position.y() < 2));
+/// }
+///
+/// let cost = |tile: &Wall| (!tile.0).then_some(1);
+/// let path = astar(&map, GridPosition::new_xy(0, 0), GridPosition::new_xy(2, 0), cost).unwrap();
+///
+/// assert_eq!(7, path.len());
+/// assert_eq!(GridPosition::new_xy(1, 2), path[3]);
+/// ```
+pub fn astar<Data, F>(
+    map: &GridMap2D<Data>,
+    start: GridPosition,
+    goal: GridPosition,
+    cost: F,
+) -> Option<Vec<GridPosition>>
+where
+    Data: TileData,
+    F: Fn(&Data) -> Option<u32>,
+{
+    if !map.size().is_position_valid(&start) || !map.size().is_position_valid(&goal) {
+        return None;
+    }
+
+    let heuristic = |position: &GridPosition| {
+        position.x().abs_diff(*goal.x()) + position.y().abs_diff(*goal.y())
+    };
+    let mut open = BinaryHeap::from([Reverse((heuristic(&start), 0, start))]);
+    let mut best_costs = HashMap::from([(start, 0)]);
+    let mut came_from = HashMap::new();
+
+    while let Some(Reverse((_, current_cost, current))) = open.pop() {
+        if current == goal {
+            let mut path = vec![current];
+            while let Some(previous) = came_from.get(path.last().unwrap()) {
+                path.push(*previous);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if best_costs
+            .get(&current)
+            .is_some_and(|best| *best < current_cost)
+        {
+            continue;
+        }
+
+        for direction in GridDir::ALL_2D {
+            let Some(neighbour) = direction.march_step(&current, map.size()) else {
+                continue;
+            };
+            let Some(step_cost) = map
+                .get_tile_at_position(&neighbour)
+                .and_then(|tile| cost(tile.as_ref()))
+            else {
+                continue;
+            };
+            let neighbour_cost = current_cost + step_cost;
+            if best_costs
+                .get(&neighbour)
+                .is_some_and(|best| *best <= neighbour_cost)
+            {
+                continue;
+            }
+            best_costs.insert(neighbour, neighbour_cost);
+            came_from.insert(neighbour, current);
+            open.push(Reverse((
+                neighbour_cost + heuristic(&neighbour),
+                neighbour_cost,
+                neighbour,
+            )));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use crate::map::{GridMap2D, GridSize};
+    use crate::tile::{GridPosition, TileData};
+
+    use super::astar;
+
+    struct Cost(Option<u32>);
+
+    impl TileData for Cost {}
+
+    fn uniform_map(size: GridSize) -> GridMap2D<Cost> {
+        let mut map = GridMap2D::new(size);
+        for position in size.get_all_possible_positions() {
+            map.insert_data(&position, Cost(Some(1)));
+        }
+        map
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let map = uniform_map(GridSize::new_xy(3, 3));
+        let start = GridPosition::new_xy(1, 1);
+
+        assert_eq!(Some(vec![start]), astar(&map, start, start, |tile| tile.0));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let mut map = uniform_map(GridSize::new_xy(3, 3));
+        for y in 0..3 {
+            map.insert_data(&GridPosition::new_xy(1, y), Cost(None));
+        }
+
+        let start = GridPosition::new_xy(0, 0);
+        assert!(astar(&map, start, GridPosition::new_xy(2, 2), |tile| tile.0).is_none());
+        assert!(astar(&map, start, GridPosition::new_xy(5, 5), |tile| tile.0).is_none());
+    }
+
+    #[test]
+    fn avoids_costly_tiles() {
+        // Direct row is expensive, the detour through the second row is cheaper.
+        let mut map = uniform_map(GridSize::new_xy(4, 2));
+        for x in 1..3 {
+            map.insert_data(&GridPosition::new_xy(x, 0), Cost(Some(10)));
+        }
+
+        let path = astar(
+            &map,
+            GridPosition::new_xy(0, 0),
+            GridPosition::new_xy(3, 0),
+            |tile| tile.0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                GridPosition::new_xy(0, 0),
+                GridPosition::new_xy(0, 1),
+                GridPosition::new_xy(1, 1),
+                GridPosition::new_xy(2, 1),
+                GridPosition::new_xy(3, 1),
+                GridPosition::new_xy(3, 0),
+            ],
+            path
+        );
+    }
+}
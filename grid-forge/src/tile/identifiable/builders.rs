@@ -68,12 +68,14 @@ use crate::tile::{GridPosition, GridTile};
 #[derive(Debug, Clone)]
 pub struct IdentTileCloneBuilder<Data: IdentifiableTileData + Clone> {
     tiles: BTreeMap<u64, Data>,
+    aliases: BTreeMap<u64, u64>,
 }
 
 impl<T: IdentifiableTileData + Clone> Default for IdentTileCloneBuilder<T> {
     fn default() -> Self {
         Self {
             tiles: BTreeMap::new(),
+            aliases: BTreeMap::new(),
         }
     }
 }
@@ -90,13 +92,47 @@ impl<Data: IdentifiableTileData + Clone> IdentTileCloneBuilder<Data> {
             self.tiles.insert(tile.tile_type_id(), tile.clone());
         }
     }
+
+    /// Makes the builder create tiles for `alias_id` using the prototype provided for `canonical_id`. Built tiles are
+    /// exact clones of the prototype, so they will have `canonical_id` as their `tile_type_id`.
+    ///
+    /// Prototype provided directly for `alias_id` takes precedence over the alias. Aliases are not resolved
+    /// recursively, so `canonical_id` needs to have its own prototype.
+    ///
+    /// # Examples
+    /// ```
+    /// use grid_forge::GridPosition;
+    /// use grid_forge::identifiable::{BasicIdentTileData, IdentifiableTileData};
+    /// use grid_forge::identifiable::builders::{ConstructableViaIdentifierTile, IdentTileBuilder, IdentTileCloneBuilder};
+    ///
+    /// let mut builder = IdentTileCloneBuilder::<BasicIdentTileData>::default();
+    /// builder.add_tiles(&[BasicIdentTileData::tile_new(1)], false);
+    /// builder.add_alias(7, 1);
+    ///
+    /// assert!(builder.check_missing_ids(&[1, 7]).is_ok());
+    /// let tile = builder.build_tile(GridPosition::new_xy(0, 0), 7).unwrap();
+    /// assert_eq!(1, tile.as_ref().tile_type_id());
+    ///
+    /// builder.add_alias(8, 2);
+    /// assert!(builder.check_missing_ids(&[8]).is_err());
+    /// ```
+    pub fn add_alias(&mut self, alias_id: u64, canonical_id: u64) {
+        self.aliases.insert(alias_id, canonical_id);
+    }
+
+    fn get_prototype(&self, tile_type_id: u64) -> Option<&Data> {
+        self.tiles.get(&tile_type_id).or_else(|| {
+            self.aliases
+                .get(&tile_type_id)
+                .and_then(|canonical_id| self.tiles.get(canonical_id))
+        })
+    }
 }
 
 impl<Data: IdentifiableTileData + Clone> IdentTileBuilder<Data> for IdentTileCloneBuilder<Data> {
     fn build_tile_unchecked(&self, position: GridPosition, tile_type_id: u64) -> GridTile<Data> {
         let tile_data = self
-            .tiles
-            .get(&tile_type_id)
+            .get_prototype(tile_type_id)
             .unwrap_or_else(|| panic!("can't get tile_data with `tile_type_id`: {tile_type_id}"))
             .clone();
 
@@ -108,7 +144,7 @@ impl<Data: IdentifiableTileData + Clone> IdentTileBuilder<Data> for IdentTileClo
         position: GridPosition,
         tile_type_id: u64,
     ) -> Result<GridTile<Data>, TileBuilderError> {
-        if let Some(tile) = self.tiles.get(&tile_type_id) {
+        if let Some(tile) = self.get_prototype(tile_type_id) {
             let data = tile.clone();
             Ok(GridTile::new(position, data))
         } else {
@@ -119,7 +155,7 @@ impl<Data: IdentifiableTileData + Clone> IdentTileBuilder<Data> for IdentTileClo
     fn check_missing_ids(&self, tile_type_ids: &[u64]) -> Result<(), TileBuilderError> {
         let missing_ids = tile_type_ids
             .iter()
-            .filter(|tile_id| !self.tiles.contains_key(tile_id))
+            .filter(|tile_id| self.get_prototype(**tile_id).is_none())
             .copied()
             .collect::<Vec<_>>();
 
@@ -230,6 +266,223 @@ impl<Data: IdentifiableTileData> IdentTileBuilder<Data> for IdentTileFunBuilder<
     }
 }
 
+/// [`IdentTileBuilder`] which creates new tiles with given identifier based on the position-aware contructor functions
+/// provided to the builder via [`set_tile_constructor`](Self::set_tile_constructor).
+///
+/// Works the same way as [`IdentTileFunBuilder`], but the constructors receive the [`GridPosition`] of the created tile
+/// alongside its `tile_type_id`, eg. to seed deterministic per-tile randomness.
+///
+/// # Examples
+/// ```
+/// use grid_forge::{GridPosition, TileData, TileContainer};
+/// use grid_forge::identifiable::IdentifiableTileData;
+/// use grid_forge::identifiable::builders::{IdentTileBuilder, IdentTilePosFunBuilder};
+///
+/// struct MyTileData {
+///     tile_type_id: u64,
+///     origin: GridPosition,
+/// }
+///
+/// impl TileData for MyTileData {};
+///
+/// impl IdentifiableTileData for MyTileData {
+///     fn tile_type_id(&self) -> u64 {
+///         self.tile_type_id
+///     }
+/// }
+///
+/// let mut builder = IdentTilePosFunBuilder::<MyTileData>::default();
+/// builder.set_tile_constructor(1, |origin, tile_type_id| MyTileData { tile_type_id, origin });
+///
+/// assert!(builder.check_missing_ids(&[1, 2]).is_err());
+///
+/// let tile = builder.build_tile_unchecked(GridPosition::new_xy(2, 3), 1);
+/// assert_eq!((GridPosition::new_xy(2, 3), 1), (tile.as_ref().origin, tile.as_ref().tile_type_id()));
+/// assert_eq!(tile.grid_position(), tile.as_ref().origin);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IdentTilePosFunBuilder<T: IdentifiableTileData> {
+    funs: BTreeMap<u64, fn(GridPosition, u64) -> T>,
+}
+
+impl<Data: IdentifiableTileData> IdentTilePosFunBuilder<Data> {
+    pub fn set_tile_constructor(
+        &mut self,
+        tile_id: u64,
+        constructor: fn(GridPosition, u64) -> Data,
+    ) {
+        self.funs.insert(tile_id, constructor);
+    }
+
+    pub fn clear(&mut self) {
+        self.funs.clear();
+    }
+}
+
+impl<Data: IdentifiableTileData> Default for IdentTilePosFunBuilder<Data> {
+    fn default() -> Self {
+        Self {
+            funs: BTreeMap::new(),
+        }
+    }
+}
+
+impl<Data: IdentifiableTileData> IdentTileBuilder<Data> for IdentTilePosFunBuilder<Data> {
+    fn build_tile_unchecked(&self, position: GridPosition, tile_type_id: u64) -> GridTile<Data> {
+        let fun = self.funs.get(&tile_type_id).unwrap_or_else(|| {
+            panic!("can't get tile constructor function for `tile_type_id`: {tile_type_id}")
+        });
+
+        GridTile::new(position, fun(position, tile_type_id))
+    }
+
+    fn build_tile(
+        &self,
+        position: GridPosition,
+        tile_id: u64,
+    ) -> Result<GridTile<Data>, TileBuilderError> {
+        if let Some(fun) = self.funs.get(&tile_id) {
+            Ok(GridTile::new(position, fun(position, tile_id)))
+        } else {
+            Err(TileBuilderError::new(&[tile_id]))
+        }
+    }
+
+    fn check_missing_ids(&self, tile_ids: &[u64]) -> Result<(), TileBuilderError> {
+        let missing_ids = tile_ids
+            .iter()
+            .filter(|tile_id| !self.funs.contains_key(tile_id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if !missing_ids.is_empty() {
+            Err(TileBuilderError::new(&missing_ids))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// [`IdentTileBuilder`] which creates new tiles by dispatching the `tile_type_id` to constructors based on predicates
+/// provided via [`add_dispatch`](Self::add_dispatch).
+///
+/// Predicates are checked in order of their addition, and the tile is built by the constructor paired with the first
+/// matching predicate. It is more flexible than [`IdentTileFunBuilder`], as single constructor can handle whole ranges of
+/// identifiers, eg. to create different variants of one `Data` enum.
+///
+/// # Examples
+/// ```
+/// use grid_forge::{GridPosition, TileData, TileContainer};
+/// use grid_forge::identifiable::IdentifiableTileData;
+/// use grid_forge::identifiable::builders::{IdentTileBuilder, IdentTileDispatchBuilder};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum MyTileData {
+///     Floor { tile_type_id: u64 },
+///     Wall { tile_type_id: u64 },
+/// }
+///
+/// impl TileData for MyTileData {};
+///
+/// impl IdentifiableTileData for MyTileData {
+///     fn tile_type_id(&self) -> u64 {
+///         match self {
+///             MyTileData::Floor { tile_type_id } | MyTileData::Wall { tile_type_id } => *tile_type_id,
+///         }
+///     }
+/// }
+///
+/// let mut builder = IdentTileDispatchBuilder::<MyTileData>::default();
+/// builder.add_dispatch(|id| id % 2 == 0, |tile_type_id| MyTileData::Floor { tile_type_id });
+/// builder.add_dispatch(|id| id < 10, |tile_type_id| MyTileData::Wall { tile_type_id });
+///
+/// if let Err(err) = builder.check_missing_ids(&[2, 3, 11, 12]) {
+///     assert_eq!(&[11], err.get_missing_tile_type_ids());
+/// } else {
+///     panic!("Should return error!");
+/// }
+///
+/// let even = builder.build_tile_unchecked(GridPosition::new_xy(2, 3), 4);
+/// assert_eq!(&MyTileData::Floor { tile_type_id: 4 }, even.as_ref());
+///
+/// let odd = builder.build_tile_unchecked(GridPosition::new_xy(3, 4), 5);
+/// assert_eq!(&MyTileData::Wall { tile_type_id: 5 }, odd.as_ref());
+///
+/// assert!(builder.build_tile(GridPosition::new_xy(0, 0), 11).is_err());
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct IdentTileDispatchBuilder<Data: IdentifiableTileData> {
+    dispatch: Vec<(Box<dyn Fn(u64) -> bool>, Box<dyn Fn(u64) -> Data>)>,
+}
+
+impl<Data: IdentifiableTileData> IdentTileDispatchBuilder<Data> {
+    /// Adds the `constructor` used to build the tiles for which `predicate` returns `true`. Predicates are checked in
+    /// order of their addition.
+    pub fn add_dispatch(
+        &mut self,
+        predicate: impl Fn(u64) -> bool + 'static,
+        constructor: impl Fn(u64) -> Data + 'static,
+    ) {
+        self.dispatch
+            .push((Box::new(predicate), Box::new(constructor)));
+    }
+
+    pub fn clear(&mut self) {
+        self.dispatch.clear();
+    }
+
+    fn get_constructor(&self, tile_type_id: u64) -> Option<&dyn Fn(u64) -> Data> {
+        self.dispatch
+            .iter()
+            .find(|(predicate, _)| predicate(tile_type_id))
+            .map(|(_, constructor)| constructor.as_ref())
+    }
+}
+
+impl<Data: IdentifiableTileData> Default for IdentTileDispatchBuilder<Data> {
+    fn default() -> Self {
+        Self {
+            dispatch: Vec::new(),
+        }
+    }
+}
+
+impl<Data: IdentifiableTileData> IdentTileBuilder<Data> for IdentTileDispatchBuilder<Data> {
+    fn build_tile_unchecked(&self, position: GridPosition, tile_type_id: u64) -> GridTile<Data> {
+        let fun = self.get_constructor(tile_type_id).unwrap_or_else(|| {
+            panic!("can't get tile constructor function for `tile_type_id`: {tile_type_id}")
+        });
+
+        GridTile::new(position, fun(tile_type_id))
+    }
+
+    fn build_tile(
+        &self,
+        position: GridPosition,
+        tile_type_id: u64,
+    ) -> Result<GridTile<Data>, TileBuilderError> {
+        if let Some(fun) = self.get_constructor(tile_type_id) {
+            Ok(GridTile::new(position, fun(tile_type_id)))
+        } else {
+            Err(TileBuilderError::new(&[tile_type_id]))
+        }
+    }
+
+    fn check_missing_ids(&self, tile_type_ids: &[u64]) -> Result<(), TileBuilderError> {
+        let missing_ids = tile_type_ids
+            .iter()
+            .filter(|tile_id| self.get_constructor(**tile_id).is_none())
+            .copied()
+            .collect::<Vec<_>>();
+
+        if !missing_ids.is_empty() {
+            Err(TileBuilderError::new(&missing_ids))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// Trait which allows creating new istance of struct implementing [`IdentifiableTileData`].
 ///
 /// See also [`BasicIdentTileData`](crate::tile::identifiable::BasicIdentTileData) for basic identifiable tile type which
@@ -322,8 +575,10 @@ impl<Data: IdentifiableTileData + ConstructableViaIdentifierTile> IdentTileBuild
 /// Trait shared by objects which on basis of the grid position and tile identifier of given [`IdentifiableTileData`]-implementing struct can
 /// create correct instance of the tile. Necessary for many [`GridMap2D`](crate::map::GridMap2D) creating methods.
 ///
-/// Three different builders are available in the `grid_forge`:
+/// Five different builders are available in the `grid_forge`:
 /// - [`IdentTileFunBuilder`] - for tiles not implementing any additional traits.
+/// - [`IdentTilePosFunBuilder`] - for tiles not implementing any additional traits, constructed with their position.
+/// - [`IdentTileDispatchBuilder`] - for tiles not implementing any additional traits, constructed on basis of predicates.
 /// - [`IdentTileCloneBuilder`] - for tiles implementing [`Clone`].
 /// - [`IdentTileTraitBuilder`] - for tiles implementing [`ConstructableViaIdentifierTile`].
 ///
@@ -144,6 +144,21 @@ pub trait IdentTileCollection {
         self.rev().get(&Self::generate_type_id(data)).copied()
     }
 
+    /// Iterates over all `tile_type_id`s stored in the collection, in arbitrary order.
+    fn iter_tile_types(&self) -> impl Iterator<Item = &u64> {
+        self.inner().keys()
+    }
+
+    /// Returns the number of `tile_type_id`s stored in the collection.
+    fn len(&self) -> usize {
+        self.inner().len()
+    }
+
+    /// Checks if there are no `tile_type_id`s stored in the collection.
+    fn is_empty(&self) -> bool {
+        self.inner().is_empty()
+    }
+
     /// Generates `tile_type_id` using provided [`DATA`](IdentTileCollection::DATA).
     fn generate_type_id(data: &Self::DATA) -> u64 {
         let mut hasher = DefaultHasher::default();
@@ -188,6 +203,7 @@ mod test {
     #[test]
     fn test_collection_data_only() {
         let mut collection = TestTileCollection::default();
+        assert!(collection.is_empty());
 
         for data in TEST_DATA {
             assert!(collection.add_tile(data), "no data: {data} has been added");
@@ -195,6 +211,12 @@ mod test {
 
         assert_eq!(TEST_DATA.len(), collection.inner().len());
         assert_eq!(TEST_DATA.len(), collection.rev().len());
+        assert_eq!(TEST_DATA.len(), collection.len());
+        let mut tile_types = collection.iter_tile_types().copied().collect::<Vec<_>>();
+        tile_types.sort_unstable();
+        let mut keys = collection.inner().keys().copied().collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(keys, tile_types);
 
         for data in TEST_DATA {
             assert!(!collection.add_tile(data), "data: {data} has been added");
@@ -12,8 +12,19 @@ where
 }
 
 /// Marker trait for structs that can be contained withing [`GridMap2D`](crate::map::GridMap2D) and [`TileContainer`]
+///
+/// Implemented for primitive numeric types and `bool`, allowing for maps of plain values, eg. the metrics computed for
+/// each tile.
 pub trait TileData: Sized {}
 
+macro_rules! impl_tile_data_for_primitives {
+    ($($t:ty),*) => {
+        $(impl TileData for $t {})*
+    };
+}
+
+impl_tile_data_for_primitives!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, bool);
+
 /// Container of the [`TileData`] owning the data, when it is not yet passed to [`GridMap2D`](crate::map::GridMap2D) or
 /// after it was retrieved from it through [`drain`](crate::map::GridMap2D::drain) or [`drain_remapped`](crate::map::GridMap2D::drain_remapped)
 impl<Data: TileData> GridTile<Data> {
@@ -134,6 +145,7 @@ impl<Data: TileData> TileContainer for GridTileRefMut<'_, Data> {
 
 /// Position of the [`TileData`] within a [`GridMap2D`](crate::map::GridMap2D).
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GridPosition {
     x: u32,
     y: u32,
@@ -176,10 +188,10 @@ impl GridPosition {
         self.y += xy.1;
     }
 
+    /// Adds `z` to the `z` coordinate of the position. If the position has no `z` coordinate, it is treated as `0`, so
+    /// the position becomes three-dimensional with `z` coordinate equal to the added value.
     pub fn add_z(&mut self, z: u32) {
-        if let Some(zs) = &mut self.z {
-            *zs += z;
-        }
+        self.z = Some(self.z.unwrap_or(0) + z);
     }
 
     pub fn in_range(&self, other: &Self, range: u32) -> bool {
@@ -202,6 +214,28 @@ impl GridPosition {
         (distance + self.y().max(other.y()) - self.y().min(other.y())) <= range
     }
 
+    /// Returns the sum of distances along each axis between `self` and `other`, which is the number of steps between
+    /// them when moving only along the axes. Same as with [`in_range`](Self::in_range), the `z` axis is taken into
+    /// account only if both positions have the `z` coordinate.
+    pub fn manhattan_distance(&self, other: &Self) -> u32 {
+        self.axis_distances(other).iter().sum()
+    }
+
+    /// Returns the largest of distances along each axis between `self` and `other`, which is the number of steps between
+    /// them when moving diagonally is allowed. Same as with [`in_range`](Self::in_range), the `z` axis is taken into
+    /// account only if both positions have the `z` coordinate.
+    pub fn chebyshev_distance(&self, other: &Self) -> u32 {
+        self.axis_distances(other).into_iter().max().unwrap()
+    }
+
+    fn axis_distances(&self, other: &Self) -> [u32; 3] {
+        let z = match (self.z, other.z) {
+            (Some(zl), Some(zr)) => zl.abs_diff(zr),
+            _ => 0,
+        };
+        [self.x.abs_diff(other.x), self.y.abs_diff(other.y), z]
+    }
+
     pub fn generate_rect_area(upper_left: &Self, lower_right: &Self) -> Vec<Self> {
         let mut out = Vec::new();
 
@@ -230,20 +264,71 @@ impl GridPosition {
         out
     }
 
+    /// Returns all positions on the straight line between `self` and `other`, including both endpoints, using
+    /// Bresenham's line algorithm.
+    ///
+    /// The line is drawn in the 2D plane - all returned positions share the `z` coordinate of `self`.
+    ///
+    /// ```
+    /// use grid_forge::GridPosition;
+    ///
+    /// let line = GridPosition::new_xy(0, 0).line_to(&GridPosition::new_xy(3, 1));
+    /// assert_eq!(line.len(), 4);
+    /// assert_eq!(line.first(), Some(&GridPosition::new_xy(0, 0)));
+    /// assert_eq!(line.last(), Some(&GridPosition::new_xy(3, 1)));
+    /// ```
+    pub fn line_to(&self, other: &Self) -> Vec<Self> {
+        let (x0, y0) = (self.x as i64, self.y as i64);
+        let (x1, y1) = (other.x as i64, other.y as i64);
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let step_x = if x0 < x1 { 1 } else { -1 };
+        let step_y = if y0 < y1 { 1 } else { -1 };
+
+        let mut out = Vec::with_capacity(dx.max(-dy) as usize + 1);
+        let (mut x, mut y) = (x0, y0);
+        let mut error = dx + dy;
+        loop {
+            out.push(Self {
+                x: x as u32,
+                y: y as u32,
+                z: self.z,
+            });
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+        out
+    }
+
     /// Filter the `pos` vector, removing from it all positions contained within `to_filter`.
     pub fn filter_positions(pos: &mut Vec<GridPosition>, to_filter: &[GridPosition]) {
         pos.retain(|p| !to_filter.contains(p));
     }
 }
 
+/// Arithmetic on positions treats the missing `z` coordinate as `0` if the other position has it, so the result is
+/// two-dimensional only if both positions are. As with `x` and `y`, subtraction resulting in negative `z` panics.
 impl Add for GridPosition {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
-        if let (Some(lz), Some(rz)) = (&self.z, &rhs.z) {
-            Self::new_xyz(self.x + rhs.x, self.y + rhs.y, lz + rz)
-        } else {
-            Self::new_xy(self.x + rhs.x, self.y + rhs.y)
+        match (self.z, rhs.z) {
+            (None, None) => Self::new_xy(self.x + rhs.x, self.y + rhs.y),
+            (lz, rz) => Self::new_xyz(
+                self.x + rhs.x,
+                self.y + rhs.y,
+                lz.unwrap_or(0) + rz.unwrap_or(0),
+            ),
         }
     }
 }
@@ -252,23 +337,20 @@ impl Sub for GridPosition {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        match (&self.z, &rhs.z) {
+        match (self.z, rhs.z) {
             (None, None) => Self::new_xy(self.x - rhs.x, self.y - rhs.y),
-            (None, Some(z)) => Self::new_xyz(self.x - rhs.x, self.y - rhs.y, *z),
-            (Some(z), None) => Self::new_xyz(self.x - rhs.x, self.y - rhs.y, *z),
-            (Some(lz), Some(rz)) => Self::new_xyz(self.x - rhs.x, self.y - rhs.y, lz - rz),
+            (lz, rz) => Self::new_xyz(
+                self.x - rhs.x,
+                self.y - rhs.y,
+                lz.unwrap_or(0) - rz.unwrap_or(0),
+            ),
         }
     }
 }
 
 impl AddAssign for GridPosition {
     fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-
-        if let (Some(mut lz), Some(rz)) = (&mut self.z, &rhs.z) {
-            lz += rz;
-        }
+        *self = *self + rhs;
     }
 }
 
@@ -278,3 +360,104 @@ impl AddAssign for GridPosition {
 pub trait TileContainer {
     fn grid_position(&self) -> GridPosition;
 }
+
+#[cfg(test)]
+mod test {
+    use super::GridPosition;
+
+    fn positions(coords: &[(u32, u32)]) -> Vec<GridPosition> {
+        coords
+            .iter()
+            .map(|(x, y)| GridPosition::new_xy(*x, *y))
+            .collect()
+    }
+
+    #[test]
+    fn line_to_straight() {
+        let start = GridPosition::new_xy(1, 2);
+
+        assert_eq!(
+            positions(&[(1, 2), (2, 2), (3, 2), (4, 2)]),
+            start.line_to(&GridPosition::new_xy(4, 2))
+        );
+        assert_eq!(
+            positions(&[(1, 2), (1, 1), (1, 0)]),
+            start.line_to(&GridPosition::new_xy(1, 0))
+        );
+        assert_eq!(positions(&[(1, 2)]), start.line_to(&start));
+    }
+
+    #[test]
+    fn distances_2d() {
+        let from = GridPosition::new_xy(1, 5);
+        let to = GridPosition::new_xy(4, 1);
+
+        assert_eq!(7, from.manhattan_distance(&to));
+        assert_eq!(7, to.manhattan_distance(&from));
+        assert_eq!(4, from.chebyshev_distance(&to));
+        assert_eq!(0, from.chebyshev_distance(&from));
+        assert!(from.in_range(&to, from.manhattan_distance(&to)));
+        assert!(!from.in_range(&to, from.manhattan_distance(&to) - 1));
+    }
+
+    #[test]
+    fn distances_3d() {
+        let from = GridPosition::new_xyz(1, 1, 0);
+        let to = GridPosition::new_xyz(2, 3, 6);
+
+        assert_eq!(9, from.manhattan_distance(&to));
+        assert_eq!(6, from.chebyshev_distance(&to));
+        // `z` is ignored if any of positions lacks it.
+        assert_eq!(3, GridPosition::new_xy(1, 1).manhattan_distance(&to));
+        assert_eq!(2, GridPosition::new_xy(1, 1).chebyshev_distance(&to));
+    }
+
+    #[test]
+    fn mixed_dimensions_arithmetic() {
+        let mut flat = GridPosition::new_xy(1, 2);
+        flat.add_z(3);
+        assert_eq!(GridPosition::new_xyz(1, 2, 3), flat);
+        flat.add_z(1);
+        assert_eq!(GridPosition::new_xyz(1, 2, 4), flat);
+
+        let (flat, deep) = (GridPosition::new_xy(4, 4), GridPosition::new_xyz(1, 1, 2));
+        assert_eq!(GridPosition::new_xyz(5, 5, 2), flat + deep);
+        assert_eq!(GridPosition::new_xyz(5, 5, 2), deep + flat);
+        assert_eq!(
+            GridPosition::new_xyz(3, 3, 2),
+            (flat + deep) - GridPosition::new_xy(2, 2)
+        );
+        assert_eq!(
+            GridPosition::new_xy(3, 3),
+            flat - GridPosition::new_xy(1, 1)
+        );
+
+        let mut sum = deep;
+        sum += deep;
+        assert_eq!(GridPosition::new_xyz(2, 2, 4), sum);
+        sum += flat;
+        assert_eq!(GridPosition::new_xyz(6, 6, 4), sum);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_z_panics() {
+        let _ = GridPosition::new_xy(4, 4) - GridPosition::new_xyz(1, 1, 2);
+    }
+
+    #[test]
+    fn line_to_diagonal() {
+        assert_eq!(
+            positions(&[(0, 0), (1, 1), (2, 2), (3, 3)]),
+            GridPosition::new_xy(0, 0).line_to(&GridPosition::new_xy(3, 3))
+        );
+        assert_eq!(
+            positions(&[(3, 0), (2, 1), (1, 2), (0, 3)]),
+            GridPosition::new_xy(3, 0).line_to(&GridPosition::new_xy(0, 3))
+        );
+        assert_eq!(
+            positions(&[(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]),
+            GridPosition::new_xy(0, 0).line_to(&GridPosition::new_xy(4, 2))
+        );
+    }
+}
@@ -8,7 +8,7 @@ use crate::tile::identifiable::IdentifiableTileData;
 use crate::tile::{GridPosition, TileContainer};
 
 use super::error::VisError;
-use super::ops::create_tile_id_from_pixels;
+use super::ops::{check_grid_vis_size, create_tile_id_from_pixels};
 use super::{read_tile, write_tile, EmptyTile, PixelWithDefault, VisTile2D, VisTileData};
 
 /// Outcome of `set_*` and `add_*` methods of [`VisCollection`].
@@ -33,6 +33,12 @@ pub type VisCollectionResult<P, const WIDTH: usize, const HEIGHT: usize> =
 ///
 /// You can view it as a basic *Resource system* - it allows transforming between [`ImageBuffer`] and [`GridMap2D`]
 /// without keeping pixels in every individual tile data, as with [`VisTileData`] implementors.
+///
+/// # Breaking change
+/// Identifiers created from the pixels, including the one of the empty tile, are now calculated with a stable FNV-1a
+/// hash instead of [`DefaultHasher`](std::collections::hash_map::DefaultHasher), so they differ from the ones created by
+/// previous versions. Persisted identifiers need to be regenerated, as described in
+/// [`load_gridmap_identifiable_auto`](super::ops::load_gridmap_identifiable_auto).
 #[derive(Debug, Clone)]
 pub struct VisCollection<P, const WIDTH: usize, const HEIGHT: usize>
 where
@@ -165,6 +171,24 @@ where
         }
     }
 
+    /// Registers pixels of every distinct tile type present in the [`GridMap2D`] of [`VisTileData`], keyed by their
+    /// `tile_type_id`, so the map built programmatically can be rendered. Tile types already present in the collection
+    /// are skipped.
+    ///
+    /// Returns the number of newly registered tile types.
+    ///
+    /// # See also
+    /// - [`Self::add_tiles_from_vis_map`]
+    pub fn register_from_map<Data>(&mut self, grid_map: &GridMap2D<Data>) -> usize
+    where
+        Data: VisTileData<P, WIDTH, HEIGHT> + IdentifiableTileData,
+    {
+        grid_map
+            .iter_tiles()
+            .filter(|tile| matches!(self.add_vis_tile_pixels(tile), VisCollectionOutcome::Added))
+            .count()
+    }
+
     pub fn add_tile_pixels<Data, Tile>(
         &mut self,
         tile: &Tile,
@@ -263,6 +287,18 @@ where
         self.rev.get(&create_tile_id_from_pixels(pixels))
     }
 
+    /// Returns pixels registered for given `tile_id`, if any.
+    pub fn get_pixels_by_tile_id(&self, tile_id: u64) -> Option<&[[P; WIDTH]; HEIGHT]> {
+        self.inner.get(&tile_id)
+    }
+
+    /// Returns the identifier of the empty tile, if its pixels were set with [`set_empty_tile_pixels`](Self::set_empty_tile_pixels).
+    ///
+    /// The identifier is derived from the pixels in a way that is stable across runs, platforms and compiler versions.
+    pub fn empty_tile_id(&self) -> Option<u64> {
+        self.empty.as_ref().map(|empty| empty.tile_id())
+    }
+
     pub fn is_empty(&self, check_pixels: &[[P; WIDTH]; HEIGHT]) -> bool {
         if let Some(EmptyTile { tile_id: _, pixels }) = self.empty {
             &pixels == check_pixels
@@ -307,6 +343,41 @@ where
         Ok(())
     }
 
+    /// Creates an image presenting pixels of every registered tile, one tile per cell, ordered by `tile_type_id`
+    /// ascending row by row. Handy for checking which pixels are registered for which `tile_type_id`.
+    ///
+    /// If number of `columns` is not provided, tiles are laid out in a square-ish grid. Cells not filled by any tile are
+    /// left with default pixels.
+    pub fn write_legend(&self, columns: Option<usize>) -> ImageBuffer<P, Vec<P::Subpixel>> {
+        let mut tile_ids = self.inner.keys().copied().collect::<Vec<_>>();
+        tile_ids.sort_unstable();
+
+        let count = tile_ids.len().max(1);
+        let columns = columns
+            .unwrap_or_else(|| (count as f64).sqrt().ceil() as usize)
+            .clamp(1, count);
+        let rows = count.div_ceil(columns);
+
+        let mut buffer = self.init_map_image_buffer(&GridSize::new_xy(columns as u32, rows as u32));
+        for (idx, tile_id) in tile_ids.iter().enumerate() {
+            let position = GridPosition::new_xy((idx % columns) as u32, (idx / columns) as u32);
+            write_tile(&mut buffer, position, &self.inner[tile_id])
+                .expect("legend buffer should fit all tiles");
+        }
+        buffer
+    }
+
+    /// Verifies that the dimensions of provided [`ImageBuffer`] are exact multiples of tile size in pixels
+    /// (`WIDTH` x `HEIGHT`), so the whole image can be read as a grid of tiles.
+    ///
+    /// Returns [`VisError`] containing the offending image dimensions otherwise. See also [`check_grid_vis_size`],
+    /// which additionally returns the inferred [`GridSize`].
+    pub fn verify_pixel_dims(
+        image: &ImageBuffer<P, Vec<P::Subpixel>>,
+    ) -> Result<(), VisError<WIDTH, HEIGHT>> {
+        check_grid_vis_size(image).map(|_| ())
+    }
+
     // ------ Private ------ //
     pub(crate) fn read_pixels_for_tile_at_pos(
         buffer: &ImageBuffer<P, Vec<P::Subpixel>>,
@@ -1,3 +1,4 @@
+use std::error::Error;
 use std::fmt::Display;
 
 use crate::tile::GridPosition;
@@ -15,9 +16,12 @@ impl<const WIDTH: usize, const HEIGHT: usize> VisError<WIDTH, HEIGHT> {
         }
     }
 
-    pub(crate) fn new_grid_load(x: u32, y: u32) -> Self {
+    pub(crate) fn new_dimension_mismatch(image: (u32, u32)) -> Self {
         Self {
-            kind: VisErrorKind::WrongSizeGridLoad { x, y },
+            kind: VisErrorKind::DimensionMismatch {
+                image,
+                tile: (WIDTH, HEIGHT),
+            },
         }
     }
 
@@ -58,8 +62,21 @@ impl<const WIDTH: usize, const HEIGHT: usize> Display for VisError<WIDTH, HEIGHT
           VisErrorKind::NonExistingTile(pos) => {
             write!(f, "tile at position: {pos:?} is not contained within used `VisCollection`. Make sure to register it first manually")
           }
-            VisErrorKind::WrongSizeGridLoad { x, y } => {
-                write!(f, "expected tile pixel size (x: {WIDTH}; y: {HEIGHT}) is incompatible with GridMap image size: (x: {x}, y: {y})")
+            VisErrorKind::DimensionMismatch { image, tile } => {
+                write!(f, "image size (x: {}, y: {}) is not a multiple of tile pixel size (x: {}, y: {})", image.0, image.1, tile.0, tile.1)?;
+                let fit = |image: u32, tile: usize| {
+                    let tile = tile as u32;
+                    let smaller = image / tile * tile;
+                    let larger = image.div_ceil(tile) * tile;
+                    (smaller.max(tile), larger)
+                };
+                let (x_smaller, x_larger) = fit(image.0, tile.0);
+                let (y_smaller, y_larger) = fit(image.1, tile.1);
+                if (x_smaller, y_smaller) == (x_larger, y_larger) {
+                    write!(f, "; expected image size: (x: {x_larger}, y: {y_larger})")
+                } else {
+                    write!(f, "; expected image size such as (x: {x_smaller}, y: {y_smaller}) or (x: {x_larger}, y: {y_larger})")
+                }
             }
             VisErrorKind::NoPixelsForIdent(tile_id) => write!(
               f,
@@ -78,6 +95,8 @@ impl<const WIDTH: usize, const HEIGHT: usize> Display for VisError<WIDTH, HEIGHT
     }
 }
 
+impl<const WIDTH: usize, const HEIGHT: usize> Error for VisError<WIDTH, HEIGHT> {}
+
 #[derive(Debug, Clone, Copy)]
 enum VisErrorKind {
     NonExistingTile(GridPosition),
@@ -90,9 +109,9 @@ enum VisErrorKind {
         tile_pos: GridPosition,
         pixel_pos: (u32, u32),
     },
-    WrongSizeGridLoad {
-        x: u32,
-        y: u32,
+    DimensionMismatch {
+        image: (u32, u32),
+        tile: (usize, usize),
     },
     WrongSizeGridSave {
         expected: (u32, u32),
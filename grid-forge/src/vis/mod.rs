@@ -1,8 +1,9 @@
 //! Allows operating on image representations of [`GridMap2D`](crate::map::GridMap2D)
 
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::hash::Hash;
 
 use image::{ImageBuffer, Luma, LumaA, Pixel, Rgb, Rgba};
+use num_traits::ToPrimitive;
 
 use crate::tile::{GridPosition, GridTile, GridTileRef, GridTileRefMut, TileContainer, TileData};
 
@@ -11,6 +12,8 @@ use self::error::VisError;
 pub mod collection;
 pub mod error;
 pub mod ops;
+#[cfg(all(feature = "gen", feature = "png"))]
+pub mod subscriber;
 
 /// Visual representation of tile which is empty.
 #[derive(Debug, Clone, Copy)]
@@ -29,12 +32,41 @@ where
     P: PixelWithDefault + Hash,
 {
     pub fn new(pixels: [[P; WIDTH]; HEIGHT]) -> Self {
-        let mut hasher = DefaultHasher::default();
-        pixels.hash(&mut hasher);
-        let tile_id = hasher.finish();
+        let tile_id = stable_pixels_id(&pixels);
 
         Self { tile_id, pixels }
     }
+
+    pub fn tile_id(&self) -> u64 {
+        self.tile_id
+    }
+}
+
+/// Generates identifier of the pixels, which is stable across runs, platforms and compiler versions.
+///
+/// It is calculated as FNV-1a hash of the value of each subpixel.
+pub(crate) fn stable_pixels_id<P, const WIDTH: usize, const HEIGHT: usize>(
+    pixels: &[[P; WIDTH]; HEIGHT],
+) -> u64
+where
+    P: Pixel,
+{
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for pixel in pixels.iter().flatten() {
+        for subpixel in pixel.channels() {
+            let value = subpixel
+                .to_f64()
+                .expect("subpixel should be representable as `f64`");
+            for byte in value.to_bits().to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+    }
+    hash
 }
 
 /// Writes pixels array into image buffer at provided [GridPosition].
@@ -382,4 +414,132 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn auto_load_rejects_incompatible_dims() {
+        use crate::tile::identifiable::{builders::IdentTileTraitBuilder, BasicIdentTileData};
+        use crate::vis::{collection::VisCollection, ops::load_gridmap_identifiable_auto};
+
+        let image = ImageBuffer::<DefaultVisPixel, _>::new(5, 4);
+        assert!(VisCollection::<DefaultVisPixel, 2, 2>::verify_pixel_dims(&image).is_err());
+
+        let mut collection = VisCollection::<DefaultVisPixel, 2, 2>::default();
+        let builder = IdentTileTraitBuilder::<BasicIdentTileData>::default();
+        let Err(err) = load_gridmap_identifiable_auto(&image, &mut collection, &builder) else {
+            panic!("image of size 5x4 should not load with 2x2 tiles");
+        };
+        let message = err.to_string();
+        assert!(message.contains("x: 5") && message.contains("y: 4"));
+
+        let image = ImageBuffer::<DefaultVisPixel, _>::new(4, 4);
+        assert!(VisCollection::<DefaultVisPixel, 2, 2>::verify_pixel_dims(&image).is_ok());
+    }
+
+    #[test]
+    fn write_with_empty_paints_empty_positions() {
+        use crate::map::{GridMap2D, GridSize};
+        use crate::tile::identifiable::builders::ConstructableViaIdentifierTile;
+        use crate::tile::identifiable::BasicIdentTileData;
+        use crate::vis::{collection::VisCollection, ops::write_gridmap_identifiable_with_empty};
+
+        let mut collection = VisCollection::<DefaultVisPixel, 2, 2>::default();
+        collection.add_tile_pixels_manual(1, PIX_ARRAYS[1]);
+
+        let mut map = GridMap2D::new(GridSize::new_xy(2, 2));
+        map.insert_tile(GridTile::new(
+            GridPosition::new_xy(1, 0),
+            BasicIdentTileData::tile_new(1),
+        ));
+
+        let mut image = ImageBuffer::new(4, 4);
+        write_gridmap_identifiable_with_empty(&mut image, &map, &collection, PIX_ARRAYS[3])
+            .unwrap();
+
+        for (position, expected) in [
+            (GridPosition::new_xy(0, 0), PIX_ARRAYS[3]),
+            (GridPosition::new_xy(1, 0), PIX_ARRAYS[1]),
+            (GridPosition::new_xy(1, 1), PIX_ARRAYS[3]),
+        ] {
+            let mut pixels = [[DefaultVisPixel::pix_default(); 2]; 2];
+            read_tile(&mut pixels, &image, &position).unwrap();
+            assert_eq!(expected, pixels);
+        }
+
+        let mut wrong_size = ImageBuffer::new(4, 2);
+        assert!(write_gridmap_identifiable_with_empty(
+            &mut wrong_size,
+            &map,
+            &collection,
+            PIX_ARRAYS[3]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn register_from_map_skips_known_types() {
+        use crate::map::{GridMap2D, GridSize};
+        use crate::tile::identifiable::IdentifiableTileData;
+        use crate::vis::collection::VisCollection;
+
+        struct ColoredTile(u64);
+
+        impl TileData for ColoredTile {}
+
+        impl IdentifiableTileData for ColoredTile {
+            fn tile_type_id(&self) -> u64 {
+                self.0
+            }
+        }
+
+        impl VisTileData<DefaultVisPixel, 2, 2> for ColoredTile {
+            fn vis_pixels(&self) -> [[DefaultVisPixel; 2]; 2] {
+                PIX_ARRAYS[self.0 as usize]
+            }
+        }
+
+        let mut map = GridMap2D::new(GridSize::new_xy(3, 1));
+        for (x, id) in [(0, 1), (1, 2), (2, 1)] {
+            map.insert_data(&GridPosition::new_xy(x, 0), ColoredTile(id));
+        }
+
+        let mut collection = VisCollection::<DefaultVisPixel, 2, 2>::default();
+        collection.add_tile_pixels_manual(2, PIX_ARRAYS[3]);
+
+        assert_eq!(1, collection.register_from_map(&map));
+        assert_eq!(Some(&PIX_ARRAYS[1]), collection.get_pixels_by_tile_id(1));
+        assert_eq!(Some(&PIX_ARRAYS[3]), collection.get_pixels_by_tile_id(2));
+        assert_eq!(0, collection.register_from_map(&map));
+    }
+
+    #[test]
+    fn legend_is_ordered_by_tile_id() {
+        use crate::vis::collection::VisCollection;
+
+        let mut collection = VisCollection::<DefaultVisPixel, 2, 2>::default();
+        collection.add_tile_pixels_manual(7, PIX_ARRAYS[0]);
+        collection.add_tile_pixels_manual(2, PIX_ARRAYS[1]);
+        collection.add_tile_pixels_manual(4, PIX_ARRAYS[2]);
+
+        let legend = collection.write_legend(None);
+        assert_eq!((4, 4), legend.dimensions());
+        for (position, expected) in [
+            (GridPosition::new_xy(0, 0), PIX_ARRAYS[1]),
+            (GridPosition::new_xy(1, 0), PIX_ARRAYS[2]),
+            (GridPosition::new_xy(0, 1), PIX_ARRAYS[0]),
+        ] {
+            let mut pixels = [[DefaultVisPixel::pix_default(); 2]; 2];
+            read_tile(&mut pixels, &legend, &position).unwrap();
+            assert_eq!(expected, pixels);
+        }
+
+        assert_eq!((6, 2), collection.write_legend(Some(3)).dimensions());
+        assert_eq!((6, 2), collection.write_legend(Some(10)).dimensions());
+        assert_eq!((2, 6), collection.write_legend(Some(0)).dimensions());
+    }
+
+    #[test]
+    fn empty_tile_id_is_stable() {
+        let empty = super::EmptyTile::new(PIX_ARRAYS[0]);
+        assert_eq!(12289599435391639567, empty.tile_id());
+    }
 }
@@ -1,7 +1,5 @@
 //! Various IO operations transforming between [`GridMap2D`] and [`ImageBuffer`] representation of grid map.
 
-use std::hash::{DefaultHasher, Hash, Hasher};
-
 use image::{ImageBuffer, Pixel};
 
 use crate::map::{GridMap2D, GridSize};
@@ -10,7 +8,7 @@ use crate::tile::identifiable::IdentifiableTileData;
 
 use super::collection::VisCollection;
 use super::error::VisError;
-use super::{PixelWithDefault, VisTile2D, VisTileData};
+use super::{stable_pixels_id, write_tile, EmptyTile, PixelWithDefault, VisTile2D, VisTileData};
 
 /// Easily load [`GridMap2D`] of [`IdentifiableTileData`]-implementing TileData, automatically saving each tile into provided
 /// [`VisCollection`].
@@ -22,9 +20,37 @@ use super::{PixelWithDefault, VisTile2D, VisTileData};
 /// - `builder` - a struct which can be used to construct new tiles on basis of their `tile_id`. One of [`IdentTileBuilder`]
 /// implementing objects.
 ///
+/// Image dimensions are verified with [`check_grid_vis_size`] before any tile is read, so an image which
+/// cannot be split into whole tiles results in [`VisError`] rather than a failure mid-read.
+///
+/// # Pixel types
+/// Any [`PixelWithDefault`] can be used, so the image doesn't need to be converted into RGB beforehand. With
+/// [`Rgba`](image::Rgba) pixels the alpha channel is a part of tile identity - tiles differing only in transparency
+/// get different `tile_type_id`:
+///
+/// ```no_run
+/// use grid_forge::vis::collection::VisCollection;
+/// use grid_forge::vis::ops::load_gridmap_identifiable_auto;
+/// use grid_forge::identifiable::BasicIdentTileData;
+/// use grid_forge::identifiable::builders::IdentTileTraitBuilder;
+///
+/// let image = image::open("sprites.png").unwrap().into_rgba8();
+/// let mut collection = VisCollection::<image::Rgba<u8>, 4, 4>::default();
+/// let builder = IdentTileTraitBuilder::<BasicIdentTileData>::default();
+///
+/// let map = load_gridmap_identifiable_auto(&image, &mut collection, &builder).unwrap();
+/// ```
+///
 /// # Warning
 /// As the `tile_type_id` **is automatically calculated** with this function on basis of pixels, it won't work with specific,
 /// manually declared identifiers. In this case, you need to use [`load_gridmap_identifiable_manual`].
+///
+/// # Breaking change
+/// Automatically calculated `tile_type_id` is now a FNV-1a hash of the pixels, stable across runs, platforms and
+/// compiler versions, instead of the [`DefaultHasher`](std::collections::hash_map::DefaultHasher) output. The
+/// identifiers of all tiles, including the one registered for the empty tile in [`VisCollection`], differ from the
+/// ones generated by previous versions - if you persisted them (eg. in saved maps or adjacency rules), regenerate them
+/// by loading the source images again.
 pub fn load_gridmap_identifiable_auto<Data, P, B, const WIDTH: usize, const HEIGHT: usize>(
     image_buffer: &ImageBuffer<P, Vec<P::Subpixel>>,
     collection: &mut VisCollection<P, WIDTH, HEIGHT>,
@@ -38,7 +64,7 @@ where
     let size = check_grid_vis_size(image_buffer)?;
     let mut grid = GridMap2D::<Data>::new(size);
 
-    for position in size.get_all_possible_positions() {
+    for position in size.iter_all_positions() {
         let pixels = VisCollection::<P, WIDTH, HEIGHT>::read_pixels_for_tile_at_pos(
             image_buffer,
             &position,
@@ -77,7 +103,7 @@ where
     let size = check_grid_vis_size(image_buffer)?;
     let mut grid = GridMap2D::<Data>::new(size);
 
-    for position in size.get_all_possible_positions() {
+    for position in size.iter_all_positions() {
         let pixels = VisCollection::<P, WIDTH, HEIGHT>::read_pixels_for_tile_at_pos(
             image_buffer,
             &position,
@@ -107,6 +133,9 @@ where
 ///
 /// To make sure that the image buffer has exact correct size needed to write the `GridMap2D` representation into it,
 /// you can use [`check_grid_image_size`] beforehand.
+///
+/// Pixels are written as they were registered, so the maps loaded with [`load_gridmap_identifiable_auto`] from images
+/// with alpha channel are written back with the same transparency.
 pub fn write_gridmap_identifiable<Data, P, const WIDTH: usize, const HEIGHT: usize>(
     image_buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
     grid_map: &GridMap2D<Data>,
@@ -121,6 +150,35 @@ where
     Ok(())
 }
 
+/// Write [`GridMap2D`] comprised of tiles containing [`IdentifiableTileData`] into provided [`ImageBuffer`], using
+/// pixel data gathered in [`VisCollection`] and painting `empty_pixels` into every position without a tile.
+///
+/// Useful for visualizing partially-filled maps, eg. in-progress collapse states. Size of the image buffer is checked
+/// upfront with [`check_grid_image_size`], so empty positions never cause an error. Occupied positions still need to
+/// have pixels registered in the `collection`.
+pub fn write_gridmap_identifiable_with_empty<Data, P, const WIDTH: usize, const HEIGHT: usize>(
+    image_buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    grid_map: &GridMap2D<Data>,
+    collection: &VisCollection<P, WIDTH, HEIGHT>,
+    empty_pixels: [[P; WIDTH]; HEIGHT],
+) -> Result<(), VisError<WIDTH, HEIGHT>>
+where
+    Data: IdentifiableTileData,
+    P: PixelWithDefault + 'static,
+{
+    check_grid_image_size(image_buffer, grid_map.size())?;
+    let empty = EmptyTile::new(empty_pixels);
+
+    for position in grid_map.size().iter_all_positions() {
+        match grid_map.get_tile_at_position(&position) {
+            Some(tile) => collection.draw_tile(&tile, image_buffer)?,
+            None => write_tile(image_buffer, position, &empty.pixels)?,
+        }
+    }
+
+    Ok(())
+}
+
 /// Write [`GridMap2D`] comprised of tiles containing [`VisTileData`] into provided [`ImageBuffer`]. Pixel data retrieved
 /// via [`VisTileData::vis_pixels`] will be used.
 ///
@@ -147,11 +205,14 @@ where
 /// Checks the size of the [`ImageBuffer`] while loading [`GridMap2D`] from its visual representation, and produces
 /// the [`GridSize`] inferred from the image size. Results in [`VisError`] if the image size is not compatible
 /// with provided tile size in pixels.
-pub fn check_grid_vis_size<P: Pixel + 'static, const WIDTH: usize, const HEIGHT: usize>(
+pub fn check_grid_vis_size<P: Pixel, const WIDTH: usize, const HEIGHT: usize>(
     image: &ImageBuffer<P, Vec<P::Subpixel>>,
 ) -> Result<GridSize, VisError<WIDTH, HEIGHT>> {
     if image.height() as usize % HEIGHT != 0 || image.width() as usize % WIDTH != 0 {
-        Err(VisError::new_grid_load(image.width(), image.height()))
+        Err(VisError::new_dimension_mismatch((
+            image.width(),
+            image.height(),
+        )))
     } else {
         Ok(GridSize::new_xy(
             image.width() / WIDTH as u32,
@@ -188,7 +249,95 @@ pub(crate) fn create_tile_id_from_pixels<
 >(
     pixels: &[[P; WIDTH]; HEIGHT],
 ) -> u64 {
-    let mut hasher = DefaultHasher::default();
-    pixels.hash(&mut hasher);
-    hasher.finish()
+    stable_pixels_id(pixels)
+}
+
+#[cfg(test)]
+mod test {
+    use image::{ImageBuffer, ImageFormat, Rgba};
+
+    use crate::tile::identifiable::builders::IdentTileTraitBuilder;
+    use crate::tile::identifiable::{BasicIdentTileData, IdentifiableTileData};
+    use crate::tile::GridPosition;
+    use crate::vis::collection::VisCollection;
+
+    use super::{
+        check_grid_vis_size, init_map_image_buffer, load_gridmap_identifiable_auto,
+        load_gridmap_identifiable_manual, write_gridmap_identifiable,
+    };
+
+    #[test]
+    fn mismatched_dimensions_are_reported_upfront() {
+        let image = ImageBuffer::from_pixel(5, 4, Rgba([10, 10, 10, 255]));
+        let mut collection = VisCollection::<Rgba<u8>, 2, 2>::default();
+        let builder = IdentTileTraitBuilder::<BasicIdentTileData>::default();
+
+        let err = load_gridmap_identifiable_auto(&image, &mut collection, &builder).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("(x: 5, y: 4)"));
+        assert!(message.contains("(x: 4, y: 4) or (x: 6, y: 4)"));
+        assert!(load_gridmap_identifiable_manual(&image, &collection, &builder).is_err());
+
+        let err = check_grid_vis_size::<Rgba<u8>, 4, 4>(&ImageBuffer::new(3, 8)).unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("expected image size: (x: 4, y: 8)"));
+    }
+
+    #[test]
+    fn empty_tiles_are_skipped_on_auto_load() {
+        let empty = [[Rgba([0, 0, 0, 0]); 2]; 2];
+        let image = ImageBuffer::from_fn(4, 4, |x, y| match (x / 2, y / 2) {
+            (0, 0) | (1, 1) => Rgba([0, 0, 0, 0]),
+            _ => Rgba([50, 150, 250, 255]),
+        });
+
+        let mut collection = VisCollection::<Rgba<u8>, 2, 2>::default();
+        collection.set_empty_tile_pixels(Some(empty));
+        let builder = IdentTileTraitBuilder::<BasicIdentTileData>::default();
+        let map = load_gridmap_identifiable_auto(&image, &mut collection, &builder).unwrap();
+
+        assert_eq!(
+            vec![GridPosition::new_xy(0, 1), GridPosition::new_xy(1, 0)],
+            map.get_all_positions()
+        );
+        let empty_id = collection.empty_tile_id().unwrap();
+        assert!(collection.get_pixels_by_tile_id(empty_id).is_none());
+        assert!(map
+            .iter_tiles()
+            .all(|tile| tile.as_ref().tile_type_id() != empty_id));
+    }
+
+    #[test]
+    fn rgba_png_roundtrip() {
+        // Tiles at `x` 0 and 1 differ only by the alpha channel.
+        let source = ImageBuffer::from_fn(6, 4, |x, y| match (x / 2, y / 2) {
+            (0, _) => Rgba([200, 100, 0, 255]),
+            (1, _) => Rgba([200, 100, 0, 128]),
+            (_, 0) => Rgba([0, 0, (x * 40) as u8, 255]),
+            _ => Rgba([0, 0, 0, 0]),
+        });
+        let path = std::env::temp_dir().join(format!("grid_forge_rgba_{}.png", std::process::id()));
+        source.save_with_format(&path, ImageFormat::Png).unwrap();
+        let loaded = image::open(&path).unwrap().into_rgba8();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut collection = VisCollection::<Rgba<u8>, 2, 2>::default();
+        let builder = IdentTileTraitBuilder::<BasicIdentTileData>::default();
+        let map = load_gridmap_identifiable_auto(&loaded, &mut collection, &builder).unwrap();
+
+        let type_at = |x, y| {
+            map.get_tile_at_position(&GridPosition::new_xy(x, y))
+                .unwrap()
+                .as_ref()
+                .tile_type_id()
+        };
+        assert_ne!(type_at(0, 0), type_at(1, 0));
+        assert_eq!(type_at(0, 0), type_at(0, 1));
+        assert_ne!(type_at(2, 0), type_at(2, 1));
+
+        let mut written = init_map_image_buffer::<Rgba<u8>, 2, 2>(map.size());
+        write_gridmap_identifiable(&mut written, &map, &collection).unwrap();
+        assert_eq!(source, written);
+    }
 }
@@ -0,0 +1,212 @@
+//! Subscribers rendering the collapse process into images.
+//!
+//! Requires the `png` feature, which enables PNG encoding in the `image` crate.
+
+use std::any::Any;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use image::{EncodableLayout, ImageBuffer, ImageFormat, PixelWithColorType};
+
+use crate::gen::collapse::{overlap, singular};
+use crate::map::GridSize;
+use crate::tile::GridPosition;
+
+use super::collection::VisCollection;
+use super::error::VisError;
+use super::{write_tile, PixelWithDefault};
+
+/// Subscriber writing a PNG frame into provided directory as tiles are collapsed, allowing to render the animation
+/// of the generation process.
+///
+/// Implements both [`overlap::Subscriber`] and [`singular::Subscriber`], making it usable with both resolvers. Pixels
+/// for every collapsed `tile_type_id` are retrieved from the [`VisCollection`], and painted on top of all tiles
+/// collapsed before. By default, frame is written after every collapse - use [`with_frame_stride`](Self::with_frame_stride)
+/// to write only every Nth one.
+///
+/// Frames are named `frame_00000.png`, `frame_00001.png` and so on. Every new generation began by the resolver resets
+/// the image and the frame counter.
+///
+/// As [`Subscriber`](singular::Subscriber) methods cannot return errors, first error encountered while drawing or
+/// writing the frame is retained and can be retrieved with [`error`](Self::error).
+#[derive(Debug)]
+pub struct ImageSequenceSubscriber<P, const WIDTH: usize, const HEIGHT: usize>
+where
+    P: PixelWithDefault + 'static,
+{
+    collection: VisCollection<P, WIDTH, HEIGHT>,
+    size: GridSize,
+    directory: PathBuf,
+    frame_stride: usize,
+    buffer: ImageBuffer<P, Vec<P::Subpixel>>,
+    collapsed: usize,
+    frames: usize,
+    error: Option<Box<dyn Error>>,
+}
+
+impl<P, const WIDTH: usize, const HEIGHT: usize> ImageSequenceSubscriber<P, WIDTH, HEIGHT>
+where
+    P: PixelWithDefault + PixelWithColorType + 'static,
+    [P::Subpixel]: EncodableLayout,
+{
+    /// Creates new subscriber writing frames of map of given `size` into `directory`, using pixels registered in
+    /// the `collection`.
+    pub fn new(
+        collection: VisCollection<P, WIDTH, HEIGHT>,
+        size: GridSize,
+        directory: impl AsRef<Path>,
+    ) -> Self {
+        Self {
+            buffer: collection.init_map_image_buffer(&size),
+            collection,
+            size,
+            directory: directory.as_ref().to_path_buf(),
+            frame_stride: 1,
+            collapsed: 0,
+            frames: 0,
+            error: None,
+        }
+    }
+
+    /// Sets the number of collapses between written frames. Frame is always written after the first collapse.
+    ///
+    /// Stride of `0` is treated as `1`.
+    pub fn with_frame_stride(mut self, frame_stride: usize) -> Self {
+        self.frame_stride = frame_stride.max(1);
+        self
+    }
+
+    /// Returns the number of frames written since the generation started.
+    pub fn frames_written(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns the first error encountered while drawing or writing frames since the generation started.
+    pub fn error(&self) -> Option<&dyn Error> {
+        self.error.as_deref()
+    }
+
+    /// Returns the image containing all tiles collapsed so far.
+    pub fn image(&self) -> &ImageBuffer<P, Vec<P::Subpixel>> {
+        &self.buffer
+    }
+
+    fn reset(&mut self) {
+        self.buffer = self.collection.init_map_image_buffer(&self.size);
+        self.collapsed = 0;
+        self.frames = 0;
+        self.error = None;
+    }
+
+    fn handle_collapse(&mut self, position: &GridPosition, tile_type_id: u64) {
+        if let Err(err) = self.paint(position, tile_type_id) {
+            self.error.get_or_insert(err);
+        }
+    }
+
+    fn paint(&mut self, position: &GridPosition, tile_type_id: u64) -> Result<(), Box<dyn Error>> {
+        let pixels = self
+            .collection
+            .get_pixels_by_tile_id(tile_type_id)
+            .ok_or_else(|| VisError::<WIDTH, HEIGHT>::new_nopix(tile_type_id))?;
+        write_tile(&mut self.buffer, *position, pixels)?;
+
+        self.collapsed += 1;
+        if !(self.collapsed - 1).is_multiple_of(self.frame_stride) {
+            return Ok(());
+        }
+
+        let path = self.directory.join(format!("frame_{:05}.png", self.frames));
+        self.buffer.save_with_format(path, ImageFormat::Png)?;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+impl<P, const WIDTH: usize, const HEIGHT: usize> singular::Subscriber
+    for ImageSequenceSubscriber<P, WIDTH, HEIGHT>
+where
+    P: PixelWithDefault + PixelWithColorType + 'static,
+    [P::Subpixel]: EncodableLayout,
+{
+    fn on_generation_start(&mut self) {
+        self.reset();
+    }
+
+    fn on_collapse(&mut self, position: &GridPosition, tile_type_id: u64) {
+        self.handle_collapse(position, tile_type_id);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl<P, const WIDTH: usize, const HEIGHT: usize> overlap::Subscriber
+    for ImageSequenceSubscriber<P, WIDTH, HEIGHT>
+where
+    P: PixelWithDefault + PixelWithColorType + 'static,
+    [P::Subpixel]: EncodableLayout,
+{
+    fn on_generation_start(&mut self) {
+        self.reset();
+    }
+
+    fn on_collapse(&mut self, position: &GridPosition, tile_type_id: u64, _pattern_id: u64) {
+        self.handle_collapse(position, tile_type_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::gen::collapse::singular::Subscriber;
+    use crate::map::GridSize;
+    use crate::tile::GridPosition;
+    use crate::vis::collection::VisCollection;
+    use crate::vis::{read_tile, DefaultVisPixel, PixelWithDefault};
+
+    use super::ImageSequenceSubscriber;
+
+    #[test]
+    fn writes_every_nth_frame() {
+        let directory =
+            std::env::temp_dir().join(format!("grid_forge_frames_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let first = [[DefaultVisPixel::from([10, 20, 30]); 2]; 2];
+        let second = [[DefaultVisPixel::from([40, 50, 60]); 2]; 2];
+        let mut collection = VisCollection::<DefaultVisPixel, 2, 2>::default();
+        collection.add_tile_pixels_manual(1, first);
+        collection.add_tile_pixels_manual(2, second);
+
+        let mut subscriber =
+            ImageSequenceSubscriber::new(collection, GridSize::new_xy(2, 2), &directory)
+                .with_frame_stride(2);
+        subscriber.on_generation_start();
+        subscriber.on_collapse(&GridPosition::new_xy(0, 0), 1);
+        subscriber.on_collapse(&GridPosition::new_xy(1, 0), 2);
+        subscriber.on_collapse(&GridPosition::new_xy(1, 1), 1);
+
+        assert!(subscriber.error().is_none());
+        assert_eq!(2, subscriber.frames_written());
+
+        let frame = image::open(directory.join("frame_00001.png"))
+            .unwrap()
+            .to_rgb8();
+        for (position, expected) in [
+            (GridPosition::new_xy(0, 0), first),
+            (GridPosition::new_xy(1, 0), second),
+            (GridPosition::new_xy(1, 1), first),
+        ] {
+            let mut pixels = [[DefaultVisPixel::pix_default(); 2]; 2];
+            read_tile(&mut pixels, &frame, &position).unwrap();
+            assert_eq!(expected, pixels);
+        }
+        assert!(!directory.join("frame_00002.png").exists());
+
+        subscriber.on_collapse(&GridPosition::new_xy(0, 1), 3);
+        assert!(subscriber.error().is_some());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}